@@ -0,0 +1,77 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+/// Ed25519 instruction layout: count, padding, then one 14-byte offsets entry
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+/// Instruction index meaning "data is in this same instruction"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(ErrorCode::InvalidShareAttestation)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    Ok(data
+        .get(start..start + len)
+        .ok_or(ErrorCode::InvalidShareAttestation)?)
+}
+
+/// Verify the instruction right before the current one is an Ed25519 signature
+/// check of `message` by `signer`. The Ed25519 program has already verified the
+/// signature itself, so only its signer and message need to match.
+pub fn verify_ed25519_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::ShareAttestationRequired);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        ErrorCode::ShareAttestationRequired
+    );
+
+    // Exactly one signature, with every part inside the Ed25519 instruction itself
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        ErrorCode::InvalidShareAttestation
+    );
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_ix = read_u16(data, offsets + 2)?;
+    let pubkey_offset = read_u16(data, offsets + 4)?;
+    let pubkey_ix = read_u16(data, offsets + 6)?;
+    let message_offset = read_u16(data, offsets + 8)?;
+    let message_len = read_u16(data, offsets + 10)?;
+    let message_ix = read_u16(data, offsets + 12)?;
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        ErrorCode::InvalidShareAttestation
+    );
+
+    require!(
+        read_slice(data, pubkey_offset, PUBKEY_LEN)? == signer.as_ref(),
+        ErrorCode::InvalidShareAttestation
+    );
+    require!(
+        read_slice(data, message_offset, message_len as usize)? == message,
+        ErrorCode::InvalidShareAttestation
+    );
+
+    Ok(())
+}
@@ -19,10 +19,66 @@ pub enum ErrorCode {
     InvalidAmount,
     #[msg("Exceed daily incense limit")]
     ExceedDailyIncenseLimit,
+    #[msg("Incense type is on cooldown")]
+    IncenseOnCooldown,
     #[msg("Insufficient incense balance")]
     InsufficientIncenseBalance,
+    #[msg("Incense inventory has no free slot")]
+    IncenseInventoryFull,
     #[msg("This incense type is only available through donations")]
     DonationOnlyIncense,
+    #[msg("Incense batch is empty, too large or repeats an incense type")]
+    InvalidIncenseBatch,
+    #[msg("Remaining accounts do not match the incense batch")]
+    InvalidBatchAccounts,
+    #[msg("Invalid incense beneficiary")]
+    InvalidBeneficiary,
+    #[msg("Invalid streak multiplier config")]
+    InvalidStreakConfig,
+    #[msg("Invalid altar config")]
+    InvalidAltarConfig,
+    #[msg("Invalid payment mint config")]
+    InvalidPaymentMintConfig,
+    #[msg("Payment mint not accepted")]
+    PaymentMintNotAccepted,
+    #[msg("Invalid token payment accounts")]
+    InvalidPaymentAccounts,
+    #[msg("No price set in this payment mint")]
+    TokenPriceNotSet,
+    #[msg("Insufficient token balance")]
+    InsufficientTokenBalance,
+    #[msg("SOL/USD price feed required for USD prices")]
+    PriceFeedRequired,
+    #[msg("Invalid SOL/USD price feed")]
+    InvalidPriceFeed,
+    #[msg("SOL/USD price is stale")]
+    StalePriceFeed,
+    #[msg("SOL/USD price confidence too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Invalid metadata config")]
+    InvalidMetadataConfig,
+    #[msg("Invalid activity economy config")]
+    InvalidActivityEconomyConfig,
+    #[msg("Fortune draw not found in history")]
+    FortuneNotFound,
+    #[msg("Share attestation required")]
+    ShareAttestationRequired,
+    #[msg("Invalid share attestation")]
+    InvalidShareAttestation,
+    #[msg("Invalid fortune resolve config")]
+    InvalidFortuneResolveConfig,
+    #[msg("Only bad fortunes can be resolved")]
+    FortuneNotResolvable,
+    #[msg("Fortune already resolved")]
+    FortuneAlreadyResolved,
+    #[msg("Fortune resolve window expired")]
+    ResolveWindowExpired,
+    #[msg("Resolve payment method not enabled")]
+    ResolvePaymentDisabled,
+    #[msg("Daily reroll limit exceeded")]
+    RerollLimitExceeded,
+    #[msg("Invalid fortune set")]
+    InvalidFortuneSet,
     #[msg("Failed to trigger special effect")]
     SpecialEffectFailed,
     #[msg("Insufficient merit points")]
@@ -69,6 +125,8 @@ pub enum ErrorCode {
     InvalidIncenseType,
     #[msg("Invalid fortune config")]
     InvalidFortuneConfig,
+    #[msg("Invalid timezone offset or day boundary hour")]
+    InvalidDayBoundary,
     #[msg("Invalid donation level")]
     InvalidDonationLevel,
     #[msg("Invalid temple level")]
@@ -105,6 +163,16 @@ pub enum ErrorCode {
     // 御守相关错误
     #[msg("Insufficient pending amulets balance")]
     InsufficientPendingAmulets,
+    #[msg("Invalid amulet type")]
+    InvalidAmuletType,
+    #[msg("Amulet not held by user")]
+    InvalidAmuletOwner,
+    #[msg("Amulet type does not match this buff")]
+    AmuletTypeMismatch,
+    #[msg("Amulet has no charges left")]
+    AmuletDepleted,
+    #[msg("Amulet is on cooldown")]
+    AmuletOnCooldown,
 
     // 商城相关错误
     #[msg("Insufficient stock for the item")]
@@ -123,4 +191,24 @@ pub enum ErrorCode {
     WishTowerLevelFull,
     #[msg("Wish not owned by user")]
     WishNotOwnedByUser,
+
+    // 迁移相关错误
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+
+    // 回向相关错误
+    #[msg("Invalid dedication config")]
+    InvalidDedicationConfig,
+
+    // 元数据相关错误
+    #[msg("Rendered NFT metadata exceeds the token metadata limits")]
+    MetadataTooLong,
+
+    // 签文集相关错误
+    #[msg("Fortune NFT is part of a claimed fortune set")]
+    FortuneInSet,
+
+    // 改签相关错误
+    #[msg("A reroll of this fortune is still pending")]
+    RerollPending,
 }
@@ -0,0 +1,104 @@
+use crate::error::ErrorCode;
+use crate::state::altar::{Altar, AltarStick};
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+#[instruction(slot_count: u16)]
+pub struct CreateAltar<'info> {
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [Altar::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+        payer = owner,
+        space = Altar::space(slot_count)
+    )]
+    pub altar: Box<Account<'info, Altar>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeAltar<'info> {
+    #[account(
+        mut,
+        seeds = [Altar::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = altar.bump,
+    )]
+    pub altar: Box<Account<'info, Altar>>,
+
+    #[account(
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_altar(ctx: Context<CreateAltar>, slot_count: u16) -> Result<()> {
+    require!(
+        slot_count > 0 && slot_count <= Altar::MAX_SLOTS,
+        ErrorCode::InvalidAltarConfig
+    );
+
+    let altar = &mut ctx.accounts.altar;
+    altar.temple_config = ctx.accounts.temple_config.key();
+    altar.head = 0;
+    altar.total_sticks_lit = 0;
+    altar.bump = ctx.bumps.altar;
+    altar.sticks = vec![AltarStick::default(); slot_count as usize];
+
+    msg!("Altar created with {} slots", slot_count);
+    Ok(())
+}
+
+pub fn resize_altar(ctx: Context<ResizeAltar>, slot_count: u16) -> Result<()> {
+    require!(
+        slot_count > 0 && slot_count <= Altar::MAX_SLOTS,
+        ErrorCode::InvalidAltarConfig
+    );
+
+    let altar = &mut ctx.accounts.altar;
+    altar.set_slot_count(slot_count);
+
+    // Grow the account when the ring outgrows its allocation
+    let required_space = Altar::space(slot_count);
+    let altar_info = altar.to_account_info();
+    if required_space > altar_info.data_len() {
+        let rent_due = Rent::get()?
+            .minimum_balance(required_space)
+            .saturating_sub(altar_info.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: altar_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        altar_info.resize(required_space)?;
+    }
+
+    msg!("Altar resized to {} slots", slot_count);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::error::ErrorCode;
+use crate::state::fortune_table::{FortuneTable, FortuneTier};
+use crate::state::temple_config::{FortuneConfig, TempleConfig};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateFortuneTable<'info> {
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + FortuneTable::INIT_SPACE
+    )]
+    pub fortune_table: Box<Account<'info, FortuneTable>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_fortune_table(
+    ctx: Context<CreateFortuneTable>,
+    regular_fortune: FortuneConfig,
+    buddha_fortune: FortuneConfig,
+) -> Result<()> {
+    let fortune_table = &mut ctx.accounts.fortune_table;
+    fortune_table.temple_config = ctx.accounts.temple_config.key();
+    fortune_table.bump = ctx.bumps.fortune_table;
+    fortune_table.tiers = FortuneTier::default_tiers(&regular_fortune, &buddha_fortune);
+
+    msg!("Fortune table created with the default five tiers");
+    Ok(())
+}
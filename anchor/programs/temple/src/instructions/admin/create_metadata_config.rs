@@ -0,0 +1,69 @@
+use crate::error::ErrorCode;
+use crate::state::metadata_config::{MetadataConfig, NftFamily, NftMetadataTemplate};
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CreateMetadataConfig<'info> {
+    #[account(
+        mut,
+        address = crate::admin::ID @ ErrorCode::InvalidOwner
+    )]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+        payer = owner,
+        space = 8 + MetadataConfig::INIT_SPACE
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadataConfig<'info> {
+    #[account(
+        mut,
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    #[account(
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn create_metadata_config(ctx: Context<CreateMetadataConfig>) -> Result<()> {
+    let temple_config_key = ctx.accounts.temple_config.key();
+    ctx.accounts
+        .metadata_config
+        .initialize(temple_config_key, ctx.bumps.metadata_config);
+
+    msg!("Metadata config created with default templates");
+    Ok(())
+}
+
+pub fn update_metadata_template(
+    ctx: Context<UpdateMetadataConfig>,
+    family: NftFamily,
+    template: NftMetadataTemplate,
+) -> Result<()> {
+    template.validate()?;
+    ctx.accounts.metadata_config.set_template(family, template);
+
+    msg!("Updated {:?} metadata template", family);
+    Ok(())
+}
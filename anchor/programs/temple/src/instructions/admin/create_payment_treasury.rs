@@ -0,0 +1,51 @@
+use crate::error::ErrorCode;
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Create the temple's treasury ATA receiving payments in a whitelisted mint
+#[derive(Accounts)]
+pub struct CreatePaymentTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    /// Treasury ATA owned by the temple config PDA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = payment_mint,
+        associated_token::authority = temple_config,
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_payment_treasury(ctx: Context<CreatePaymentTreasury>) -> Result<()> {
+    require!(
+        ctx.accounts
+            .temple_config
+            .find_payment_mint(&ctx.accounts.payment_mint.key())
+            .is_some(),
+        ErrorCode::PaymentMintNotAccepted
+    );
+
+    msg!(
+        "Payment treasury for mint {} at {}",
+        ctx.accounts.payment_mint.key(),
+        ctx.accounts.treasury_token_account.key()
+    );
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use crate::error::ErrorCode;
+use crate::instructions::shop::purchase_item::STREAK_PROTECTION_ITEM_ID;
 use crate::state::event::ShopConfigUpdated;
 use crate::state::shop_config::ShopConfig;
+use crate::state::shop_item::{ShopItem, ShopItemType};
 use crate::state::temple_config::TempleConfig;
 use anchor_lang::prelude::*;
 
@@ -32,14 +34,36 @@ pub struct CreateShopConfig<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+// Streak protection card, seeded when the item list leaves it out
+fn streak_protection_item() -> ShopItem {
+    ShopItem {
+        id: STREAK_PROTECTION_ITEM_ID,
+        name: "Streak Protection".to_string(),
+        description: "Saves a burning streak across one missed day".to_string(),
+        price: 10000000, // 0.01 SOL
+        price_usd_cents: None,
+        item_type: ShopItemType::Prop,
+        stock: 1000000,
+        is_available: true,
+        incense_config: None,
+    }
+}
+
 pub fn create_shop_config(
     ctx: Context<CreateShopConfig>,
-    shop_items: Vec<crate::state::shop_item::ShopItem>,
+    mut shop_items: Vec<crate::state::shop_item::ShopItem>,
 ) -> Result<()> {
     let shop_config = &mut ctx.accounts.shop_config;
     let temple_config = &ctx.accounts.temple_config;
     let clock = Clock::get()?;
 
+    if !shop_items
+        .iter()
+        .any(|item| item.id == STREAK_PROTECTION_ITEM_ID)
+    {
+        shop_items.push(streak_protection_item());
+    }
+
     // Initialize system configuration
     shop_config.temple_config = temple_config.key();
     shop_config.owner = ctx.accounts.owner.key();
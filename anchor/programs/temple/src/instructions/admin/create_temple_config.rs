@@ -4,7 +4,7 @@ use crate::state::temple_config::*;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
-#[instruction(treasury: Pubkey, regular_fortune: FortuneConfig, buddha_fortune: FortuneConfig, donation_levels: Vec<DonationLevelConfig>, donation_rewards: Vec<DonationRewardConfig>, temple_levels: Vec<TempleLevelConfig>)]
+#[instruction(treasury: Pubkey, donation_levels: Vec<DonationLevelConfig>, donation_rewards: Vec<DonationRewardConfig>, temple_levels: Vec<TempleLevelConfig>)]
 pub struct CreateTempleConfig<'info> {
     #[account(
         mut,
@@ -37,8 +37,6 @@ pub struct CreateTempleConfig<'info> {
 pub fn create_temple_config(
     ctx: Context<CreateTempleConfig>,
     treasury: Pubkey,
-    regular_fortune: FortuneConfig,
-    buddha_fortune: FortuneConfig,
     donation_levels: Vec<DonationLevelConfig>,
     donation_rewards: Vec<DonationRewardConfig>,
     temple_levels: Vec<TempleLevelConfig>,
@@ -55,6 +53,8 @@ pub fn create_temple_config(
     temple_config.total_buddha_nft = 0;
     temple_config.status = 0;
     temple_config.open_time = clock.unix_timestamp as u64; // onlin time
+    temple_config.utc_offset_minutes = TempleConfig::DEFAULT_UTC_OFFSET_MINUTES;
+    temple_config.day_boundary_hour = 0; // Local midnight
 
     // Initialize dynamic configuration
     temple_config.dynamic_config = DynamicConfig {
@@ -63,53 +63,81 @@ pub fn create_temple_config(
                 id: 1,
                 name: "Fresh".to_string(),
                 price_lamports: 10000000, // 0.01 SOL
+                price_usd_cents: None,
                 merit: 10,
                 incense_points: 100,
                 is_donation: false,
+                daily_cap: 10,
+                cooldown_seconds: 0,
+                burn_duration_seconds: 1800, // 30 minutes
+                holder_cap: None,
             },
             IncenseType {
                 id: 2,
                 name: "Sandalwood".to_string(),
                 price_lamports: 50000000, // 0.05 SOL
+                price_usd_cents: None,
                 merit: 65,
                 incense_points: 600,
                 is_donation: false,
+                daily_cap: 10,
+                cooldown_seconds: 0,
+                burn_duration_seconds: 1800, // 30 minutes
+                holder_cap: None,
             },
             IncenseType {
                 id: 3,
                 name: "Ambergris".to_string(),
                 price_lamports: 100000000, // 0.1 SOL
+                price_usd_cents: None,
                 merit: 1200,
                 incense_points: 3100,
                 is_donation: false,
+                daily_cap: 10,
+                cooldown_seconds: 0,
+                burn_duration_seconds: 1800, // 30 minutes
+                holder_cap: None,
             },
             IncenseType {
                 id: 4,
                 name: "Supreme Spirit".to_string(),
                 price_lamports: 300000000, // 0.3 SOL
+                price_usd_cents: None,
                 merit: 3400,
                 incense_points: 9000,
                 is_donation: false,
+                daily_cap: 10,
+                cooldown_seconds: 0,
+                burn_duration_seconds: 1800, // 30 minutes
+                holder_cap: None,
             },
             IncenseType {
                 id: 5,
                 name: "Secret Brew Incense".to_string(),
                 price_lamports: 5000000000, // 5 SOL (placeholder, not used for purchase)
+                price_usd_cents: None,
                 merit: 12000,
                 incense_points: 10000,
                 is_donation: true,
+                daily_cap: 10,
+                cooldown_seconds: 0,
+                burn_duration_seconds: 1800, // 30 minutes
+                holder_cap: None,
             },
             IncenseType {
                 id: 6,
                 name: "Celestial Incense".to_string(),
                 price_lamports: 50000000000, // 50 SOL (placeholder, not used for purchase)
+                price_usd_cents: None,
                 merit: 300000,
                 incense_points: 400000,
                 is_donation: true,
+                daily_cap: 10,
+                cooldown_seconds: 0,
+                burn_duration_seconds: 1800, // 30 minutes
+                holder_cap: None,
             },
         ],
-        regular_fortune: regular_fortune.clone(),
-        buddha_fortune: buddha_fortune.clone(),
         donation_levels: donation_levels.clone(),
         donation_rewards: donation_rewards.clone(),
         temple_levels: temple_levels.clone(),
@@ -133,6 +161,14 @@ pub fn create_temple_config(
                 is_donation_only: true,
             },
         ],
+        streak_multipliers: StreakMultiplier::default_tiers(),
+        payment_mints: vec![],
+        activity_economy: ActivityEconomyConfig::default(),
+        fortune_resolve: FortuneResolveConfig::default(),
+        fortune_reroll: FortuneRerollConfig::default(),
+        fortune_set: FortuneSetConfig::default(),
+        fortune_release: FortuneReleaseConfig::default(),
+        dedication: DedicationConfig::default(),
     };
 
     // Global State
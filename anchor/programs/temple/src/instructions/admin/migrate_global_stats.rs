@@ -0,0 +1,74 @@
+use crate::error::ErrorCode;
+use crate::state::global_stats::GlobalStats;
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+#[derive(Accounts)]
+pub struct MigrateGlobalStats<'info> {
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Global stats allocated before token payment totals were appended, grown here
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub global_stats: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Grow global stats to the current size, the admin funds the extra rent.
+// The zeroed tail decodes as no token payment totals.
+pub fn migrate_global_stats(ctx: Context<MigrateGlobalStats>) -> Result<()> {
+    let global_stats_info = ctx.accounts.global_stats.to_account_info();
+    let legacy_len = global_stats_info.data_len();
+    let required_space = 8 + GlobalStats::INIT_SPACE;
+    require!(legacy_len < required_space, ErrorCode::AlreadyMigrated);
+    require!(
+        global_stats_info.try_borrow_data()?[..8] == *GlobalStats::DISCRIMINATOR,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let rent_due = Rent::get()?
+        .minimum_balance(required_space)
+        .saturating_sub(global_stats_info.lamports());
+    if rent_due > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: global_stats_info.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    global_stats_info.resize(required_space)?;
+
+    {
+        let mut data = global_stats_info.try_borrow_mut_data()?;
+        data[legacy_len..].fill(0);
+    }
+    let global_stats =
+        GlobalStats::try_deserialize(&mut &global_stats_info.try_borrow_data()?[..])?;
+
+    msg!(
+        "Global stats grown to {} bytes, {} token payment totals",
+        required_space,
+        global_stats.token_payments.len()
+    );
+    Ok(())
+}
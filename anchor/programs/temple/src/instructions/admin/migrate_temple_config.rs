@@ -0,0 +1,77 @@
+use crate::error::ErrorCode;
+use crate::state::legacy::LegacyTempleConfig;
+use crate::state::temple_config::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct MigrateTempleConfig<'info> {
+    /// CHECK: Temple config on the first release layout, decoded through LegacyTempleConfig
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Rewrite a first release temple config in the current layout, the admin funds any extra rent
+pub fn migrate_temple_config(ctx: Context<MigrateTempleConfig>) -> Result<()> {
+    let temple_config_info = ctx.accounts.temple_config.to_account_info();
+
+    let legacy = {
+        let data = temple_config_info.try_borrow_data()?;
+        // Configs already on the current layout have nothing to migrate
+        require!(
+            TempleConfig::try_deserialize(&mut &data[..]).is_err(),
+            ErrorCode::AlreadyMigrated
+        );
+        LegacyTempleConfig::decode(&data)?
+    };
+    require_keys_eq!(
+        legacy.owner,
+        ctx.accounts.authority.key(),
+        ErrorCode::Unauthorized
+    );
+
+    // The fortune probabilities now live in the fortune table
+    msg!(
+        "Legacy fortune probabilities, pass them to create_fortune_table: regular {:?}, buddha {:?}",
+        legacy.dynamic_config.regular_fortune,
+        legacy.dynamic_config.buddha_fortune
+    );
+
+    let temple_config = legacy.upgrade();
+    let required_space = 8 + borsh::to_vec(&temple_config)?.len();
+    if required_space > temple_config_info.data_len() {
+        let rent_due = Rent::get()?
+            .minimum_balance(required_space)
+            .saturating_sub(temple_config_info.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: temple_config_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        temple_config_info.resize(required_space)?;
+    }
+
+    let mut data = temple_config_info.try_borrow_mut_data()?;
+    data.fill(0);
+    temple_config.try_serialize(&mut &mut data[..])?;
+
+    msg!("Temple config migrated to the current layout");
+    Ok(())
+}
@@ -2,6 +2,16 @@ pub mod create_temple_config;
 pub use create_temple_config::*;
 pub mod create_shop_config;
 pub use create_shop_config::*;
+pub mod create_altar;
+pub use create_altar::*;
+pub mod create_metadata_config;
+pub use create_metadata_config::*;
+pub mod create_fortune_table;
+pub use create_fortune_table::*;
+pub mod create_payment_treasury;
+pub use create_payment_treasury::*;
+pub mod withdraw_token_treasury;
+pub use withdraw_token_treasury::*;
 pub mod create_nft_mint;
 pub use create_nft_mint::*;
 pub mod mint_nft_to_user;
@@ -16,3 +26,7 @@ pub mod update_dynamic_config;
 pub use update_dynamic_config::*;
 pub mod update_temple_status;
 pub use update_temple_status::*;
+pub mod migrate_temple_config;
+pub use migrate_temple_config::*;
+pub mod migrate_global_stats;
+pub use migrate_global_stats::*;
@@ -1,5 +1,6 @@
 use crate::error::ErrorCode;
 use crate::state::event::ShopConfigUpdated;
+use crate::state::fortune_table::{FortuneTable, FortuneTier};
 use crate::state::shop_config::ShopConfig;
 use crate::state::shop_item::ShopItem;
 use crate::state::temple_config::*;
@@ -17,6 +18,43 @@ pub struct UpdateDynamicConfig<'info> {
     pub authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateFortuneTiers<'info> {
+    #[account(
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, written in place by FortuneTable::write_tiers
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Dynamic config sections that may outgrow the config's allocation
+#[derive(Accounts)]
+pub struct UpdateConfigSection<'info> {
+    #[account(
+        mut,
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateShopItems<'info> {
     #[account(
@@ -45,10 +83,14 @@ pub fn update_incense_types(
     let temple_config = &mut ctx.accounts.temple_config;
 
     // Validate incense type data
-    for incense in &incense_types {
-        require!(incense.id > 0, ErrorCode::InvalidIncenseType);
-        require!(!incense.name.is_empty(), ErrorCode::InvalidIncenseType);
-        require!(incense.price_lamports > 0, ErrorCode::InvalidIncenseType);
+    for (i, incense) in incense_types.iter().enumerate() {
+        incense.validate()?;
+        require!(
+            incense_types[..i]
+                .iter()
+                .all(|other| other.id != incense.id),
+            ErrorCode::InvalidIncenseType
+        );
     }
 
     // Update incense types configuration
@@ -58,35 +100,62 @@ pub fn update_incense_types(
     Ok(())
 }
 
-// 2. Update fortune configuration
-pub fn update_fortune_config(
-    ctx: Context<UpdateDynamicConfig>,
-    regular_fortune: FortuneConfig,
-    buddha_fortune: FortuneConfig,
+// 2. Update fortune tiers
+// Tables too large for one transaction are written in chunks starting at `offset`;
+// everything from `offset` on is replaced. Draws must stay paused until the last chunk lands.
+pub fn update_fortune_tiers(
+    ctx: Context<UpdateFortuneTiers>,
+    offset: u8,
+    total_tiers: u8,
+    tiers: Vec<FortuneTier>,
 ) -> Result<()> {
-    let temple_config = &mut ctx.accounts.temple_config;
-
-    // Validate probability total is 100
-    let regular_total = regular_fortune.great_luck_prob as u16
-        + regular_fortune.good_luck_prob as u16
-        + regular_fortune.neutral_prob as u16
-        + regular_fortune.bad_luck_prob as u16
-        + regular_fortune.great_bad_luck_prob as u16;
-
-    let buddha_total = buddha_fortune.great_luck_prob as u16
-        + buddha_fortune.good_luck_prob as u16
-        + buddha_fortune.neutral_prob as u16
-        + buddha_fortune.bad_luck_prob as u16
-        + buddha_fortune.great_bad_luck_prob as u16;
-
-    require!(regular_total == 100, ErrorCode::InvalidFortuneConfig);
-    require!(buddha_total == 100, ErrorCode::InvalidFortuneConfig);
+    let temple_config = &ctx.accounts.temple_config;
+    let offset = offset as usize;
+    let total_tiers = total_tiers as usize;
+    let end = offset + tiers.len();
+
+    require!(
+        total_tiers > 0 && total_tiers <= FortuneTier::MAX_TIERS,
+        ErrorCode::InvalidFortuneConfig
+    );
+    require!(end <= total_tiers, ErrorCode::InvalidFortuneConfig);
+    for tier in &tiers {
+        tier.validate()?;
+    }
 
-    // Update probability configuration
-    temple_config.dynamic_config.regular_fortune = regular_fortune;
-    temple_config.dynamic_config.buddha_fortune = buddha_fortune;
+    // Update fortune table, growing the account when the table outgrows its allocation
+    let (regular_total, buddha_total) = FortuneTable::write_tiers(
+        &ctx.accounts.fortune_table.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        offset,
+        &tiers,
+    )?;
+
+    if end == total_tiers {
+        // Complete table: weights must add up to 100%
+        require!(
+            regular_total == FortuneTier::TOTAL_WEIGHT_BPS,
+            ErrorCode::InvalidFortuneConfig
+        );
+        require!(
+            buddha_total == FortuneTier::TOTAL_WEIGHT_BPS,
+            ErrorCode::InvalidFortuneConfig
+        );
+    } else {
+        // Partial table: only while draws are paused
+        require!(
+            !temple_config.get_status_by_bit(TempleStatusBitIndex::DrawFortune),
+            ErrorCode::InvalidFortuneConfig
+        );
+    }
 
-    msg!("Updated fortune configuration");
+    msg!(
+        "Updated fortune tiers {}..{} of {}",
+        offset,
+        end,
+        total_tiers
+    );
     Ok(())
 }
 
@@ -107,6 +176,10 @@ pub fn update_donation_levels(
             level_config.min_amount_sol > 0.0,
             ErrorCode::InvalidDonationLevel
         );
+        require!(
+            level_config.min_amount_usd_cents != Some(0),
+            ErrorCode::InvalidDonationLevel
+        );
     }
 
     // Update donation levels configuration
@@ -160,6 +233,164 @@ pub fn update_temple_levels(
     Ok(())
 }
 
+// 4.5. Update temple calendar (timezone and day boundary)
+pub fn update_day_boundary(
+    ctx: Context<UpdateDynamicConfig>,
+    utc_offset_minutes: i16,
+    day_boundary_hour: u8,
+) -> Result<()> {
+    let temple_config = &mut ctx.accounts.temple_config;
+
+    require!(
+        (TempleConfig::MIN_UTC_OFFSET_MINUTES..=TempleConfig::MAX_UTC_OFFSET_MINUTES)
+            .contains(&utc_offset_minutes),
+        ErrorCode::InvalidDayBoundary
+    );
+    require!(day_boundary_hour < 24, ErrorCode::InvalidDayBoundary);
+
+    temple_config.utc_offset_minutes = utc_offset_minutes;
+    temple_config.day_boundary_hour = day_boundary_hour;
+
+    msg!(
+        "Updated day boundary: UTC{:+} minutes, hour {}",
+        utc_offset_minutes,
+        day_boundary_hour
+    );
+    Ok(())
+}
+
+// 4.6. Update burning streak multipliers
+pub fn update_streak_multipliers(
+    ctx: Context<UpdateDynamicConfig>,
+    streak_multipliers: Vec<StreakMultiplier>,
+) -> Result<()> {
+    let temple_config = &mut ctx.accounts.temple_config;
+
+    require!(
+        streak_multipliers.len() <= StreakMultiplier::MAX_TIERS,
+        ErrorCode::InvalidStreakConfig
+    );
+    for tier in &streak_multipliers {
+        require!(
+            tier.min_days > 0 && tier.bonus_percent <= StreakMultiplier::MAX_BONUS_PERCENT,
+            ErrorCode::InvalidStreakConfig
+        );
+    }
+
+    temple_config.dynamic_config.streak_multipliers = streak_multipliers;
+
+    msg!("Updated streak multipliers configuration");
+    Ok(())
+}
+
+// 4.7. Update whitelisted payment mints and their prices
+pub fn update_payment_mints(
+    ctx: Context<UpdateConfigSection>,
+    payment_mints: Vec<PaymentMint>,
+) -> Result<()> {
+    require!(
+        payment_mints.len() <= PaymentMint::MAX_MINTS,
+        ErrorCode::InvalidPaymentMintConfig
+    );
+    for (i, payment_mint) in payment_mints.iter().enumerate() {
+        payment_mint.validate()?;
+        require!(
+            payment_mints[..i]
+                .iter()
+                .all(|other| other.mint != payment_mint.mint),
+            ErrorCode::InvalidPaymentMintConfig
+        );
+    }
+
+    set_section_and_grow(ctx, |config| config.payment_mints = payment_mints)?;
+
+    msg!("Updated payment mints configuration");
+    Ok(())
+}
+
+// 4.8. Update draw, share and wish pricing and rewards
+pub fn update_activity_economy(
+    ctx: Context<UpdateConfigSection>,
+    activity_economy: ActivityEconomyConfig,
+) -> Result<()> {
+    activity_economy.validate()?;
+    set_section_and_grow(ctx, |config| config.activity_economy = activity_economy)?;
+
+    msg!("Updated activity economy configuration");
+    Ok(())
+}
+
+// 4.9. Update the cost, window and reward of resolving bad fortunes
+pub fn update_fortune_resolve(
+    ctx: Context<UpdateConfigSection>,
+    fortune_resolve: FortuneResolveConfig,
+) -> Result<()> {
+    fortune_resolve.validate()?;
+    set_section_and_grow(ctx, |config| config.fortune_resolve = fortune_resolve)?;
+
+    msg!("Updated fortune resolve configuration");
+    Ok(())
+}
+
+// 4.10. Update the escalating merit cost and daily limit of fortune rerolls
+pub fn update_fortune_reroll(
+    ctx: Context<UpdateConfigSection>,
+    fortune_reroll: FortuneRerollConfig,
+) -> Result<()> {
+    set_section_and_grow(ctx, |config| config.fortune_reroll = fortune_reroll)?;
+
+    msg!("Updated fortune reroll configuration");
+    Ok(())
+}
+
+// 4.11. Update the reward of completing a fortune set and whether the set is burned
+pub fn update_fortune_set(
+    ctx: Context<UpdateConfigSection>,
+    fortune_set: FortuneSetConfig,
+) -> Result<()> {
+    set_section_and_grow(ctx, |config| config.fortune_set = fortune_set)?;
+
+    msg!("Updated fortune set configuration");
+    Ok(())
+}
+
+// 4.12. Update the merit reward of releasing a Fortune NFT
+pub fn update_fortune_release(
+    ctx: Context<UpdateConfigSection>,
+    fortune_release: FortuneReleaseConfig,
+) -> Result<()> {
+    set_section_and_grow(ctx, |config| config.fortune_release = fortune_release)?;
+
+    msg!("Updated fortune release configuration");
+    Ok(())
+}
+
+// 4.13. Update the giver's share of a dedicated burn's merit
+pub fn update_dedication(
+    ctx: Context<UpdateConfigSection>,
+    dedication: DedicationConfig,
+) -> Result<()> {
+    dedication.validate()?;
+    set_section_and_grow(ctx, |config| config.dedication = dedication)?;
+
+    msg!("Updated dedication configuration");
+    Ok(())
+}
+
+/// Replace one dynamic config section, growing configs allocated before the section existed
+fn set_section_and_grow(
+    ctx: Context<UpdateConfigSection>,
+    set: impl FnOnce(&mut DynamicConfig),
+) -> Result<()> {
+    let temple_config = &mut ctx.accounts.temple_config;
+    set(&mut temple_config.dynamic_config);
+    TempleConfig::grow_to_fit(
+        temple_config,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )
+}
+
 // 5. Update shop items configuration
 pub fn update_shop_items(ctx: Context<UpdateShopItems>, shop_items: Vec<ShopItem>) -> Result<()> {
     let shop_config = &mut ctx.accounts.shop_config;
@@ -170,6 +401,10 @@ pub fn update_shop_items(ctx: Context<UpdateShopItems>, shop_items: Vec<ShopItem
         require!(item.id > 0, ErrorCode::InvalidShopItemId);
         require!(!item.name.is_empty(), ErrorCode::InvalidShopItemId);
         require!(item.price > 0, ErrorCode::InvalidShopItemId);
+        require!(
+            item.price_usd_cents != Some(0),
+            ErrorCode::InvalidShopItemId
+        );
         require!(item.stock >= 0, ErrorCode::InvalidShopItemId);
     }
 
@@ -0,0 +1,70 @@
+use crate::error::ErrorCode;
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+/// Move SPL token payments out of the temple's treasury ATA
+#[derive(Accounts)]
+pub struct WithdrawTokenTreasury<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+        constraint = temple_config.owner == authority.key() @ ErrorCode::Unauthorized
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    pub payment_mint: Box<Account<'info, Mint>>,
+
+    /// Treasury ATA owned by the temple config PDA
+    #[account(
+        mut,
+        associated_token::mint = payment_mint,
+        associated_token::authority = temple_config,
+    )]
+    pub treasury_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Token account receiving the withdrawal
+    #[account(
+        mut,
+        token::mint = payment_mint,
+    )]
+    pub destination_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn withdraw_token_treasury(ctx: Context<WithdrawTokenTreasury>, amount: u64) -> Result<()> {
+    require!(
+        amount > 0 && amount <= ctx.accounts.treasury_token_account.amount,
+        ErrorCode::InsufficientTokenBalance
+    );
+
+    let temple_signer_seeds: &[&[&[u8]]] = &[&[
+        TempleConfig::SEED_PREFIX.as_bytes(),
+        &[ctx.bumps.temple_config],
+    ]];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.treasury_token_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.temple_config.to_account_info(),
+            },
+            temple_signer_seeds,
+        ),
+        amount,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    msg!(
+        "Withdrew {} of mint {} to {}",
+        amount,
+        ctx.accounts.payment_mint.key(),
+        ctx.accounts.destination_token_account.key()
+    );
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use crate::error::ErrorCode;
-use crate::state::amulet::{AmuletNFT, AmuletSource};
+use crate::state::amulet::{AmuletNFT, AmuletType};
 use crate::state::global_stats::GlobalStats;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
+use crate::state::pending_amulets::PendingAmulets;
 use crate::state::temple_config::TempleConfig;
 use crate::state::user_state::UserState;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::create_metadata_accounts_v3;
-use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 use anchor_spl::metadata::CreateMetadataAccountsV3;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::token::mint_to;
@@ -26,19 +27,24 @@ pub fn mint_amulet_nft(ctx: Context<MintAmuletNFT>, amulet_type: u8, source: u8)
         current_time,
     )?;
 
+    let amulet_kind = AmuletType::from_u8(amulet_type).ok_or(ErrorCode::InvalidAmuletType)?;
+
+    // Only amulets credited by a drop or purchase can be minted
+    ctx.accounts.pending_amulets.debit(amulet_kind)?;
+
     // Get serial number
     ctx.accounts.temple_config.total_amulets += 1;
     let serial_number: u32 = ctx.accounts.temple_config.total_amulets;
 
-    let (nft_name_str, amulet_type_str) = match amulet_type {
-        0 => (format!("Fortune Amulet #{}", serial_number), "Fortune"),
-        1 => (
-            format!("Protection Amulet #{}", serial_number),
-            "Protection",
-        ),
-        2 => (format!("Merit Amulet #{}", serial_number), "Merit"),
-        _ => (format!("Amulet #{}", serial_number), "Unknown"),
-    };
+    let template = ctx.accounts.metadata_config.template(NftFamily::Amulet);
+    let nft_data = template.to_data(&MetadataVars {
+        name: amulet_kind.as_str(),
+        serial: serial_number as u64,
+        level: amulet_type,
+        variant: template.variant(amulet_type as usize),
+        ..Default::default()
+    })?;
+    let nft_name_str = nft_data.name.clone();
 
     let source_str = match source {
         0 => "burn_incense",
@@ -68,16 +74,7 @@ pub fn mint_amulet_nft(ctx: Context<MintAmuletNFT>, amulet_type: u8, source: u8)
             },
             temple_signer_seeds,
         ),
-        DataV2 {
-            name: nft_name_str.clone(),
-            symbol: "AMULET".to_string(),
-            uri: "https://solji.mypinata.cloud/ipfs/QmYhnX7HCKQEPuq5mtTApaL4ccqxCrL1Pcawyvv2ReYc8Q"
-                .to_string(),
-            seller_fee_basis_points: 500,
-            creators: None,
-            collection: None,
-            uses: None,
-        },
+        nft_data,
         false, // immutable
         true,
         None,
@@ -103,6 +100,20 @@ pub fn mint_amulet_nft(ctx: Context<MintAmuletNFT>, amulet_type: u8, source: u8)
     )?;
     msg!("Amulet NFT minted successfully");
 
+    // Initialize AmuletNFT account data, used to verify buffs on-chain
+    let amulet_nft = &mut ctx.accounts.amulet_nft_account;
+    amulet_nft.owner = ctx.accounts.authority.key();
+    amulet_nft.mint = ctx.accounts.nft_mint_account.key();
+    amulet_nft.name = nft_name_str;
+    amulet_nft.description = amulet_kind.get_description().to_string();
+    amulet_nft.minted_at = clock.unix_timestamp;
+    amulet_nft.source = source;
+    amulet_nft.serial_number = serial_number;
+    amulet_nft.amulet_type = amulet_type;
+    amulet_nft.remaining_uses = AmuletNFT::MAX_USES;
+    amulet_nft.last_used_at = 0;
+    amulet_nft.bump = ctx.bumps.amulet_nft_account;
+
     // Update temple config
     ctx.accounts.temple_config.total_amulets += 1;
 
@@ -134,6 +145,12 @@ pub struct MintAmuletNFT<'info> {
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
 
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
     #[account(
         mut,
         seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
@@ -149,6 +166,15 @@ pub struct MintAmuletNFT<'info> {
     )]
     pub user_state: Box<Account<'info, UserState>>,
 
+    /// Pending amulets ledger
+    #[account(
+        mut,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump = pending_amulets.bump,
+        constraint = pending_amulets.user == authority.key() @ ErrorCode::InvalidUserState
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
     #[account(
         init,
         payer = authority,
@@ -163,6 +189,20 @@ pub struct MintAmuletNFT<'info> {
     )]
     pub nft_mint_account: Box<Account<'info, Mint>>,
 
+    /// Amulet account, tracks type and buff usage
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AmuletNFT::INIT_SPACE,
+        seeds = [
+            AmuletNFT::SEED_PREFIX.as_bytes(),
+            b"account",
+            nft_mint_account.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub amulet_nft_account: Box<Account<'info, AmuletNFT>>,
+
     /// User's NFT associated token account
     #[account(
         init_if_needed,
@@ -1,8 +1,12 @@
 use crate::error::ErrorCode;
+use crate::payment::{resolve_token_payment, transfer_token_payment};
+use crate::price::load_sol_usd_price;
 use crate::state::event::{DonationCompleted, DonationNFTMinted};
 use crate::state::global_stats::GlobalStats;
 use crate::state::medal_nft::*;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
 use crate::state::temple_config::TempleConfig;
+use crate::state::user_incense_inventory::UserIncenseInventory;
 use crate::state::user_state::{UserDonationState, UserIncenseState, UserState};
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
@@ -31,6 +35,12 @@ pub struct DonateFund<'info> {
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
 
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
     #[account(
         mut,
         seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
@@ -59,6 +69,15 @@ pub struct DonateFund<'info> {
     )]
     pub user_incense_state: Box<Account<'info, UserIncenseState>>,
 
+    #[account(
+        init_if_needed,
+        payer = donor,
+        space = 8 + UserIncenseInventory::INIT_SPACE,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), donor.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_inventory: Box<Account<'info, UserIncenseInventory>>,
+
     /// CHECK: Temple treasury account
     #[account(
         mut,
@@ -66,6 +85,20 @@ pub struct DonateFund<'info> {
     )]
     pub temple_treasury: AccountInfo<'info>,
 
+    /// SPL token donated instead of SOL (optional)
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Donor's token account for the payment mint
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Temple treasury ATA for the payment mint
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SOL/USD price feed for USD prices (optional), validated in price::load_sol_usd_price
+    pub sol_usd_price: Option<UncheckedAccount<'info>>,
+
     // Medal NFT accounts (optional - only needed if minting medal NFT)
     #[account(
         init_if_needed,
@@ -121,6 +154,7 @@ pub struct DonateFund<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+/// Donate `amount` lamports, or base units of a whitelisted SPL token when the token accounts are passed
 pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp as u64;
@@ -131,33 +165,77 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
         current_time,
     )?;
 
+    // Users who skipped migrate_incense_inventory get their legacy slots moved on first use
+    ctx.accounts
+        .user_incense_inventory
+        .ensure_initialized(ctx.accounts.donor.key(), ctx.bumps.user_incense_inventory);
+    ctx.accounts
+        .user_incense_inventory
+        .absorb_legacy(&mut ctx.accounts.user_incense_state)?;
+
     let donor = &ctx.accounts.donor;
     let temple_treasury = &ctx.accounts.temple_treasury;
 
     // Validate donation amount
     require!(amount > 0, ErrorCode::InvalidAmount);
 
-    // Transfer SOL to temple treasury
-    let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
-        &donor.key(),
-        &temple_treasury.key(),
-        amount,
-    );
-
-    anchor_lang::solana_program::program::invoke(
-        &transfer_ix,
-        &[
-            donor.to_account_info(),
-            temple_treasury.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    let token_payment = resolve_token_payment(
+        &ctx.accounts.temple_config,
+        ctx.accounts.payment_mint.as_deref(),
+        ctx.accounts.payer_token_account.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.donor.key(),
     )?;
 
-    // Process donation record
-    ctx.accounts.user_donation_state.process_donation(amount);
+    // Donation levels and rewards are counted in lamports
+    let donation_lamports = if let Some(payment_mint) = &token_payment {
+        // Transfer SPL tokens to the temple treasury ATA
+        let donation_lamports = payment_mint.to_donation_lamports(amount)?;
+        transfer_token_payment(
+            &ctx.accounts.token_program,
+            ctx.accounts.payment_mint.as_deref(),
+            ctx.accounts.payer_token_account.as_deref(),
+            ctx.accounts.treasury_token_account.as_deref(),
+            &ctx.accounts.donor,
+            amount,
+        )?;
+
+        // Update global stats
+        ctx.accounts
+            .global_stats
+            .add_token_payment(payment_mint.mint, amount);
+        donation_lamports
+    } else {
+        // Transfer SOL to temple treasury
+        let transfer_ix = anchor_lang::solana_program::system_instruction::transfer(
+            &donor.key(),
+            &temple_treasury.key(),
+            amount,
+        );
 
-    // Update global stats
-    ctx.accounts.global_stats.add_donation(amount);
+        anchor_lang::solana_program::program::invoke(
+            &transfer_ix,
+            &[
+                donor.to_account_info(),
+                temple_treasury.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        // Update global stats
+        ctx.accounts.global_stats.add_donation(amount);
+        amount
+    };
+
+    // Process donation record, against USD level thresholds when configured
+    let sol_usd = load_sol_usd_price(ctx.accounts.sol_usd_price.as_deref())?;
+    let level_thresholds = ctx
+        .accounts
+        .temple_config
+        .donation_level_thresholds(sol_usd.as_ref())?;
+    ctx.accounts
+        .user_donation_state
+        .process_donation(donation_lamports, &level_thresholds);
 
     // Process donation rewards
     let (merit_reward, incense_points_reward) =
@@ -199,8 +277,8 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
                     msg!("Extra burn count: {}", current_reward);
                 } else {
                     ctx.accounts
-                        .user_incense_state
-                        .add_incense_balance(reward_config.incense_id, current_reward);
+                        .user_incense_inventory
+                        .add_balance(reward_config.incense_id, current_reward)?;
                     msg!(
                         "Unlocked incense {}: {} sticks",
                         reward_config.incense_id,
@@ -225,8 +303,8 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
 
             if total_reward > 0 {
                 ctx.accounts
-                    .user_incense_state
-                    .add_incense_balance(special_incense.id, total_reward);
+                    .user_incense_inventory
+                    .add_balance(special_incense.id, total_reward)?;
                 msg!(
                     "Special incense {}: {} sticks",
                     special_incense.name,
@@ -236,6 +314,13 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
         }
     }
 
+    // Unlocked incense types may have outgrown the inventory allocation
+    UserIncenseInventory::grow_to_fit(
+        &ctx.accounts.user_incense_inventory,
+        &ctx.accounts.donor.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
     // 5. Update global stats with rewards
     ctx.accounts
         .global_stats
@@ -243,34 +328,18 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
 
     // ===== MINT MEDAL NFT IF ELIGIBLE =====
 
-    let donation_sol = amount as f64 / 1_000_000_000.0;
-
     // Check if user meets donation level requirements for medal NFT
-    if donation_sol >= MedalNFT::get_level_min_donation_sol(1) {
-        // Determine user's current level
-        let mut current_level = 1;
-        for level in (1..=4).rev() {
-            if total_donation_sol >= MedalNFT::get_level_min_donation_sol(level) {
-                current_level = level;
-                break;
-            }
-        }
+    if donation_lamports >= level_thresholds[0] {
+        // User's current level
+        let current_level = ctx.accounts.user_donation_state.donation_level;
 
         // Check if user already has medal NFT
         if ctx.accounts.user_state.has_medal_nft {
             msg!("User already has medal NFT");
             let serial_number = ctx.accounts.medal_nft_account.serial_number;
-            let new_name = if current_level == 4 {
-                format!("Supreme Dragon Medal #{}", serial_number)
-            } else if current_level == 3 {
-                format!("Protector Gold Medal #{}", serial_number)
-            } else if current_level == 2 {
-                format!("Diligent Silver Medal #{}", serial_number)
-            } else {
-                format!("Entry Merit Bronze Medal #{}", serial_number)
-            };
-
-            let new_uri = MedalNFT::get_medal_uri_by_level(current_level);
+            let medal_data =
+                medal_metadata(&ctx.accounts.metadata_config, current_level, serial_number)?;
+            let new_name = medal_data.name.clone();
 
             // Update metadata
             let temple_signer_seeds: &[&[&[u8]]] = &[&[
@@ -288,15 +357,7 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
                     temple_signer_seeds,
                 ),
                 None,
-                Some(DataV2 {
-                    name: new_name.clone(),
-                    symbol: "TMM".to_string(),
-                    uri: new_uri,
-                    seller_fee_basis_points: 0,
-                    creators: None,
-                    collection: None,
-                    uses: None,
-                }),
+                Some(medal_data),
                 None,
                 None,
             )?;
@@ -323,15 +384,9 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
             // Mint new medal NFT
             let serial_number = ctx.accounts.user_donation_state.total_donation_count;
 
-            let medal_name = if current_level == 4 {
-                format!("Supreme Dragon Medal #{}", serial_number)
-            } else if current_level == 3 {
-                format!("Protector Gold Medal #{}", serial_number)
-            } else if current_level == 2 {
-                format!("Diligent Silver Medal #{}", serial_number)
-            } else {
-                format!("Entry Merit Bronze Medal #{}", serial_number)
-            };
+            let medal_data =
+                medal_metadata(&ctx.accounts.metadata_config, current_level, serial_number)?;
+            let medal_name = medal_data.name.clone();
 
             // Create metadata account
             let temple_signer_seeds: &[&[&[u8]]] = &[&[
@@ -339,8 +394,6 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
                 &[ctx.bumps.temple_config],
             ]];
 
-            let uri = MedalNFT::get_medal_uri_by_level(current_level);
-
             create_metadata_accounts_v3(
                 CpiContext::new_with_signer(
                     ctx.accounts.token_metadata_program.to_account_info(),
@@ -355,15 +408,7 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
                     },
                     temple_signer_seeds,
                 ),
-                DataV2 {
-                    name: medal_name.clone(),
-                    symbol: "TMM".to_string(),
-                    uri,
-                    seller_fee_basis_points: 0,
-                    creators: None,
-                    collection: None,
-                    uses: None,
-                },
+                medal_data,
                 true, // Allow metadata to be mutable for future upgrades
                 true,
                 None,
@@ -419,13 +464,14 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
 
     emit!(DonationCompleted {
         user: donor.key(),
-        amount,
+        amount: donation_lamports,
+        payment_mint: token_payment.map(|payment_mint| payment_mint.mint),
         total_donated: ctx.accounts.user_donation_state.donation_amount,
         level: ctx.accounts.user_donation_state.donation_level,
         timestamp: clock.unix_timestamp,
     });
 
-    let donation_sol = amount as f64 / 1_000_000_000.0;
+    let donation_sol = donation_lamports as f64 / 1_000_000_000.0;
     msg!("User {} donated {:.6} SOL", donor.key(), donation_sol);
     msg!(
         "Current donation level: {}",
@@ -435,3 +481,19 @@ pub fn donate_fund(ctx: Context<DonateFund>, amount: u64) -> Result<()> {
 
     Ok(())
 }
+
+/// Medal metadata for a level, rendered from the medal template
+fn medal_metadata(
+    metadata_config: &MetadataConfig,
+    level: u8,
+    serial_number: u32,
+) -> Result<DataV2> {
+    let template = metadata_config.template(NftFamily::Medal);
+    template.to_data(&MetadataVars {
+        name: MedalNFT::get_medal_name_by_level(level),
+        serial: serial_number as u64,
+        level,
+        variant: template.variant(level.saturating_sub(1) as usize),
+        ..Default::default()
+    })
+}
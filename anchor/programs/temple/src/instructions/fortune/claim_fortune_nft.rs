@@ -0,0 +1,194 @@
+use crate::state::event::FortuneNFTMinted;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
+use crate::state::pending_fortune::PendingFortune;
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::create_metadata_accounts_v3;
+use anchor_spl::metadata::CreateMetadataAccountsV3;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::mint_to;
+use anchor_spl::token::Mint;
+use anchor_spl::token::MintTo;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(draw_index: u32)]
+pub struct ClaimFortuneNFT<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Pending fortune being claimed, closed back to the user
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            PendingFortune::SEED_PREFIX.as_bytes(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump = pending_fortune.bump,
+    )]
+    pub pending_fortune: Box<Account<'info, PendingFortune>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    // Fortune NFT accounts, same addresses draw_fortune would have used
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"fortune_nft",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+        space = 8 + FortuneNFT::INIT_SPACE,
+    )]
+    pub fortune_nft_account: Box<Account<'info, FortuneNFT>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"fortune_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = temple_config.key(),
+        mint::freeze_authority = temple_config.key(),
+    )]
+    pub fortune_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's fortune NFT associated account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = fortune_nft_mint,
+        associated_token::authority = user,
+    )]
+    pub fortune_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Fortune NFT metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            fortune_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub fortune_nft_metadata: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn claim_fortune_nft(ctx: Context<ClaimFortuneNFT>, _draw_index: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    // The NFT shows the tier as it was when drawn
+    let pending_fortune = &ctx.accounts.pending_fortune;
+
+    let temple_signer_seeds: &[&[&[u8]]] = &[&[
+        TempleConfig::SEED_PREFIX.as_bytes(),
+        &[ctx.bumps.temple_config],
+    ]];
+
+    // Create metadata account
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.fortune_nft_metadata.to_account_info(),
+                mint: ctx.accounts.fortune_nft_mint.to_account_info(),
+                mint_authority: ctx.accounts.temple_config.to_account_info(),
+                update_authority: ctx.accounts.temple_config.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            temple_signer_seeds,
+        ),
+        ctx.accounts
+            .metadata_config
+            .template(NftFamily::Fortune)
+            .to_data(&MetadataVars {
+                name: &pending_fortune.tier_name,
+                variant: &pending_fortune.tier_uri,
+                ..Default::default()
+            })?,
+        true, // Allow metadata to be mutable for future upgrades
+        true,
+        None,
+    )?;
+
+    // Mint fortune NFT
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.fortune_nft_mint.to_account_info(),
+                to: ctx.accounts.fortune_nft_token_account.to_account_info(),
+                authority: ctx.accounts.temple_config.to_account_info(),
+            },
+            temple_signer_seeds,
+        ),
+        1,
+    )?;
+
+    // Initialize FortuneNFT account data from the pending draw
+    let fortune_nft = &mut ctx.accounts.fortune_nft_account;
+    fortune_nft.owner = ctx.accounts.user.key();
+    fortune_nft.mint = ctx.accounts.fortune_nft_mint.key();
+    fortune_nft.fortune_result = pending_fortune.fortune_result;
+    fortune_nft.tier_index = pending_fortune.tier_index;
+    fortune_nft.minted_at = pending_fortune.drawn_at;
+    fortune_nft.merit_cost = pending_fortune.merit_cost;
+    fortune_nft.serial_number = pending_fortune.serial_number;
+    fortune_nft.resolved = false;
+    fortune_nft.rerolls = 0;
+    fortune_nft.in_set = false;
+
+    msg!("Fortune NFT claimed: {}", pending_fortune.tier_name);
+
+    emit!(FortuneNFTMinted {
+        user: ctx.accounts.user.key(),
+        fortune_nft_mint: ctx.accounts.fortune_nft_mint.key(),
+        fortune_result: pending_fortune.tier_name.clone(),
+        merit_cost: pending_fortune.merit_cost as u32,
+        serial_number: pending_fortune.serial_number,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,302 @@
+use crate::error::ErrorCode;
+use crate::state::event::FortuneSetClaimed;
+use crate::state::fortune_history::FortuneHistory;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::fortune_set_claim::FortuneSetClaim;
+use crate::state::fortune_table::FortuneTable;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::UserIncenseState;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::create_metadata_accounts_v3;
+use anchor_spl::metadata::CreateMetadataAccountsV3;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::burn;
+use anchor_spl::token::freeze_account;
+use anchor_spl::token::mint_to;
+use anchor_spl::token::Burn;
+use anchor_spl::token::FreezeAccount;
+use anchor_spl::token::Mint;
+use anchor_spl::token::MintTo;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+/// Trade a full set of Fortune NFTs for a Complete Fortune NFT.
+/// A set holds one Fortune NFT per category (FortuneResult) the fortune table currently has:
+/// tables may hold many tiers, so the set follows their categories rather than single tiers.
+/// Remaining accounts: (fortune_nft_account, fortune_nft_mint, token_account) per
+/// category in FortuneResult order; Fortune NFT accounts must be writable
+#[derive(Accounts)]
+pub struct ClaimFortuneSet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    /// Fortune history ledger, counts the claimed sets
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FortuneHistory::INIT_SPACE,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    /// Claimed set, one per set the user claims
+    #[account(
+        init,
+        payer = user,
+        space = 8 + FortuneSetClaim::INIT_SPACE,
+        seeds = [
+            FortuneSetClaim::SEED_PREFIX.as_bytes(),
+            user.key().as_ref(),
+            &fortune_history.sets_claimed.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_set_claim: Box<Account<'info, FortuneSetClaim>>,
+
+    #[account(
+        init,
+        payer = user,
+        seeds = [
+            b"fortune_set_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &fortune_history.sets_claimed.to_string().as_bytes(),
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = temple_config.key(),
+        mint::freeze_authority = temple_config.key(),
+    )]
+    pub set_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's Complete Fortune NFT associated account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = set_nft_mint,
+        associated_token::authority = user,
+    )]
+    pub set_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Complete Fortune NFT metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            set_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub set_nft_metadata: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn claim_fortune_set<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimFortuneSet<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    let categories = FortuneTable::categories(&FortuneTable::data(&ctx.accounts.fortune_table)?)?;
+    require!(
+        ctx.remaining_accounts.len() == categories.len() * 3,
+        ErrorCode::InvalidFortuneSet
+    );
+
+    let user_key = ctx.accounts.user.key();
+    let config = ctx
+        .accounts
+        .temple_config
+        .dynamic_config
+        .fortune_set
+        .clone();
+    let temple_signer_seeds: &[&[&[u8]]] = &[&[
+        TempleConfig::SEED_PREFIX.as_bytes(),
+        &[ctx.bumps.temple_config],
+    ]];
+
+    // Verify one held Fortune NFT per category, then burn or freeze it
+    let mut serial_numbers = Vec::with_capacity(FortuneSetClaim::MAX_SET_SIZE);
+    for (i, accounts) in ctx.remaining_accounts.chunks(3).enumerate() {
+        let (fortune_nft_info, mint_info, token_account_info) =
+            (&accounts[0], &accounts[1], &accounts[2]);
+
+        require_keys_eq!(
+            *fortune_nft_info.owner,
+            crate::ID,
+            ErrorCode::InvalidFortuneSet
+        );
+        let mut fortune_nft = FortuneNFT::load_upgraded(
+            fortune_nft_info,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+        require!(
+            fortune_nft.fortune_result as u8 == categories[i] as u8,
+            ErrorCode::InvalidFortuneSet
+        );
+        require!(!fortune_nft.in_set, ErrorCode::FortuneInSet);
+
+        // Ownership comes from the token account, so traded NFTs count too
+        require_keys_eq!(
+            mint_info.key(),
+            fortune_nft.mint,
+            ErrorCode::InvalidFortuneSet
+        );
+        let token_account = Account::<TokenAccount>::try_from(token_account_info)?;
+        require!(
+            token_account.mint == fortune_nft.mint
+                && token_account.owner == user_key
+                && token_account.amount == 1,
+            ErrorCode::InvalidOwner
+        );
+
+        if config.burn_nfts {
+            burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: mint_info.clone(),
+                        from: token_account_info.clone(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+        } else {
+            // The temple is the freeze authority of Fortune NFT mints, a frozen set cannot be traded
+            freeze_account(CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                FreezeAccount {
+                    account: token_account_info.clone(),
+                    mint: mint_info.clone(),
+                    authority: ctx.accounts.temple_config.to_account_info(),
+                },
+                temple_signer_seeds,
+            ))?;
+        }
+
+        // Frozen NFTs stay in the wallet, the flag keeps them out of later sets
+        fortune_nft.in_set = true;
+        fortune_nft.try_serialize(&mut &mut fortune_nft_info.try_borrow_mut_data()?[..])?;
+
+        serial_numbers.push(fortune_nft.serial_number);
+    }
+
+    // Create metadata account
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.set_nft_metadata.to_account_info(),
+                mint: ctx.accounts.set_nft_mint.to_account_info(),
+                mint_authority: ctx.accounts.temple_config.to_account_info(),
+                update_authority: ctx.accounts.temple_config.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            temple_signer_seeds,
+        ),
+        ctx.accounts
+            .metadata_config
+            .template(NftFamily::FortuneSet)
+            .to_data(&MetadataVars::default())?,
+        true, // Allow metadata to be mutable for future upgrades
+        true,
+        None,
+    )?;
+
+    // Mint Complete Fortune NFT
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.set_nft_mint.to_account_info(),
+                to: ctx.accounts.set_nft_token_account.to_account_info(),
+                authority: ctx.accounts.temple_config.to_account_info(),
+            },
+            temple_signer_seeds,
+        ),
+        1,
+    )?;
+
+    ctx.accounts
+        .fortune_history
+        .ensure_initialized(user_key, ctx.bumps.fortune_history);
+    let set_index = ctx.accounts.fortune_history.sets_claimed;
+    ctx.accounts.fortune_history.sets_claimed = set_index.saturating_add(1);
+
+    let fortune_set_claim = &mut ctx.accounts.fortune_set_claim;
+    fortune_set_claim.user = user_key;
+    fortune_set_claim.set_index = set_index;
+    fortune_set_claim.set_nft_mint = ctx.accounts.set_nft_mint.key();
+    fortune_set_claim.serial_numbers = serial_numbers.clone();
+    fortune_set_claim.burned = config.burn_nfts;
+    fortune_set_claim.claimed_at = now;
+    fortune_set_claim.bump = ctx.bumps.fortune_set_claim;
+
+    // Reward
+    ctx.accounts
+        .user_incense_state
+        .add_incense_value_and_merit(0, config.merit_reward);
+    msg!(
+        "Fortune set completed, earned {} merit points reward",
+        config.merit_reward
+    );
+
+    emit!(FortuneSetClaimed {
+        user: user_key,
+        set_nft_mint: ctx.accounts.set_nft_mint.key(),
+        serial_numbers,
+        burned: config.burn_nfts,
+        merit_reward: config.merit_reward,
+        timestamp: now,
+    });
+
+    Ok(())
+}
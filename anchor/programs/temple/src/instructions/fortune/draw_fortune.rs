@@ -1,12 +1,18 @@
 use crate::error::ErrorCode;
+use crate::randomness::{RandomnessSource, SlotHashesRandomness, FORTUNE_DOMAIN};
+use crate::state::amulet::{apply_amulet, AmuletNFT, AmuletType};
 use crate::state::event::FortuneDrawn;
+use crate::state::fortune_history::{FortuneHistory, FortuneRecord};
 use crate::state::fortune_nft::{FortuneNFT, FortuneResult};
+use crate::state::fortune_table::{FortuneTable, FortuneTier};
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
+use crate::state::pending_amulets::PendingAmulets;
 use crate::state::temple_config::TempleConfig;
-use crate::state::user_state::{UserIncenseState, UserState};
+use crate::state::user_state::{PendingDraw, UserIncenseState, UserState};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::create_metadata_accounts_v3;
-use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 use anchor_spl::metadata::CreateMetadataAccountsV3;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::token::mint_to;
@@ -15,52 +21,13 @@ use anchor_spl::token::MintTo;
 use anchor_spl::token::Token;
 use anchor_spl::token::TokenAccount;
 
-impl FortuneResult {
-    pub fn as_str(&self) -> &str {
-        match self {
-            FortuneResult::GreatLuck => "Great Luck",
-            FortuneResult::GoodLuck => "Good Luck",
-            FortuneResult::Neutral => "Neutral",
-            FortuneResult::BadLuck => "Bad Luck",
-            FortuneResult::GreatBadLuck => "Great Bad Luck",
-        }
-    }
-
-    pub fn get_description(&self) -> &str {
-        match self {
-            FortuneResult::GreatLuck => "Everything goes smoothly, wishes come true",
-            FortuneResult::GoodLuck => "All things go well, gradually improving",
-            FortuneResult::Neutral => "Plain and simple, steady progress",
-            FortuneResult::BadLuck => "Be careful, turn misfortune into fortune",
-            FortuneResult::GreatBadLuck => "Be extra careful, observe quietly",
-        }
-    }
-
-    pub fn get_uri(&self) -> &str {
-        match self {
-            FortuneResult::GreatLuck => {
-                "https://solji.mypinata.cloud/ipfs/QmeYUSLgMKYL8128quaieDUXfbdeKVsGBRQLmWjgAsqw2y"
-            }
-            FortuneResult::GoodLuck => {
-                "https://solji.mypinata.cloud/ipfs/Qmdkcptk4783ej2sKNsK39UrNXXjjCxoFLbsYLt5KbhzDA"
-            }
-            FortuneResult::Neutral => {
-                "https://solji.mypinata.cloud/ipfs/QmZkYr6vMhSYA37TEPpN2pAC7Cw3DZSHQKo6mNfadbwKik"
-            }
-            FortuneResult::BadLuck => {
-                "https://solji.mypinata.cloud/ipfs/QmSiaGHzMyCijCSRf5tc9oFh7Ajs7Nnr2DMq6WVtiM4D8B"
-            }
-            FortuneResult::GreatBadLuck => {
-                "https://solji.mypinata.cloud/ipfs/QmVQSbuJJnwYQ9ZJYvMcTDyxc1X75mjdVxNkjxWf6D2HsT"
-            }
-        }
-    }
-}
-
 // Define draw result structure
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
 pub struct DrawResult {
-    pub fortune: FortuneResult,
+    pub fortune: FortuneResult, // Category of the drawn tier
+    pub tier_index: u8,
+    #[max_len(18)]
+    pub tier_name: String,
     pub timestamp: i64,
     pub used_merit: bool,
 }
@@ -84,6 +51,26 @@ pub struct DrawFortune<'info> {
     )]
     pub user_incense_state: Box<Account<'info, UserIncenseState>>,
 
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    /// Fortune history ledger, updated on every draw
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FortuneHistory::INIT_SPACE,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
     #[account(
         mut,
         seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
@@ -91,6 +78,20 @@ pub struct DrawFortune<'info> {
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
 
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
     // Fortune NFT accounts
     #[account(
         init_if_needed,
@@ -144,9 +145,23 @@ pub struct DrawFortune<'info> {
     )]
     pub fortune_nft_metadata: UncheckedAccount<'info>,
 
-    /// CHECK: Randomness account (only needed in mainnet)
-    #[cfg(feature = "mainnet")]
-    pub randomness_account: Option<AccountInfo<'info>>,
+    /// Fortune amulet held by the user (optional)
+    #[account(mut)]
+    pub fortune_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the fortune amulet
+    pub fortune_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Protection amulet held by the user (optional)
+    #[account(mut)]
+    pub protection_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the protection amulet
+    pub protection_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SlotHashes sysvar, randomness for instant draws
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
     pub token_metadata_program: Program<'info, Metadata>,
@@ -155,12 +170,7 @@ pub struct DrawFortune<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn draw_fortune(
-    ctx: Context<DrawFortune>,
-    use_merit: bool,
-    has_fortune_amulet: bool,
-    has_protection_amulet: bool,
-) -> Result<DrawResult> {
+pub fn draw_fortune(ctx: Context<DrawFortune>, use_merit: bool) -> Result<DrawResult> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp as u64;
     let now = clock.unix_timestamp;
@@ -171,74 +181,100 @@ pub fn draw_fortune(
         current_time,
     )?;
 
-    let user_state: &mut Account<'_, UserState> = &mut ctx.accounts.user_state;
+    // Mainnet draws go through request_fortune / settle_fortune with Switchboard randomness
+    if cfg!(feature = "mainnet") {
+        return err!(ErrorCode::RandomnessRequestRequired);
+    }
 
     // Check if merit can be used for drawing fortune
+    let rates = ctx
+        .accounts
+        .temple_config
+        .activity_rates(&ctx.accounts.user_incense_state.title);
     if use_merit {
-        ctx.accounts.user_incense_state.consume_merit_for_draw(5)?;
+        ctx.accounts
+            .user_incense_state
+            .consume_merit_for_draw(rates.draw_merit_cost)?;
     } else {
         // Check if free draw is available
-        if !ctx.accounts.user_incense_state.can_draw_free() {
+        if !ctx
+            .accounts
+            .user_incense_state
+            .can_draw_free(&ctx.accounts.temple_config)
+        {
             return err!(ErrorCode::DailyIncenseLimitExceeded);
         }
     }
 
-    // Generate random number: decide method based on compilation features
-    #[cfg(feature = "mainnet")]
-    let random_value = {
-        // Production environment: use Switchboard oracle randomness
-        let clock = Clock::get()?;
-
-        // Parse randomness account data
-        let randomness_data = switchboard_on_demand::RandomnessAccountData::parse(
-            ctx.accounts.randomness_account.data.borrow(),
-        )
-        .map_err(|_| ErrorCode::InvalidRandomness)?;
-
-        // Get random value
-        let revealed_random_value = randomness_data
-            .get_value(clock.slot)
-            .map_err(|_| ErrorCode::RandomnessNotResolved)?;
-
-        // Extract a u64 value from random number array
-        let mut random_bytes = [0u8; 8];
-        random_bytes.copy_from_slice(&revealed_random_value[..8]);
-        let random_u64 = u64::from_le_bytes(random_bytes);
-
-        // Convert to 0-99 random number
-        (random_u64 % 100) as u8
-    };
+    // Instant draws roll on the latest slot hash
+    let randomness = SlotHashesRandomness::load(&ctx.accounts.slot_hashes)?;
+
+    // Verify amulets on-chain and consume their charges
+    let user_key = ctx.accounts.user.key();
+    let fortune_amulet = apply_amulet(
+        ctx.accounts.fortune_amulet.as_deref_mut(),
+        ctx.accounts.fortune_amulet_token_account.as_deref(),
+        &user_key,
+        AmuletType::Fortune,
+        now,
+    )?;
+    let protection_amulet = apply_amulet(
+        ctx.accounts.protection_amulet.as_deref_mut(),
+        ctx.accounts.protection_amulet_token_account.as_deref(),
+        &user_key,
+        AmuletType::Protection,
+        now,
+    )?;
 
-    #[cfg(not(feature = "mainnet"))]
-    let random_value = {
-        // Test environments (localnet, devnet): use pseudo-random seed
-        let clock = Clock::get()?;
-        let seed = clock.unix_timestamp as u64 + clock.slot;
-        (seed % 100) as u8
+    // Update user draw count
+    ctx.accounts
+        .user_incense_state
+        .update_draw_count(&ctx.accounts.temple_config);
+
+    let draw = PendingDraw {
+        commit_slot: clock.slot,
+        use_merit,
+        fortune_amulet,
+        protection_amulet,
     };
+    finish_draw(ctx, &randomness, draw, now)
+}
 
-    // Get probability settings from dynamic config
-    let mut fortune_config = ctx
-        .accounts
-        .temple_config
-        .get_fortune_config(user_state.has_buddha_nft)
-        .clone();
+/// Tier rolled for a draw, before it is minted or kept as a pending fortune
+pub(crate) struct RolledFortune {
+    pub serial_number: u32,
+    pub tier_index: u8,
+    pub tier: FortuneTier,
+    pub merit_cost: u64,
+    pub amulet_dropped: bool,
+}
+
+/// Roll the fortune tier and give the draw's merit reward.
+/// Payment, amulet charges and draw counts must already be settled by the caller.
+pub(crate) fn roll_fortune(
+    user_key: Pubkey,
+    user_state: &UserState,
+    user_incense_state: &mut UserIncenseState,
+    temple_config: &TempleConfig,
+    fortune_table: &AccountInfo,
+    randomness: &impl RandomnessSource,
+    draw: &PendingDraw,
+) -> Result<RolledFortune> {
+    let merit_cost = if draw.use_merit {
+        temple_config
+            .activity_rates(&user_incense_state.title)
+            .draw_merit_cost
+    } else {
+        0
+    };
+    let serial_number = user_incense_state.total_draws;
 
     // Apply amulet effects to fortune probabilities
-    if has_fortune_amulet {
-        // Good Luck Amulet: +20% to Great Luck and Good Luck probabilities
-        let bonus = 20;
-        fortune_config.great_luck_prob = fortune_config.great_luck_prob.saturating_add(bonus);
-        fortune_config.good_luck_prob = fortune_config.good_luck_prob.saturating_add(bonus);
+    if draw.fortune_amulet {
         msg!("Fortune Amulet activated: +20% to Great Luck and Good Luck probabilities");
     }
 
-    if has_protection_amulet {
-        // Protection Amulet: -20% to Bad Luck and Great Bad Luck probabilities
-        let reduction = 20;
-        fortune_config.bad_luck_prob = fortune_config.bad_luck_prob.saturating_sub(reduction);
-        fortune_config.great_bad_luck_prob =
-            fortune_config.great_bad_luck_prob.saturating_sub(reduction);
+    if draw.protection_amulet {
         msg!("Protection Amulet activated: -20% to Bad Luck and Great Bad Luck probabilities");
     }
 
@@ -246,66 +282,147 @@ pub fn draw_fortune(
         msg!("Buddha NFT holder gets probability bonus");
     }
 
-    let fortune = {
-        let mut cumulative_prob = 0u8;
-        cumulative_prob += fortune_config.great_luck_prob;
-        if random_value < cumulative_prob {
-            FortuneResult::GreatLuck
-        } else {
-            cumulative_prob += fortune_config.good_luck_prob;
-            if random_value < cumulative_prob {
-                FortuneResult::GoodLuck
-            } else {
-                cumulative_prob += fortune_config.neutral_prob;
-                if random_value < cumulative_prob {
-                    FortuneResult::Neutral
-                } else {
-                    cumulative_prob += fortune_config.bad_luck_prob;
-                    if random_value < cumulative_prob {
-                        FortuneResult::BadLuck
-                    } else {
-                        FortuneResult::GreatBadLuck
-                    }
-                }
-            }
-        }
+    // Sample the fortune table by cumulative weight
+    let roll = randomness.roll_u64(FORTUNE_DOMAIN, &user_key, serial_number as u64);
+    let (tier_index, tier) = FortuneTable::sample_tier(
+        &FortuneTable::data(fortune_table)?,
+        roll,
+        user_state.has_buddha_nft,
+        draw.fortune_amulet,
+        draw.protection_amulet,
+    )?;
+
+    // Give merit reward
+    if !draw.use_merit {
+        user_incense_state.add_incense_value_and_merit(0, tier.merit_reward);
+    }
+
+    msg!("Draw result: {}", tier.name);
+    msg!("Fortune explanation: {}", tier.description);
+
+    // TODO Amulet drop probability logic: 10% chance
+    #[cfg(feature = "localnet")]
+    let amulet_dropped = true; // Test environment: 100% drop rate for amulet
+
+    #[cfg(not(feature = "localnet"))]
+    let amulet_dropped = {
+        let amulet_drop_random = randomness.roll_percent(
+            crate::randomness::FORTUNE_AMULET_DROP_DOMAIN,
+            &user_key,
+            serial_number as u64,
+        );
+        amulet_drop_random < 10
     };
 
-    // Update user draw count
-    ctx.accounts.user_incense_state.update_draw_count();
+    Ok(RolledFortune {
+        serial_number,
+        tier_index,
+        tier,
+        merit_cost,
+        amulet_dropped,
+    })
+}
+
+/// Record a rolled fortune in the history, credit a dropped amulet and emit the draw event.
+/// Both ledgers must already be initialized by the caller.
+pub(crate) fn record_fortune(
+    user_key: Pubkey,
+    pending_amulets: &mut PendingAmulets,
+    fortune_history: &mut FortuneHistory,
+    rolled: &RolledFortune,
+    draw: &PendingDraw,
+    now: i64,
+) {
+    // Record the draw in the user's fortune history
+    fortune_history.record(FortuneRecord {
+        serial_number: rolled.serial_number,
+        tier_index: rolled.tier_index,
+        category: rolled.tier.category,
+        drawn_at: now,
+        merit_cost: rolled.merit_cost as u8,
+        fortune_amulet: draw.fortune_amulet,
+        protection_amulet: draw.protection_amulet,
+        rerolls: 0,
+    });
+
+    if rolled.amulet_dropped {
+        // Credit pending amulet, minted later through mint_amulet_nft
+        pending_amulets.credit(AmuletType::Fortune, 1);
+
+        // Emit amulet dropped event with type information
+        msg!("Congratulations! Got 1 Fortune Amulet NFT from drawing fortune!");
+        emit!(crate::state::event::AmuletDropped {
+            user: user_key,
+            amulet_type: 0, // Fortune Amulet
+            source: "draw_fortune".to_string(),
+            timestamp: now,
+        });
+    }
+
+    // Emit draw fortune event
+    emit!(FortuneDrawn {
+        user: user_key,
+        fortune_result: rolled.tier.name.clone(),
+        used_merit: draw.use_merit,
+        amulet_dropped: rolled.amulet_dropped,
+        timestamp: now,
+    });
+}
+
+/// Accounts needed to mint a rolled fortune as an NFT
+pub(crate) struct FortuneMintAccounts<'a, 'info> {
+    pub user: AccountInfo<'info>,
+    pub temple_config: AccountInfo<'info>,
+    pub temple_config_bump: u8,
+    pub metadata_config: &'a MetadataConfig,
+    pub fortune_nft_account: &'a mut FortuneNFT,
+    pub fortune_nft_mint: AccountInfo<'info>,
+    pub fortune_nft_token_account: AccountInfo<'info>,
+    pub fortune_nft_metadata: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_metadata_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+/// Mint the Fortune NFT of a rolled fortune and fill its FortuneNFT account,
+/// shared by draw_fortune and settle_fortune
+pub(crate) fn mint_fortune_nft(
+    accounts: FortuneMintAccounts,
+    rolled: &RolledFortune,
+    now: i64,
+) -> Result<()> {
+    let tier = &rolled.tier;
 
     // Mint fortune NFT
     let temple_signer_seeds: &[&[&[u8]]] = &[&[
         TempleConfig::SEED_PREFIX.as_bytes(),
-        &[ctx.bumps.temple_config],
+        &[accounts.temple_config_bump],
     ]];
 
-    let fortune_str = fortune.as_str();
-
     // Create metadata account
     create_metadata_accounts_v3(
         CpiContext::new_with_signer(
-            ctx.accounts.token_metadata_program.to_account_info(),
+            accounts.token_metadata_program,
             CreateMetadataAccountsV3 {
-                metadata: ctx.accounts.fortune_nft_metadata.to_account_info(),
-                mint: ctx.accounts.fortune_nft_mint.to_account_info(),
-                mint_authority: ctx.accounts.temple_config.to_account_info(),
-                update_authority: ctx.accounts.user.to_account_info(),
-                payer: ctx.accounts.user.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                rent: ctx.accounts.rent.to_account_info(),
+                metadata: accounts.fortune_nft_metadata,
+                mint: accounts.fortune_nft_mint.clone(),
+                mint_authority: accounts.temple_config.clone(),
+                update_authority: accounts.temple_config.clone(),
+                payer: accounts.user.clone(),
+                system_program: accounts.system_program,
+                rent: accounts.rent,
             },
             temple_signer_seeds,
         ),
-        DataV2 {
-            name: format!("Fortune NFT - {}", fortune_str),
-            symbol: "TMF".to_string(),
-            uri: fortune.get_uri().to_string(),
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        },
+        accounts
+            .metadata_config
+            .template(NftFamily::Fortune)
+            .to_data(&MetadataVars {
+                name: &tier.name,
+                variant: &tier.uri,
+                ..Default::default()
+            })?,
         true, // Allow metadata to be mutable for future upgrades
         true,
         None,
@@ -314,11 +431,11 @@ pub fn draw_fortune(
     // Mint fortune NFT
     mint_to(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program,
             MintTo {
-                mint: ctx.accounts.fortune_nft_mint.to_account_info(),
-                to: ctx.accounts.fortune_nft_token_account.to_account_info(),
-                authority: ctx.accounts.temple_config.to_account_info(),
+                mint: accounts.fortune_nft_mint.clone(),
+                to: accounts.fortune_nft_token_account,
+                authority: accounts.temple_config,
             },
             temple_signer_seeds,
         ),
@@ -326,71 +443,91 @@ pub fn draw_fortune(
     )?;
 
     // Initialize FortuneNFT account data
-    ctx.accounts.fortune_nft_account.owner = ctx.accounts.user.key();
-    ctx.accounts.fortune_nft_account.mint = ctx.accounts.fortune_nft_mint.key();
-    ctx.accounts.fortune_nft_account.fortune_result = fortune.clone();
-    ctx.accounts.fortune_nft_account.minted_at = now;
-    ctx.accounts.fortune_nft_account.merit_cost = if use_merit { 5 } else { 0 };
-    ctx.accounts.fortune_nft_account.serial_number = ctx.accounts.user_incense_state.total_draws;
-
-    // Give merit reward
-    if !use_merit {
-        ctx.accounts
-            .user_incense_state
-            .add_incense_value_and_merit(0, 2);
-    }
+    let fortune_nft = accounts.fortune_nft_account;
+    fortune_nft.owner = accounts.user.key();
+    fortune_nft.mint = accounts.fortune_nft_mint.key();
+    fortune_nft.fortune_result = tier.category;
+    fortune_nft.tier_index = rolled.tier_index;
+    fortune_nft.minted_at = now;
+    fortune_nft.merit_cost = rolled.merit_cost as u8;
+    fortune_nft.serial_number = rolled.serial_number;
+    fortune_nft.resolved = false;
+    fortune_nft.rerolls = 0;
+    fortune_nft.in_set = false;
 
-    let fortune_str = fortune.as_str();
-    let fortune_desc = fortune.get_description();
-
-    msg!("Draw result: {}", fortune_str);
-    msg!("Fortune explanation: {}", fortune_desc);
     msg!("Fortune NFT minted successfully!");
 
-    // TODO Amulet drop probability logic: 10% chance
-    #[cfg(feature = "localnet")]
-    let amulet_dropped = true; // Test environment: 100% drop rate for amulet
-
-    #[cfg(not(feature = "localnet"))]
-    let amulet_dropped = {
-        let amulet_drop_random = (random_value.wrapping_add(42) % 100) as u8;
-        amulet_drop_random < 10
-    };
-    if amulet_dropped {
-        // Emit amulet dropped event with type information
-        msg!("Congratulations! Got 1 Fortune Amulet NFT from drawing fortune!");
-        emit!(crate::state::event::AmuletDropped {
-            user: ctx.accounts.user.key(),
-            amulet_type: 0, // Fortune Amulet
-            source: "draw_fortune".to_string(),
-            timestamp: now,
-        });
-    }
-
-    // Emit draw fortune event
-    emit!(FortuneDrawn {
-        user: ctx.accounts.user.key(),
-        fortune_result: fortune.as_str().to_string(),
-        used_merit: use_merit,
-        amulet_dropped,
-        timestamp: now,
-    });
-
     // Emit fortune NFT minted event
     emit!(crate::state::event::FortuneNFTMinted {
-        user: ctx.accounts.user.key(),
-        fortune_nft_mint: ctx.accounts.fortune_nft_mint.key(),
-        fortune_result: fortune.as_str().to_string(),
-        merit_cost: if use_merit { 5 } else { 0 },
-        serial_number: ctx.accounts.user_incense_state.total_draws,
+        user: accounts.user.key(),
+        fortune_nft_mint: accounts.fortune_nft_mint.key(),
+        fortune_result: tier.name.clone(),
+        merit_cost: rolled.merit_cost as u32,
+        serial_number: rolled.serial_number,
         timestamp: now,
     });
 
-    let result = DrawResult {
-        fortune,
-        timestamp: now,
-        used_merit: use_merit,
-    };
+    Ok(())
+}
 
-    Ok(result)
+/// Roll the fortune and mint its NFT.
+/// Payment, amulet charges and draw counts must already be settled by the caller.
+fn finish_draw(
+    ctx: Context<DrawFortune>,
+    randomness: &impl RandomnessSource,
+    draw: PendingDraw,
+    now: i64,
+) -> Result<DrawResult> {
+    let user_key = ctx.accounts.user.key();
+    let rolled = roll_fortune(
+        user_key,
+        &ctx.accounts.user_state,
+        &mut ctx.accounts.user_incense_state,
+        &ctx.accounts.temple_config,
+        &ctx.accounts.fortune_table,
+        randomness,
+        &draw,
+    )?;
+
+    mint_fortune_nft(
+        FortuneMintAccounts {
+            user: ctx.accounts.user.to_account_info(),
+            temple_config: ctx.accounts.temple_config.to_account_info(),
+            temple_config_bump: ctx.bumps.temple_config,
+            metadata_config: &ctx.accounts.metadata_config,
+            fortune_nft_account: &mut ctx.accounts.fortune_nft_account,
+            fortune_nft_mint: ctx.accounts.fortune_nft_mint.to_account_info(),
+            fortune_nft_token_account: ctx.accounts.fortune_nft_token_account.to_account_info(),
+            fortune_nft_metadata: ctx.accounts.fortune_nft_metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        &rolled,
+        now,
+    )?;
+
+    ctx.accounts
+        .fortune_history
+        .ensure_initialized(user_key, ctx.bumps.fortune_history);
+    ctx.accounts
+        .pending_amulets
+        .ensure_initialized(user_key, ctx.bumps.pending_amulets);
+    record_fortune(
+        user_key,
+        &mut ctx.accounts.pending_amulets,
+        &mut ctx.accounts.fortune_history,
+        &rolled,
+        &draw,
+        now,
+    );
+
+    Ok(DrawResult {
+        fortune: rolled.tier.category,
+        tier_index: rolled.tier_index,
+        tier_name: rolled.tier.name,
+        timestamp: now,
+        used_merit: draw.use_merit,
+    })
 }
@@ -0,0 +1,379 @@
+use crate::error::ErrorCode;
+use crate::instructions::fortune::draw_fortune::{record_fortune, roll_fortune, DrawResult};
+use crate::instructions::fortune::settle_fortune::take_revealed_draw;
+use crate::randomness::{RandomnessSource, SlotHashesRandomness};
+use crate::state::amulet::{apply_amulet, AmuletNFT, AmuletType};
+use crate::state::fortune_history::FortuneHistory;
+use crate::state::fortune_table::FortuneTable;
+use crate::state::pending_amulets::PendingAmulets;
+use crate::state::pending_fortune::PendingFortune;
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::{PendingDraw, UserIncenseState, UserState};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::token::TokenAccount;
+
+/// Draw without minting: the result is kept in a PendingFortune record,
+/// claimable later through claim_fortune_nft
+#[derive(Accounts)]
+pub struct DrawOnly<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    /// Fortune history ledger, updated on every draw
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FortuneHistory::INIT_SPACE,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    /// Draw result, keyed by the same draw index as the Fortune NFT accounts
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PendingFortune::INIT_SPACE,
+        seeds = [
+            PendingFortune::SEED_PREFIX.as_bytes(),
+            user.key().as_ref(),
+            &user_incense_state.total_draws.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub pending_fortune: Box<Account<'info, PendingFortune>>,
+
+    /// Fortune amulet held by the user (optional)
+    #[account(mut)]
+    pub fortune_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the fortune amulet
+    pub fortune_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Protection amulet held by the user (optional)
+    #[account(mut)]
+    pub protection_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the protection amulet
+    pub protection_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SlotHashes sysvar, randomness for instant draws
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Settle a requested fortune without minting: the amulets and payment were taken by
+/// request_fortune, so only the PendingFortune record and the bound randomness account are needed
+#[derive(Accounts)]
+pub struct SettleFortuneOnly<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    /// Fortune history ledger, updated on every draw
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FortuneHistory::INIT_SPACE,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    /// Draw result, keyed by the same draw index as the Fortune NFT accounts
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PendingFortune::INIT_SPACE,
+        seeds = [
+            PendingFortune::SEED_PREFIX.as_bytes(),
+            user.key().as_ref(),
+            &user_incense_state.total_draws.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub pending_fortune: Box<Account<'info, PendingFortune>>,
+
+    /// CHECK: Randomness account bound by request_fortune, verified by take_revealed_draw
+    pub randomness_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts written when a fortune is kept without minting
+struct KeptFortuneAccounts<'a, 'info> {
+    user: Pubkey,
+    user_state: &'a UserState,
+    user_incense_state: &'a mut UserIncenseState,
+    pending_amulets: &'a mut PendingAmulets,
+    pending_amulets_bump: u8,
+    fortune_history: &'a mut FortuneHistory,
+    fortune_history_bump: u8,
+    temple_config: &'a TempleConfig,
+    fortune_table: &'a AccountInfo<'info>,
+    pending_fortune: &'a mut PendingFortune,
+    pending_fortune_bump: u8,
+}
+
+pub fn draw_only(ctx: Context<DrawOnly>, use_merit: bool) -> Result<DrawResult> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    // Mainnet draws go through request_fortune / settle_fortune_only with Switchboard randomness
+    if cfg!(feature = "mainnet") {
+        return err!(ErrorCode::RandomnessRequestRequired);
+    }
+
+    // Check if merit can be used for drawing fortune
+    let rates = ctx
+        .accounts
+        .temple_config
+        .activity_rates(&ctx.accounts.user_incense_state.title);
+    if use_merit {
+        ctx.accounts
+            .user_incense_state
+            .consume_merit_for_draw(rates.draw_merit_cost)?;
+    } else if !ctx
+        .accounts
+        .user_incense_state
+        .can_draw_free(&ctx.accounts.temple_config)
+    {
+        return err!(ErrorCode::DailyIncenseLimitExceeded);
+    }
+
+    // Instant draws roll on the latest slot hash
+    let randomness = SlotHashesRandomness::load(&ctx.accounts.slot_hashes)?;
+
+    // Verify amulets on-chain and consume their charges
+    let user_key = ctx.accounts.user.key();
+    let fortune_amulet = apply_amulet(
+        ctx.accounts.fortune_amulet.as_deref_mut(),
+        ctx.accounts.fortune_amulet_token_account.as_deref(),
+        &user_key,
+        AmuletType::Fortune,
+        now,
+    )?;
+    let protection_amulet = apply_amulet(
+        ctx.accounts.protection_amulet.as_deref_mut(),
+        ctx.accounts.protection_amulet_token_account.as_deref(),
+        &user_key,
+        AmuletType::Protection,
+        now,
+    )?;
+
+    // Update user draw count
+    let draw_index = ctx.accounts.user_incense_state.total_draws;
+    ctx.accounts
+        .user_incense_state
+        .update_draw_count(&ctx.accounts.temple_config);
+
+    let draw = PendingDraw {
+        commit_slot: clock.slot,
+        use_merit,
+        fortune_amulet,
+        protection_amulet,
+    };
+    finish_draw_only(
+        KeptFortuneAccounts {
+            user: user_key,
+            user_state: &ctx.accounts.user_state,
+            user_incense_state: &mut ctx.accounts.user_incense_state,
+            pending_amulets: &mut ctx.accounts.pending_amulets,
+            pending_amulets_bump: ctx.bumps.pending_amulets,
+            fortune_history: &mut ctx.accounts.fortune_history,
+            fortune_history_bump: ctx.bumps.fortune_history,
+            temple_config: &ctx.accounts.temple_config,
+            fortune_table: &ctx.accounts.fortune_table,
+            pending_fortune: &mut ctx.accounts.pending_fortune,
+            pending_fortune_bump: ctx.bumps.pending_fortune,
+        },
+        &randomness,
+        draw,
+        draw_index,
+        now,
+    )
+}
+
+/// Settle a requested fortune without minting, keeping it as a PendingFortune
+pub fn settle_fortune_only(ctx: Context<SettleFortuneOnly>) -> Result<DrawResult> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    let draw_index = ctx.accounts.user_incense_state.total_draws;
+    let (draw, revealed) = take_revealed_draw(
+        &mut ctx.accounts.user_state,
+        &mut ctx.accounts.user_incense_state,
+        &ctx.accounts.randomness_account,
+        clock.slot,
+    )?;
+
+    finish_draw_only(
+        KeptFortuneAccounts {
+            user: ctx.accounts.user.key(),
+            user_state: &ctx.accounts.user_state,
+            user_incense_state: &mut ctx.accounts.user_incense_state,
+            pending_amulets: &mut ctx.accounts.pending_amulets,
+            pending_amulets_bump: ctx.bumps.pending_amulets,
+            fortune_history: &mut ctx.accounts.fortune_history,
+            fortune_history_bump: ctx.bumps.fortune_history,
+            temple_config: &ctx.accounts.temple_config,
+            fortune_table: &ctx.accounts.fortune_table,
+            pending_fortune: &mut ctx.accounts.pending_fortune,
+            pending_fortune_bump: ctx.bumps.pending_fortune,
+        },
+        &revealed,
+        draw,
+        draw_index,
+        now,
+    )
+}
+
+/// Roll the fortune and keep it as a PendingFortune, shared by draw_only and settle_fortune_only
+fn finish_draw_only(
+    accounts: KeptFortuneAccounts,
+    randomness: &impl RandomnessSource,
+    draw: PendingDraw,
+    draw_index: u32,
+    now: i64,
+) -> Result<DrawResult> {
+    let user_key = accounts.user;
+    let rolled = roll_fortune(
+        user_key,
+        accounts.user_state,
+        accounts.user_incense_state,
+        accounts.temple_config,
+        accounts.fortune_table,
+        randomness,
+        &draw,
+    )?;
+
+    // Keep the result for a later claim
+    let pending_fortune = accounts.pending_fortune;
+    pending_fortune.user = user_key;
+    pending_fortune.draw_index = draw_index;
+    pending_fortune.serial_number = rolled.serial_number;
+    pending_fortune.tier_index = rolled.tier_index;
+    pending_fortune.fortune_result = rolled.tier.category;
+    pending_fortune.drawn_at = now;
+    pending_fortune.merit_cost = rolled.merit_cost as u8;
+    pending_fortune.bump = accounts.pending_fortune_bump;
+    pending_fortune.tier_name = rolled.tier.name.clone();
+    pending_fortune.tier_uri = rolled.tier.uri.clone();
+
+    msg!("Fortune kept without minting, claim it with claim_fortune_nft");
+
+    accounts
+        .fortune_history
+        .ensure_initialized(user_key, accounts.fortune_history_bump);
+    accounts
+        .pending_amulets
+        .ensure_initialized(user_key, accounts.pending_amulets_bump);
+    record_fortune(
+        user_key,
+        accounts.pending_amulets,
+        accounts.fortune_history,
+        &rolled,
+        &draw,
+        now,
+    );
+
+    Ok(DrawResult {
+        fortune: rolled.tier.category,
+        tier_index: rolled.tier_index,
+        tier_name: rolled.tier.name,
+        timestamp: now,
+        used_merit: draw.use_merit,
+    })
+}
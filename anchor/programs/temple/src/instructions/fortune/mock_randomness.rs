@@ -0,0 +1,58 @@
+use crate::error::ErrorCode;
+use crate::state::mock_randomness::MockRandomness;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CommitMockRandomness<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MockRandomness::INIT_SPACE,
+        seeds = [MockRandomness::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub mock_randomness: Box<Account<'info, MockRandomness>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealMockRandomness<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [MockRandomness::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump = mock_randomness.bump,
+    )]
+    pub mock_randomness: Box<Account<'info, MockRandomness>>,
+}
+
+/// Commit to the previous slot, like Switchboard's randomness commit
+pub fn commit_mock_randomness(ctx: Context<CommitMockRandomness>) -> Result<()> {
+    let clock = Clock::get()?;
+    let mock = &mut ctx.accounts.mock_randomness;
+
+    mock.authority = ctx.accounts.authority.key();
+    mock.seed_slot = clock.slot.saturating_sub(1);
+    mock.reveal_slot = 0;
+    mock.value = [0u8; 32];
+    mock.bump = ctx.bumps.mock_randomness;
+
+    Ok(())
+}
+
+/// Reveal a chosen value in the current slot
+pub fn reveal_mock_randomness(ctx: Context<RevealMockRandomness>, value: [u8; 32]) -> Result<()> {
+    let clock = Clock::get()?;
+    let mock = &mut ctx.accounts.mock_randomness;
+
+    require!(mock.seed_slot < clock.slot, ErrorCode::RandomnessNotReady);
+    mock.reveal_slot = clock.slot;
+    mock.value = value;
+
+    Ok(())
+}
@@ -1,4 +1,26 @@
+pub mod claim_fortune_nft;
+pub use claim_fortune_nft::*;
+pub mod claim_fortune_set;
+pub use claim_fortune_set::*;
 pub mod draw_fortune;
 pub use draw_fortune::*;
+pub mod draw_only;
+pub use draw_only::*;
+#[cfg(feature = "localnet")]
+pub mod mock_randomness;
+#[cfg(feature = "localnet")]
+pub use mock_randomness::*;
+pub mod release_fortune;
+pub use release_fortune::*;
+pub mod request_fortune;
+pub use request_fortune::*;
+pub mod request_reroll;
+pub use request_reroll::*;
+pub mod resolve_fortune;
+pub use resolve_fortune::*;
+pub mod settle_fortune;
+pub use settle_fortune::*;
+pub mod settle_reroll;
+pub use settle_reroll::*;
 pub mod share_fortune;
 pub use share_fortune::*;
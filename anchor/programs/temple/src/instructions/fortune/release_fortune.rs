@@ -0,0 +1,159 @@
+use crate::error::ErrorCode;
+use crate::state::event::FortuneReleased;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::pending_reroll::PendingReroll;
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::UserIncenseState;
+use anchor_lang::prelude::*;
+use anchor_spl::token::burn;
+use anchor_spl::token::close_account;
+use anchor_spl::token::Burn;
+use anchor_spl::token::CloseAccount;
+use anchor_spl::token::Mint;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+/// Release an unwanted fortune: burn the NFT and close its FortuneNFT and token accounts.
+/// The mint and metadata accounts can't be closed and stay behind
+#[derive(Accounts)]
+#[instruction(draw_index: u32)]
+pub struct ReleaseFortune<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune NFT account of the draw, loaded through FortuneNFT::load_upgraded and closed
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"fortune_nft",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"fortune_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's fortune NFT associated account, must still hold the NFT
+    #[account(
+        mut,
+        associated_token::mint = fortune_nft_mint,
+        associated_token::authority = user,
+        constraint = fortune_nft_token_account.amount == 1 @ ErrorCode::InvalidOwner,
+    )]
+    pub fortune_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: User's pending reroll, may not exist, checked by PendingReroll::ensure_not_pending
+    #[account(
+        seeds = [PendingReroll::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_reroll: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_fortune(ctx: Context<ReleaseFortune>, draw_index: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    let fortune_nft_info = ctx.accounts.fortune_nft_account.to_account_info();
+    let user_info = ctx.accounts.user.to_account_info();
+    let fortune_nft = FortuneNFT::load_upgraded(
+        &fortune_nft_info,
+        &user_info,
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    require!(!fortune_nft.in_set, ErrorCode::FortuneInSet);
+    // A paid reroll could no longer be settled on a released fortune
+    PendingReroll::ensure_not_pending(&ctx.accounts.pending_reroll, draw_index, clock.slot)?;
+
+    // Burn the NFT and close its token account
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.fortune_nft_mint.to_account_info(),
+                from: ctx.accounts.fortune_nft_token_account.to_account_info(),
+                authority: user_info.clone(),
+            },
+        ),
+        1,
+    )?;
+    let rent_refunded = ctx
+        .accounts
+        .fortune_nft_token_account
+        .to_account_info()
+        .lamports()
+        .saturating_add(fortune_nft_info.lamports());
+    close_account(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.fortune_nft_token_account.to_account_info(),
+            destination: user_info.clone(),
+            authority: user_info.clone(),
+        },
+    ))?;
+    FortuneNFT::close(&fortune_nft_info, &user_info)?;
+
+    // Reward
+    let merit_reward = ctx
+        .accounts
+        .temple_config
+        .dynamic_config
+        .fortune_release
+        .merit_reward;
+    ctx.accounts
+        .user_incense_state
+        .add_incense_value_and_merit(0, merit_reward);
+    msg!(
+        "Fortune released, earned {} merit points reward",
+        merit_reward
+    );
+
+    emit!(FortuneReleased {
+        user: ctx.accounts.user.key(),
+        fortune_nft_mint: ctx.accounts.fortune_nft_mint.key(),
+        serial_number: fortune_nft.serial_number,
+        fortune_result: fortune_nft.fortune_result,
+        rent_refunded,
+        merit_reward,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use crate::error::ErrorCode;
+use crate::randomness::load_randomness;
+use crate::state::amulet::{apply_amulet, AmuletNFT, AmuletType};
+use crate::state::event::FortuneRequested;
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::{PendingDraw, UserIncenseState, UserState};
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct RequestFortune<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Randomness account committed in this transaction, verified by load_randomness
+    pub randomness_account: UncheckedAccount<'info>,
+
+    /// Fortune amulet held by the user (optional)
+    #[account(mut)]
+    pub fortune_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the fortune amulet
+    pub fortune_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Protection amulet held by the user (optional)
+    #[account(mut)]
+    pub protection_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the protection amulet
+    pub protection_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn request_fortune(ctx: Context<RequestFortune>, use_merit: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    // An unsettled request blocks new ones until its randomness can no longer be revealed.
+    // Its payment is forfeited, so abandoning a bad reveal never yields a free redraw.
+    if ctx.accounts.user_state.pending_random_request_id.is_some() {
+        let expires_at = ctx
+            .accounts
+            .user_state
+            .pending_draw
+            .commit_slot
+            .saturating_add(PendingDraw::EXPIRY_SLOTS);
+        require!(clock.slot > expires_at, ErrorCode::RandomnessNotResolved);
+    }
+
+    // The randomness must be committed in this transaction and not yet revealed
+    let randomness = load_randomness(&ctx.accounts.randomness_account)?;
+    require!(
+        randomness.is_fresh_commit(clock.slot),
+        ErrorCode::RandomnessExpired
+    );
+    require!(
+        randomness.revealed_value(clock.slot).is_none(),
+        ErrorCode::RandomnessAlreadyUsed
+    );
+
+    // Pay for the draw up front
+    let rates = ctx
+        .accounts
+        .temple_config
+        .activity_rates(&ctx.accounts.user_incense_state.title);
+    if use_merit {
+        ctx.accounts
+            .user_incense_state
+            .consume_merit_for_draw(rates.draw_merit_cost)?;
+    } else {
+        // Check if free draw is available
+        if !ctx
+            .accounts
+            .user_incense_state
+            .can_draw_free(&ctx.accounts.temple_config)
+        {
+            return err!(ErrorCode::DailyIncenseLimitExceeded);
+        }
+    }
+    ctx.accounts
+        .user_incense_state
+        .record_daily_draw(&ctx.accounts.temple_config);
+
+    // Verify amulets on-chain and consume their charges
+    let user_key = ctx.accounts.user.key();
+    let fortune_amulet = apply_amulet(
+        ctx.accounts.fortune_amulet.as_deref_mut(),
+        ctx.accounts.fortune_amulet_token_account.as_deref(),
+        &user_key,
+        AmuletType::Fortune,
+        now,
+    )?;
+    let protection_amulet = apply_amulet(
+        ctx.accounts.protection_amulet.as_deref_mut(),
+        ctx.accounts.protection_amulet_token_account.as_deref(),
+        &user_key,
+        AmuletType::Protection,
+        now,
+    )?;
+
+    // Bind the randomness account to the user's pending request
+    let user_state = &mut ctx.accounts.user_state;
+    user_state.pending_random_request_id = Some(ctx.accounts.randomness_account.key().to_bytes());
+    user_state.pending_draw = PendingDraw {
+        commit_slot: randomness.seed_slot,
+        use_merit,
+        fortune_amulet,
+        protection_amulet,
+    };
+    UserState::grow_to_fit(
+        user_state,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Fortune requested, randomness committed at slot {}",
+        randomness.seed_slot
+    );
+
+    emit!(FortuneRequested {
+        user: user_key,
+        randomness_account: ctx.accounts.randomness_account.key(),
+        commit_slot: randomness.seed_slot,
+        used_merit: use_merit,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use crate::error::ErrorCode;
+use crate::randomness::load_randomness;
+use crate::state::event::FortuneRerollRequested;
+use crate::state::fortune_history::FortuneHistory;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::pending_reroll::PendingReroll;
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::{PendingDraw, UserIncenseState};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(draw_index: u32)]
+pub struct RequestReroll<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    /// Fortune history ledger, holds the daily reroll count
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FortuneHistory::INIT_SPACE,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    /// Reroll bound to the randomness account, one per user at a time
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PendingReroll::INIT_SPACE,
+        seeds = [PendingReroll::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_reroll: Box<Account<'info, PendingReroll>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune NFT account of the draw, loaded through FortuneNFT::load_upgraded
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"fortune_nft",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"fortune_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's fortune NFT associated account, must still hold the NFT
+    #[account(
+        associated_token::mint = fortune_nft_mint,
+        associated_token::authority = user,
+        constraint = fortune_nft_token_account.amount == 1 @ ErrorCode::InvalidOwner,
+    )]
+    pub fortune_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Randomness account committed in this transaction, verified by load_randomness
+    pub randomness_account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn request_reroll(ctx: Context<RequestReroll>, draw_index: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    // An unsettled reroll blocks new ones until its randomness can no longer be revealed.
+    // Its payment is forfeited, so abandoning a bad reveal never yields a free reroll.
+    let user_key = ctx.accounts.user.key();
+    if ctx.accounts.pending_reroll.user != Pubkey::default() {
+        let expires_at = ctx
+            .accounts
+            .pending_reroll
+            .commit_slot
+            .saturating_add(PendingDraw::EXPIRY_SLOTS);
+        require!(clock.slot > expires_at, ErrorCode::RandomnessNotResolved);
+    }
+
+    // The randomness must be committed in this transaction and not yet revealed
+    let randomness = load_randomness(&ctx.accounts.randomness_account)?;
+    require!(
+        randomness.is_fresh_commit(clock.slot),
+        ErrorCode::RandomnessExpired
+    );
+    require!(
+        randomness.revealed_value(clock.slot).is_none(),
+        ErrorCode::RandomnessAlreadyUsed
+    );
+
+    let fortune_nft = FortuneNFT::load_upgraded(
+        &ctx.accounts.fortune_nft_account.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    require!(!fortune_nft.resolved, ErrorCode::FortuneAlreadyResolved);
+    require!(!fortune_nft.in_set, ErrorCode::FortuneInSet);
+
+    // Daily limit, each reroll of the day costs more than the last
+    ctx.accounts
+        .fortune_history
+        .ensure_initialized(user_key, ctx.bumps.fortune_history);
+    let config = &ctx.accounts.temple_config.dynamic_config.fortune_reroll;
+    let rerolls_today = ctx
+        .accounts
+        .fortune_history
+        .rerolls_today(&ctx.accounts.temple_config, now);
+    require!(
+        rerolls_today < config.max_rerolls_per_day,
+        ErrorCode::RerollLimitExceeded
+    );
+    let merit_cost = config.merit_cost(rerolls_today)?;
+
+    // Pay for the reroll up front
+    let user_incense_state = &mut ctx.accounts.user_incense_state;
+    require!(
+        user_incense_state.merit >= merit_cost,
+        ErrorCode::InsufficientMerit
+    );
+    user_incense_state.merit -= merit_cost;
+    ctx.accounts
+        .fortune_history
+        .count_reroll(&ctx.accounts.temple_config, now);
+
+    // Bind the randomness account to the reroll
+    let pending_reroll = &mut ctx.accounts.pending_reroll;
+    pending_reroll.user = user_key;
+    pending_reroll.draw_index = draw_index;
+    pending_reroll.randomness_account = ctx.accounts.randomness_account.key();
+    pending_reroll.commit_slot = randomness.seed_slot;
+    pending_reroll.merit_cost = merit_cost;
+    pending_reroll.bump = ctx.bumps.pending_reroll;
+
+    msg!(
+        "Reroll requested, randomness committed at slot {}",
+        randomness.seed_slot
+    );
+
+    emit!(FortuneRerollRequested {
+        user: user_key,
+        fortune_nft_mint: ctx.accounts.fortune_nft_mint.key(),
+        randomness_account: ctx.accounts.randomness_account.key(),
+        commit_slot: randomness.seed_slot,
+        merit_cost,
+        timestamp: now,
+    });
+
+    Ok(())
+}
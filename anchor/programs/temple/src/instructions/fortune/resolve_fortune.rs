@@ -0,0 +1,325 @@
+use crate::error::ErrorCode;
+use crate::incense_nft::IncenseNFT;
+use crate::state::event::FortuneResolved;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::fortune_table::FortuneTable;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
+use crate::state::pending_reroll::PendingReroll;
+use crate::state::temple_config::{ResolvePayment, TempleConfig};
+use crate::state::user_incense_inventory::UserIncenseInventory;
+use crate::state::user_state::UserIncenseState;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::update_metadata_accounts_v2;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::metadata::UpdateMetadataAccountsV2;
+use anchor_spl::token::burn;
+use anchor_spl::token::Burn;
+use anchor_spl::token::Mint;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+#[instruction(draw_index: u32)]
+pub struct ResolveFortune<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    /// CHECK: Fortune NFT account of the draw, loaded through FortuneNFT::load_upgraded
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"fortune_nft",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"fortune_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's fortune NFT associated account, must still hold the NFT
+    #[account(
+        associated_token::mint = fortune_nft_mint,
+        associated_token::authority = user,
+        constraint = fortune_nft_token_account.amount == 1 @ ErrorCode::InvalidOwner,
+    )]
+    pub fortune_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: User's pending reroll, may not exist, checked by PendingReroll::ensure_not_pending
+    #[account(
+        seeds = [PendingReroll::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_reroll: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            fortune_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub fortune_nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// Mint of the configured resolve incense (incense payment only)
+    #[account(
+        mut,
+        seeds = [
+            IncenseNFT::SEED_PREFIX.as_bytes(),
+            temple_config.key().as_ref(),
+            &[temple_config.dynamic_config.fortune_resolve.incense_id.unwrap_or_default()],
+        ],
+        bump,
+    )]
+    pub incense_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// User's token account of the resolve incense (incense payment only)
+    #[account(mut)]
+    pub incense_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// User incense inventory, prepaid balance used after held tokens (incense payment only)
+    #[account(
+        mut,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = user_incense_inventory.bump,
+    )]
+    pub user_incense_inventory: Option<Box<Account<'info, UserIncenseInventory>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn resolve_fortune(
+    ctx: Context<ResolveFortune>,
+    draw_index: u32,
+    payment: ResolvePayment,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    let fortune_nft_info = ctx.accounts.fortune_nft_account.to_account_info();
+    let mut fortune_nft = FortuneNFT::load_upgraded(
+        &fortune_nft_info,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    // A paid reroll could no longer be settled on a resolved fortune
+    PendingReroll::ensure_not_pending(&ctx.accounts.pending_reroll, draw_index, clock.slot)?;
+
+    // Only an unresolved bad fortune, within the window after minting
+    require!(
+        fortune_nft.fortune_result.is_unlucky(),
+        ErrorCode::FortuneNotResolvable
+    );
+    require!(!fortune_nft.resolved, ErrorCode::FortuneAlreadyResolved);
+    require!(!fortune_nft.in_set, ErrorCode::FortuneInSet);
+    let config = ctx
+        .accounts
+        .temple_config
+        .dynamic_config
+        .fortune_resolve
+        .clone();
+    require!(
+        config.window_seconds > 0
+            && now.saturating_sub(fortune_nft.minted_at) <= config.window_seconds as i64,
+        ErrorCode::ResolveWindowExpired
+    );
+
+    // Pay for the ritual
+    match payment {
+        ResolvePayment::Incense => {
+            let incense_id = config.incense_id.ok_or(ErrorCode::ResolvePaymentDisabled)?;
+            let amount = config.incense_amount as u64;
+
+            // Burn held incense tokens first, then the prepaid balance
+            let held = ctx
+                .accounts
+                .incense_token_account
+                .as_ref()
+                .map_or(0, |account| account.amount);
+            let from_tokens = held.min(amount);
+            let from_balance = amount - from_tokens;
+
+            if from_tokens > 0 {
+                let (Some(incense_mint), Some(incense_token_account)) = (
+                    ctx.accounts.incense_mint.as_ref(),
+                    ctx.accounts.incense_token_account.as_ref(),
+                ) else {
+                    return err!(ErrorCode::InsufficientIncenseBalance);
+                };
+                burn(
+                    CpiContext::new(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: incense_mint.to_account_info(),
+                            from: incense_token_account.to_account_info(),
+                            authority: ctx.accounts.user.to_account_info(),
+                        },
+                    ),
+                    from_tokens,
+                )?;
+            }
+            if from_balance > 0 {
+                ctx.accounts
+                    .user_incense_inventory
+                    .as_mut()
+                    .ok_or(ErrorCode::InsufficientIncenseBalance)?
+                    .subtract_balance(incense_id, from_balance)?;
+            }
+            msg!(
+                "Burned {} of incense type {} to resolve",
+                amount,
+                incense_id
+            );
+        }
+        ResolvePayment::Merit => {
+            let merit_cost = config.merit_cost.ok_or(ErrorCode::ResolvePaymentDisabled)?;
+            let user_incense_state = &mut ctx.accounts.user_incense_state;
+            require!(
+                user_incense_state.merit >= merit_cost,
+                ErrorCode::InsufficientMerit
+            );
+            user_incense_state.merit -= merit_cost;
+            msg!("Spent {} merit to resolve", merit_cost);
+        }
+    }
+
+    // Upgrade the metadata to the resolved template
+    let tier = FortuneTable::get_tier(
+        &FortuneTable::data(&ctx.accounts.fortune_table)?,
+        fortune_nft.tier_index,
+    )?;
+    let data = ctx
+        .accounts
+        .metadata_config
+        .template(NftFamily::ResolvedFortune)
+        .to_data(&MetadataVars {
+            name: &tier.name,
+            serial: fortune_nft.serial_number as u64,
+            variant: &tier.uri,
+            ..Default::default()
+        })?;
+
+    update_fortune_metadata(
+        &ctx.accounts.token_metadata_program,
+        &ctx.accounts.fortune_nft_metadata,
+        &ctx.accounts.temple_config,
+        ctx.bumps.temple_config,
+        &ctx.accounts.user,
+        data,
+    )?;
+
+    fortune_nft.resolved = true;
+    fortune_nft.try_serialize(&mut &mut fortune_nft_info.try_borrow_mut_data()?[..])?;
+
+    // Reward
+    ctx.accounts
+        .user_incense_state
+        .add_incense_value_and_merit(0, config.merit_reward);
+    msg!(
+        "Fortune resolved, earned {} merit points reward",
+        config.merit_reward
+    );
+
+    emit!(FortuneResolved {
+        user: ctx.accounts.user.key(),
+        fortune_nft_mint: ctx.accounts.fortune_nft_mint.key(),
+        serial_number: fortune_nft.serial_number,
+        paid_with_incense: payment == ResolvePayment::Incense,
+        merit_reward: config.merit_reward,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+/// Rewrite a Fortune NFT's metadata, shared by resolve_fortune and reroll_fortune.
+/// Fortune NFTs are updated by the temple, ones minted before that by their drawer.
+pub(crate) fn update_fortune_metadata<'info>(
+    token_metadata_program: &Program<'info, Metadata>,
+    metadata: &Account<'info, MetadataAccount>,
+    temple_config: &Account<'info, TempleConfig>,
+    temple_config_bump: u8,
+    user: &Signer<'info>,
+    data: DataV2,
+) -> Result<()> {
+    let update_authority = if metadata.update_authority == temple_config.key() {
+        temple_config.to_account_info()
+    } else {
+        require_keys_eq!(
+            metadata.update_authority,
+            user.key(),
+            ErrorCode::InvalidOwner
+        );
+        user.to_account_info()
+    };
+    let temple_signer_seeds: &[&[&[u8]]] =
+        &[&[TempleConfig::SEED_PREFIX.as_bytes(), &[temple_config_bump]]];
+    update_metadata_accounts_v2(
+        CpiContext::new_with_signer(
+            token_metadata_program.to_account_info(),
+            UpdateMetadataAccountsV2 {
+                metadata: metadata.to_account_info(),
+                update_authority,
+            },
+            temple_signer_seeds,
+        ),
+        None,
+        Some(data),
+        None,
+        None,
+    )
+}
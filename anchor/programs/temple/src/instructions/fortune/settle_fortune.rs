@@ -0,0 +1,253 @@
+use crate::error::ErrorCode;
+use crate::instructions::fortune::draw_fortune::{
+    mint_fortune_nft, record_fortune, roll_fortune, DrawResult, FortuneMintAccounts,
+};
+use crate::randomness::{load_randomness, RevealedRandomness};
+use crate::state::fortune_history::FortuneHistory;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::fortune_table::FortuneTable;
+use crate::state::metadata_config::MetadataConfig;
+use crate::state::pending_amulets::PendingAmulets;
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::{PendingDraw, UserIncenseState, UserState};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::token::Mint;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+/// Settle a requested fortune: the amulets and payment were taken by request_fortune,
+/// so only the Fortune NFT accounts and the bound randomness account are needed
+#[derive(Accounts)]
+pub struct SettleFortune<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [UserState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    /// Fortune history ledger, updated on every draw
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + FortuneHistory::INIT_SPACE,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    // Fortune NFT accounts
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [
+            b"fortune_nft",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &user_incense_state.total_draws.to_string().as_bytes(),
+        ],
+        bump,
+        space = 8 + FortuneNFT::INIT_SPACE,
+    )]
+    pub fortune_nft_account: Box<Account<'info, FortuneNFT>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [
+            b"fortune_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &user_incense_state.total_draws.to_string().as_bytes(),
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = temple_config.key(),
+        mint::freeze_authority = temple_config.key(),
+    )]
+    pub fortune_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's fortune NFT associated account
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = fortune_nft_mint,
+        associated_token::authority = user,
+    )]
+    pub fortune_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Fortune NFT metadata account
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            fortune_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub fortune_nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Randomness account bound by request_fortune, verified by take_revealed_draw
+    pub randomness_account: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn settle_fortune(ctx: Context<SettleFortune>) -> Result<DrawResult> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    let (draw, revealed) = take_revealed_draw(
+        &mut ctx.accounts.user_state,
+        &mut ctx.accounts.user_incense_state,
+        &ctx.accounts.randomness_account,
+        clock.slot,
+    )?;
+
+    let user_key = ctx.accounts.user.key();
+    let rolled = roll_fortune(
+        user_key,
+        &ctx.accounts.user_state,
+        &mut ctx.accounts.user_incense_state,
+        &ctx.accounts.temple_config,
+        &ctx.accounts.fortune_table,
+        &revealed,
+        &draw,
+    )?;
+
+    mint_fortune_nft(
+        FortuneMintAccounts {
+            user: ctx.accounts.user.to_account_info(),
+            temple_config: ctx.accounts.temple_config.to_account_info(),
+            temple_config_bump: ctx.bumps.temple_config,
+            metadata_config: &ctx.accounts.metadata_config,
+            fortune_nft_account: &mut ctx.accounts.fortune_nft_account,
+            fortune_nft_mint: ctx.accounts.fortune_nft_mint.to_account_info(),
+            fortune_nft_token_account: ctx.accounts.fortune_nft_token_account.to_account_info(),
+            fortune_nft_metadata: ctx.accounts.fortune_nft_metadata.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+        },
+        &rolled,
+        now,
+    )?;
+
+    ctx.accounts
+        .fortune_history
+        .ensure_initialized(user_key, ctx.bumps.fortune_history);
+    ctx.accounts
+        .pending_amulets
+        .ensure_initialized(user_key, ctx.bumps.pending_amulets);
+    record_fortune(
+        user_key,
+        &mut ctx.accounts.pending_amulets,
+        &mut ctx.accounts.fortune_history,
+        &rolled,
+        &draw,
+        now,
+    );
+
+    Ok(DrawResult {
+        fortune: rolled.tier.category,
+        tier_index: rolled.tier_index,
+        tier_name: rolled.tier.name,
+        timestamp: now,
+        used_merit: draw.use_merit,
+    })
+}
+
+/// Consume the user's pending request once its randomness is revealed,
+/// shared by settle_fortune and settle_fortune_only
+pub(crate) fn take_revealed_draw(
+    user_state: &mut UserState,
+    user_incense_state: &mut UserIncenseState,
+    randomness_account: &UncheckedAccount,
+    clock_slot: u64,
+) -> Result<(PendingDraw, RevealedRandomness)> {
+    let request_id = user_state
+        .pending_random_request_id
+        .ok_or(ErrorCode::RandomnessRequestRequired)?;
+    let draw = user_state.pending_draw;
+
+    // Only the randomness account bound by request_fortune can settle the draw
+    require!(
+        randomness_account.key().to_bytes() == request_id,
+        ErrorCode::InvalidRandomness
+    );
+
+    // A re-committed account no longer carries the value this request was bound to
+    let randomness = load_randomness(randomness_account)?;
+    require!(
+        randomness.seed_slot == draw.commit_slot,
+        ErrorCode::RandomnessExpired
+    );
+    let revealed = randomness
+        .revealed(clock_slot)
+        .ok_or(ErrorCode::RandomnessNotResolved)?;
+
+    // Consume the request so the same reveal can never settle twice
+    user_state.pending_random_request_id = None;
+    user_state.pending_draw = PendingDraw::default();
+
+    // The daily count was recorded at request time
+    user_incense_state.total_draws = user_incense_state.total_draws.saturating_add(1);
+
+    Ok((draw, revealed))
+}
@@ -0,0 +1,220 @@
+use crate::error::ErrorCode;
+use crate::instructions::fortune::resolve_fortune::update_fortune_metadata;
+use crate::randomness::{load_randomness, RandomnessSource, FORTUNE_REROLL_DOMAIN};
+use crate::state::event::FortuneRerolled;
+use crate::state::fortune_history::FortuneHistory;
+use crate::state::fortune_nft::FortuneNFT;
+use crate::state::fortune_table::FortuneTable;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
+use crate::state::pending_reroll::PendingReroll;
+use crate::state::temple_config::TempleConfig;
+use crate::state::user_state::UserState;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token::Mint;
+use anchor_spl::token::TokenAccount;
+
+/// Settle a requested reroll: the merit was taken by request_reroll,
+/// so only the Fortune NFT accounts and the bound randomness account are needed
+#[derive(Accounts)]
+#[instruction(draw_index: u32)]
+pub struct SettleReroll<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [UserState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = user_state.bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// Fortune history ledger, created by request_reroll
+    #[account(
+        mut,
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = fortune_history.bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    /// Reroll being settled, closed back to the user
+    #[account(
+        mut,
+        close = user,
+        seeds = [PendingReroll::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = pending_reroll.bump,
+        constraint = pending_reroll.draw_index == draw_index @ ErrorCode::FortuneNotFound,
+    )]
+    pub pending_reroll: Box<Account<'info, PendingReroll>>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Fortune table, read in place through FortuneTable
+    #[account(
+        owner = crate::ID,
+        seeds = [FortuneTable::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub fortune_table: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
+    /// CHECK: Fortune NFT account of the draw, loaded through FortuneNFT::load_upgraded
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            b"fortune_nft",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            b"fortune_nft_mint",
+            temple_config.key().as_ref(),
+            user.key().as_ref(),
+            &draw_index.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub fortune_nft_mint: Box<Account<'info, Mint>>,
+
+    /// User's fortune NFT associated account, must still hold the NFT
+    #[account(
+        associated_token::mint = fortune_nft_mint,
+        associated_token::authority = user,
+        constraint = fortune_nft_token_account.amount == 1 @ ErrorCode::InvalidOwner,
+    )]
+    pub fortune_nft_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            fortune_nft_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub fortune_nft_metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: Randomness account bound by request_reroll, verified in the handler
+    pub randomness_account: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn settle_reroll(ctx: Context<SettleReroll>, _draw_index: u32) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+    let now = clock.unix_timestamp;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::DrawFortune,
+        current_time,
+    )?;
+
+    // Only the randomness account bound by request_reroll can settle the reroll
+    let pending_reroll = &ctx.accounts.pending_reroll;
+    require_keys_eq!(
+        ctx.accounts.randomness_account.key(),
+        pending_reroll.randomness_account,
+        ErrorCode::InvalidRandomness
+    );
+    // A re-committed account no longer carries the value this request was bound to
+    let randomness = load_randomness(&ctx.accounts.randomness_account)?;
+    require!(
+        randomness.seed_slot == pending_reroll.commit_slot,
+        ErrorCode::RandomnessExpired
+    );
+    let revealed = randomness
+        .revealed(clock.slot)
+        .ok_or(ErrorCode::RandomnessNotResolved)?;
+
+    let fortune_nft_info = ctx.accounts.fortune_nft_account.to_account_info();
+    let mut fortune_nft = FortuneNFT::load_upgraded(
+        &fortune_nft_info,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+    require!(!fortune_nft.resolved, ErrorCode::FortuneAlreadyResolved);
+    require!(!fortune_nft.in_set, ErrorCode::FortuneInSet);
+
+    // Redraw on the revealed value, amulets only apply to the original draw
+    let user_key = ctx.accounts.user.key();
+    let nonce = ((fortune_nft.serial_number as u64) << 8) | fortune_nft.rerolls as u64;
+    let roll = revealed.roll_u64(FORTUNE_REROLL_DOMAIN, &user_key, nonce);
+    let (tier_index, tier) = FortuneTable::sample_tier(
+        &FortuneTable::data(&ctx.accounts.fortune_table)?,
+        roll,
+        ctx.accounts.user_state.has_buddha_nft,
+        false,
+        false,
+    )?;
+
+    let data = ctx
+        .accounts
+        .metadata_config
+        .template(NftFamily::Fortune)
+        .to_data(&MetadataVars {
+            name: &tier.name,
+            variant: &tier.uri,
+            ..Default::default()
+        })?;
+    update_fortune_metadata(
+        &ctx.accounts.token_metadata_program,
+        &ctx.accounts.fortune_nft_metadata,
+        &ctx.accounts.temple_config,
+        ctx.bumps.temple_config,
+        &ctx.accounts.user,
+        data,
+    )?;
+
+    // Keep the original result in the event and history record, the NFT holds the new one
+    let previous_tier_index = fortune_nft.tier_index;
+    let previous_result = fortune_nft.fortune_result;
+    fortune_nft.tier_index = tier_index;
+    fortune_nft.fortune_result = tier.category;
+    fortune_nft.rerolls = fortune_nft.rerolls.saturating_add(1);
+    fortune_nft.try_serialize(&mut &mut fortune_nft_info.try_borrow_mut_data()?[..])?;
+
+    ctx.accounts.fortune_history.record_reroll(
+        fortune_nft.serial_number,
+        previous_result,
+        tier.category,
+    );
+
+    msg!("Reroll result: {}", tier.name);
+    msg!("Fortune explanation: {}", tier.description);
+
+    emit!(FortuneRerolled {
+        user: user_key,
+        fortune_nft_mint: ctx.accounts.fortune_nft_mint.key(),
+        serial_number: fortune_nft.serial_number,
+        previous_tier_index,
+        previous_result,
+        tier_index,
+        fortune_result: tier.category,
+        merit_cost: ctx.accounts.pending_reroll.merit_cost,
+        reroll_count: fortune_nft.rerolls,
+        timestamp: now,
+    });
+
+    Ok(())
+}
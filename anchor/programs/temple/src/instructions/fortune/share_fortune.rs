@@ -1,9 +1,15 @@
+use crate::attestation::verify_ed25519_attestation;
 use crate::error::ErrorCode;
+use crate::state::event::FortuneShared;
+use crate::state::fortune_history::FortuneHistory;
+use crate::state::share_record::ShareRecord;
 use crate::state::temple_config::TempleConfig;
 use crate::state::user_state::{UserIncenseState, UserState};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
 
 #[derive(Accounts)]
+#[instruction(serial_number: u32)]
 pub struct ShareFortune<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
@@ -22,28 +28,102 @@ pub struct ShareFortune<'info> {
     )]
     pub user_incense_state: Box<Account<'info, UserIncenseState>>,
 
+    /// Fortune history, proves the shared draw and its time
+    #[account(
+        seeds = [FortuneHistory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump = fortune_history.bump,
+    )]
+    pub fortune_history: Box<Account<'info, FortuneHistory>>,
+
+    /// One share per draw, creation fails on a repeated share
+    #[account(
+        init,
+        payer = user,
+        space = 8 + ShareRecord::INIT_SPACE,
+        seeds = [
+            ShareRecord::SEED_PREFIX.as_bytes(),
+            user.key().as_ref(),
+            &serial_number.to_string().as_bytes(),
+        ],
+        bump,
+    )]
+    pub share_record: Box<Account<'info, ShareRecord>>,
+
     #[account(
         seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
         bump,
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    /// CHECK: Instructions sysvar, holds the attester's Ed25519 signature check
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
-pub fn share_fortune(ctx: Context<ShareFortune>, share_hash: [u8; 32]) -> Result<()> {
-    // Verify user has drawn fortune in the last day
+pub fn share_fortune(
+    ctx: Context<ShareFortune>,
+    serial_number: u32,
+    share_hash: [u8; 32],
+) -> Result<()> {
+    // Verify the shared draw happened in the last day
     let now = Clock::get()?.unix_timestamp;
-    let time_since_last_draw: i64 = now - ctx.accounts.user_incense_state.last_draw_time;
+    let record = ctx
+        .accounts
+        .fortune_history
+        .find(serial_number)
+        .ok_or(ErrorCode::FortuneNotFound)?;
+    let time_since_draw: i64 = now - record.drawn_at;
 
-    require!(
-        time_since_last_draw <= 24 * 60 * 60,
-        ErrorCode::ShareTooLate
-    );
+    require!(time_since_draw <= 24 * 60 * 60, ErrorCode::ShareTooLate);
+
+    // Shares only count when co-signed by the attester, if one is configured
+    let user_key = ctx.accounts.user.key();
+    let attester = ctx
+        .accounts
+        .temple_config
+        .dynamic_config
+        .activity_economy
+        .share_attester;
+    if let Some(attester) = attester {
+        verify_ed25519_attestation(
+            &ctx.accounts.instructions,
+            &attester,
+            &ShareRecord::attestation_message(&user_key, serial_number, &share_hash),
+        )?;
+    }
+
+    let share_record = &mut ctx.accounts.share_record;
+    share_record.user = user_key;
+    share_record.serial_number = serial_number;
+    share_record.share_hash = share_hash;
+    share_record.shared_at = now;
+    share_record.attested = attester.is_some();
+    share_record.bump = ctx.bumps.share_record;
 
     // Reward
+    let share_merit_reward = ctx
+        .accounts
+        .temple_config
+        .activity_rates(&ctx.accounts.user_incense_state.title)
+        .share_merit_reward;
     ctx.accounts
         .user_incense_state
-        .add_incense_value_and_merit(0, 1);
-    msg!("Fortune sharing successful, earned 0.1 merit points reward");
+        .add_incense_value_and_merit(0, share_merit_reward);
+    msg!(
+        "Fortune sharing successful, earned {} merit points reward",
+        share_merit_reward
+    );
+
+    emit!(FortuneShared {
+        user: user_key,
+        serial_number,
+        share_hash,
+        merit_reward: share_merit_reward,
+        attested: attester.is_some(),
+        timestamp: now,
+    });
 
     Ok(())
 }
@@ -1,10 +1,21 @@
 use crate::error::ErrorCode;
 use crate::incense_nft::IncenseNFT;
-use crate::state::event::IncenseBurned;
+use crate::payment::{resolve_token_payment, transfer_token_payment};
+use crate::price::{load_sol_usd_price, SolUsdPrice};
+use crate::randomness::{RandomnessSource, SlotHashesRandomness, INCENSE_AMULET_DROP_DOMAIN};
+use crate::state::altar::{Altar, AltarStick};
+use crate::state::amulet::{apply_amulet, AmuletNFT, AmuletType};
+use crate::state::dedication_pool::DedicationPool;
+use crate::state::event::{IncenseBurned, IncenseDedicated, StreakUpdated};
 use crate::state::global_stats::GlobalStats;
+use crate::state::medal_nft::MedalNFT;
+use crate::state::pending_amulets::PendingAmulets;
 use crate::state::temple_config::*;
+use crate::state::user_incense_inventory::UserIncenseInventory;
 use crate::state::user_state::{UserIncenseState, UserState};
+use crate::state::user_streak::UserStreak;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use anchor_lang::system_program::transfer;
 use anchor_lang::system_program::Transfer;
 use anchor_spl::associated_token::AssociatedToken;
@@ -16,11 +27,19 @@ use anchor_spl::token::Mint;
 use anchor_spl::token::MintTo;
 use anchor_spl::token::Token;
 use anchor_spl::token::TokenAccount;
+
+/// Who receives the merit of a dedicated burn (回向)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Beneficiary {
+    User(Pubkey),       // Another user's incense state
+    Memorial([u8; 32]), // Hash of a memorial record, credited to the dedication pool
+}
+
 pub fn burn_incense(
     ctx: Context<BurnIncense>,
     incense_id: u8,
     amount: u64,
-    has_merit_amulet: bool,
+    beneficiary: Option<Beneficiary>,
 ) -> Result<()> {
     let clock = Clock::get()?;
     let current_time = clock.unix_timestamp as u64;
@@ -31,161 +50,537 @@ pub fn burn_incense(
         current_time,
     )?;
 
-    let incense_type = ctx
-        .accounts
-        .temple_config
-        .find_incense_type(incense_id)
-        .ok_or(ErrorCode::InvalidIncenseId)?;
-
-    let incense_points = incense_type.incense_points as u64;
-    let merit = incense_type.merit as u64;
+    // Check the beneficiary account matches the dedication
+    match beneficiary {
+        Some(Beneficiary::User(user)) => {
+            require_keys_neq!(
+                user,
+                ctx.accounts.authority.key(),
+                ErrorCode::InvalidBeneficiary
+            );
+            let beneficiary_state = ctx
+                .accounts
+                .beneficiary_incense_state
+                .as_ref()
+                .ok_or(ErrorCode::InvalidBeneficiary)?;
+            require_keys_eq!(beneficiary_state.user, user, ErrorCode::InvalidBeneficiary);
+        }
+        Some(Beneficiary::Memorial(_)) => {
+            require!(
+                ctx.accounts.dedication_pool.is_some(),
+                ErrorCode::InvalidBeneficiary
+            );
+        }
+        None => {}
+    }
 
-    // Check daily limit
+    // Users who skipped migrate_incense_inventory get their legacy slots moved on first use
+    ctx.accounts.user_incense_inventory.ensure_initialized(
+        ctx.accounts.authority.key(),
+        ctx.bumps.user_incense_inventory,
+    );
     ctx.accounts
-        .user_incense_state
-        .check_daily_incense_limit(incense_id, amount as u8)?;
-
-    if amount == 0 {
-        return err!(ErrorCode::InvalidAmount);
-    }
+        .user_incense_inventory
+        .absorb_legacy(&mut ctx.accounts.user_incense_state)?;
+
+    let burner = IncenseBurner {
+        authority: &ctx.accounts.authority,
+        temple_config: &ctx.accounts.temple_config,
+        temple_treasury: &ctx.accounts.temple_treasury,
+        payment_mint: ctx.accounts.payment_mint.as_deref(),
+        payer_token_account: ctx.accounts.payer_token_account.as_deref(),
+        treasury_token_account: ctx.accounts.treasury_token_account.as_deref(),
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+        token_payment: resolve_token_payment(
+            &ctx.accounts.temple_config,
+            ctx.accounts.payment_mint.as_deref(),
+            ctx.accounts.payer_token_account.as_deref(),
+            ctx.accounts.treasury_token_account.as_deref(),
+            &ctx.accounts.authority.key(),
+        )?,
+        sol_usd: load_sol_usd_price(ctx.accounts.sol_usd_price.as_deref())?,
+        medal_level: ctx
+            .accounts
+            .medal_nft_account
+            .as_ref()
+            .map_or(0, |medal| medal.level),
+        now: clock.unix_timestamp,
+    };
+
+    // Check daily cap and cooldown, then pay for the incense not held or prepaid
+    let plan = burner.plan(
+        &ctx.accounts.user_incense_state,
+        &ctx.accounts.user_incense_inventory,
+        incense_id,
+        amount,
+        IncenseMintAccounts {
+            mint: ctx.accounts.nft_mint_account.to_account_info(),
+            mint_bump: ctx.bumps.nft_mint_account,
+            token_account: ctx.accounts.nft_associated_token_account.to_account_info(),
+        },
+    )?;
+    burner.pay(&mut ctx.accounts.global_stats, plan.fee)?;
+    burner.consume(
+        &plan,
+        &mut ctx.accounts.user_incense_state,
+        &mut ctx.accounts.user_incense_inventory,
+        ctx.accounts.altar.as_deref_mut(),
+    )?;
 
-    let fee_per_incense = ctx.accounts.temple_config.get_fee_per_incense(incense_id);
-    let total_fee = fee_per_incense
-        .checked_mul(amount)
-        .ok_or(ErrorCode::MathOverflow)?;
+    // Verify merit amulet on-chain and consume a charge
+    let authority_key = ctx.accounts.authority.key();
+    let has_merit_amulet = apply_amulet(
+        ctx.accounts.merit_amulet.as_deref_mut(),
+        ctx.accounts.merit_amulet_token_account.as_deref(),
+        &authority_key,
+        AmuletType::Merit,
+        clock.unix_timestamp,
+    )?;
 
-    // enough balance
-    if ctx.accounts.authority.lamports() < total_fee {
-        return err!(ErrorCode::InsufficientSolBalance);
+    // Burning streak bonus
+    let streak_bonus_percent = update_streak(
+        &mut ctx.accounts.user_streak,
+        ctx.bumps.user_streak,
+        &ctx.accounts.temple_config,
+        authority_key,
+        clock.unix_timestamp,
+    );
+    let final_merit = merit_with_bonuses(plan.merit, has_merit_amulet, streak_bonus_percent)?;
+    let incense_points = plan.incense_points;
+
+    // Update incense points and merit of the burner, or split the merit between the giver and the beneficiary
+    let drop_nonce = ctx.accounts.user_incense_state.incense_points;
+    match beneficiary {
+        None => {
+            ctx.accounts
+                .user_incense_state
+                .add_incense_value_and_merit(incense_points, final_merit);
+        }
+        Some(dedication) => {
+            let (giver_merit, beneficiary_merit) = ctx
+                .accounts
+                .temple_config
+                .dynamic_config
+                .dedication
+                .split_merit(final_merit)?;
+            ctx.accounts
+                .user_incense_state
+                .add_incense_value_and_merit(0, giver_merit);
+
+            let (beneficiary_key, memorial_hash) = match dedication {
+                Beneficiary::User(user) => {
+                    if let Some(beneficiary_state) = ctx.accounts.beneficiary_incense_state.as_mut()
+                    {
+                        beneficiary_state
+                            .add_incense_value_and_merit(incense_points, beneficiary_merit);
+                    }
+                    (Some(user), None)
+                }
+                Beneficiary::Memorial(memorial_hash) => {
+                    let temple_config_key = ctx.accounts.temple_config.key();
+                    if let Some(dedication_pool) = ctx.accounts.dedication_pool.as_mut() {
+                        dedication_pool.ensure_initialized(
+                            temple_config_key,
+                            ctx.bumps.dedication_pool.unwrap_or_default(),
+                        );
+                        dedication_pool.add_dedication(incense_points, beneficiary_merit);
+                    }
+                    (None, Some(memorial_hash))
+                }
+            };
+
+            emit!(IncenseDedicated {
+                giver: ctx.accounts.authority.key(),
+                beneficiary: beneficiary_key,
+                memorial_hash,
+                incense_id,
+                amount,
+                incense_points,
+                merit: beneficiary_merit,
+                giver_merit,
+                timestamp: clock.unix_timestamp,
+            });
+        }
     }
 
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.authority.to_account_info(),
-                to: ctx.accounts.temple_treasury.to_account_info(),
-            },
-        ),
-        total_fee,
-    )?;
+    // Update global stats with merit and incense points
+    ctx.accounts
+        .global_stats
+        .add_incense_value_and_merit(incense_points, plan.merit);
 
-    msg!(
-        "User bought {} of incense type {} (total fee: {} SOL)",
-        amount,
+    // Roll amulet drop on the latest slot hash (not on mainnet), keyed by the pre-burn incense points
+    let randomness = SlotHashesRandomness::load(&ctx.accounts.slot_hashes)?;
+    burner.roll_amulet_drop(
+        &randomness,
+        drop_nonce,
         incense_id,
-        total_fee as f64 / 1e9
+        &mut ctx.accounts.pending_amulets,
+        ctx.bumps.pending_amulets,
     );
 
-    // Mint NFT
-    let temple_config_key: Pubkey = ctx.accounts.temple_config.key();
-    let signer_seeds: &[&[&[u8]]] = &[&[
-        IncenseNFT::SEED_PREFIX.as_bytes(),
-        temple_config_key.as_ref(),
-        &[incense_id],
-        &[ctx.bumps.nft_mint_account],
-    ]];
-
-    mint_to(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.nft_mint_account.to_account_info(),
-                to: ctx.accounts.nft_associated_token_account.to_account_info(),
-                authority: ctx.accounts.nft_mint_account.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        amount,
-    )?;
-    msg!("NFT minted successfully");
-
-    // Immediately burn NFT
-    burn(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Burn {
-                mint: ctx.accounts.nft_mint_account.to_account_info(),
-                from: ctx.accounts.nft_associated_token_account.to_account_info(),
-                authority: ctx.accounts.authority.to_account_info(),
-            },
-        ),
-        amount,
+    // New incense types may have outgrown the inventory allocation
+    UserIncenseInventory::grow_to_fit(
+        &ctx.accounts.user_incense_inventory,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
     )?;
-    msg!("NFT burned successfully - consumable incense used");
 
-    ctx.accounts.user_incense_state.incense_number = ctx
-        .accounts
-        .user_incense_state
-        .incense_number
-        .saturating_add(amount as u8);
+    Ok(())
+}
 
-    // Calculate merit with amulet bonus
-    let mut final_merit = merit * amount;
-    if has_merit_amulet {
-        // Merit Amulet: +10% karma value
-        let bonus_merit = (merit * amount) / 10; // 10% bonus
-        final_merit = final_merit.saturating_add(bonus_merit);
-        msg!("Merit Amulet activated: +10% merit bonus ({})", bonus_merit);
-    }
+/// Incense mint and the user's token account for one burn
+pub(crate) struct IncenseMintAccounts<'info> {
+    pub mint: AccountInfo<'info>,
+    pub mint_bump: u8,
+    pub token_account: AccountInfo<'info>,
+}
 
-    // Update user's incense points and merit
-    ctx.accounts
-        .user_incense_state
-        .add_incense_value_and_merit(incense_points * amount, final_merit);
+/// How one burn's incense is sourced and what it earns
+pub(crate) struct BurnPlan<'info> {
+    pub incense_id: u8,
+    pub amount: u64,
+    pub incense_points: u64, // Points for the whole amount
+    pub merit: u64,          // Merit for the whole amount, before bonuses
+    pub from_balance: u64,   // Taken from the prepaid ledger
+    pub shortfall: u64,      // Bought in this instruction
+    pub fee: u64,            // Price of the shortfall, in lamports or the payment token
+    pub burn_duration: i64,  // Seconds the sticks stay lit on the altar
+    pub accounts: IncenseMintAccounts<'info>,
+}
 
-    // Update global stats with merit and incense points
-    ctx.accounts
-        .global_stats
-        .add_incense_value_and_merit(incense_points * amount, merit * amount);
+/// Accounts and prices shared by every burn of an instruction
+pub(crate) struct IncenseBurner<'a, 'info> {
+    pub authority: &'a Signer<'info>,
+    pub temple_config: &'a Account<'info, TempleConfig>,
+    pub temple_treasury: &'a AccountInfo<'info>,
+    pub payment_mint: Option<&'a Account<'info, Mint>>,
+    pub payer_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub treasury_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: &'a Program<'info, Token>,
+    pub system_program: &'a Program<'info, System>,
+    pub token_payment: Option<PaymentMint>,
+    pub sol_usd: Option<SolUsdPrice>,
+    pub medal_level: u8,
+    pub now: i64,
+}
 
-    // Amulet drop logic - auto mint NFT
-    let mut amulet_dropped = false;
-    let mut amulet_type = String::new();
+impl<'info> IncenseBurner<'_, 'info> {
+    /// Check the daily limits and source a burn: held tokens first, then the prepaid balance,
+    /// buying only the shortfall
+    pub fn plan(
+        &self,
+        user_incense_state: &UserIncenseState,
+        inventory: &UserIncenseInventory,
+        incense_id: u8,
+        amount: u64,
+        accounts: IncenseMintAccounts<'info>,
+    ) -> Result<BurnPlan<'info>> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let incense_type = self
+            .temple_config
+            .find_incense_type(incense_id)
+            .ok_or(ErrorCode::InvalidIncenseId)?;
+        user_incense_state.check_daily_incense_limit(
+            inventory,
+            incense_type,
+            amount,
+            self.medal_level,
+            self.temple_config,
+        )?;
+
+        let incense_points = incense_type
+            .incense_points
+            .checked_mul(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let merit = incense_type
+            .merit
+            .checked_mul(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let held = if accounts.token_account.data_is_empty() {
+            0
+        } else {
+            TokenAccount::try_deserialize(&mut &accounts.token_account.try_borrow_data()?[..])?
+                .amount
+        };
+        let from_tokens = held.min(amount);
+        let from_balance = inventory.get_balance(incense_id).min(amount - from_tokens);
+        let shortfall = amount - from_tokens - from_balance;
+
+        let mut fee = 0;
+        if shortfall > 0 {
+            // Donation-only incense can never be bought
+            if let Some(special_incense) = self.temple_config.find_special_incense_type(incense_id)
+            {
+                require!(
+                    !special_incense.is_donation_only,
+                    ErrorCode::DonationOnlyIncense
+                );
+            }
 
-    // Generate random number for amulet drop
-    let random_seed = current_time + ctx.accounts.authority.key().as_ref()[0] as u64;
-    let random_value = (random_seed % 100) as u8;
+            let fee_per_incense = match &self.token_payment {
+                Some(payment_mint) => payment_mint
+                    .incense_price(incense_id)
+                    .ok_or(ErrorCode::TokenPriceNotSet)?,
+                None => self
+                    .temple_config
+                    .get_fee_per_incense(incense_id, self.sol_usd.as_ref())?,
+            };
+            fee = fee_per_incense
+                .checked_mul(shortfall)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
 
-    let mut dropped_amulet_type: u8 = 0;
+        Ok(BurnPlan {
+            incense_id,
+            amount,
+            incense_points,
+            merit,
+            from_balance,
+            shortfall,
+            fee,
+            burn_duration: i64::from(incense_type.burn_duration_seconds),
+            accounts,
+        })
+    }
 
-    match incense_id {
-        1 => {
-            if random_value < 5 {
-                amulet_dropped = true;
-                dropped_amulet_type = 0; // Fortune Amulet
-                msg!("Congratulations! Obtained Fortune Amulet from burning Clear Incense!");
-            }
+    /// Pay the treasury for the shortfall of one or more burns, in the payment token or SOL
+    pub fn pay(&self, global_stats: &mut GlobalStats, total_fee: u64) -> Result<()> {
+        if total_fee == 0 {
+            return Ok(());
         }
-        5 => {
-            if random_value < 10 {
-                amulet_dropped = true;
-                dropped_amulet_type = 2; // Merit Amulet
-                msg!("Congratulations! Obtained Merit Amulet from burning Supreme Spirit Incense!");
+
+        if let Some(payment_mint) = &self.token_payment {
+            transfer_token_payment(
+                self.token_program,
+                self.payment_mint,
+                self.payer_token_account,
+                self.treasury_token_account,
+                self.authority,
+                total_fee,
+            )?;
+            global_stats.add_token_payment(payment_mint.mint, total_fee);
+
+            msg!(
+                "User paid for incense (total price: {} of {})",
+                total_fee,
+                payment_mint.mint
+            );
+        } else {
+            if self.authority.lamports() < total_fee {
+                return err!(ErrorCode::InsufficientSolBalance);
             }
+
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: self.temple_treasury.to_account_info(),
+                    },
+                ),
+                total_fee,
+            )?;
+
+            msg!(
+                "User paid for incense (total fee: {} SOL)",
+                total_fee as f64 / 1e9
+            );
         }
-        _ => {}
+        Ok(())
     }
 
-    if amulet_dropped {
-        // Emit amulet dropped event with type information
-        emit!(crate::state::event::AmuletDropped {
-            user: ctx.accounts.authority.key(),
-            amulet_type: dropped_amulet_type,
-            source: format!("burn_incense_{}", incense_id),
-            timestamp: clock.unix_timestamp,
+    /// Take a planned burn from the prepaid balance, mint what is not held, burn it all,
+    /// count it against the daily cap and light it on the altar when one is passed
+    pub fn consume(
+        &self,
+        plan: &BurnPlan<'info>,
+        user_incense_state: &mut UserIncenseState,
+        inventory: &mut UserIncenseInventory,
+        altar: Option<&mut Account<'info, Altar>>,
+    ) -> Result<()> {
+        if plan.from_balance > 0 {
+            inventory.subtract_balance(plan.incense_id, plan.from_balance)?;
+            msg!(
+                "Used {} of incense type {} from balance",
+                plan.from_balance,
+                plan.incense_id
+            );
+        }
+
+        // Mint tokens for incense not yet held, then burn everything from the user's ATA
+        let to_mint = plan.from_balance + plan.shortfall;
+        if to_mint > 0 {
+            let temple_config_key = self.temple_config.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                IncenseNFT::SEED_PREFIX.as_bytes(),
+                temple_config_key.as_ref(),
+                &[plan.incense_id],
+                &[plan.accounts.mint_bump],
+            ]];
+            mint_to(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    MintTo {
+                        mint: plan.accounts.mint.clone(),
+                        to: plan.accounts.token_account.clone(),
+                        authority: plan.accounts.mint.clone(),
+                    },
+                    signer_seeds,
+                ),
+                to_mint,
+            )?;
+        }
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: plan.accounts.mint.clone(),
+                    from: plan.accounts.token_account.clone(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            plan.amount,
+        )?;
+        msg!("NFT burned successfully - consumable incense used");
+
+        user_incense_state.update_daily_count(
+            inventory,
+            plan.incense_id,
+            plan.amount,
+            self.temple_config,
+        )?;
+
+        // Light the sticks on the shared altar
+        if let Some(altar) = altar {
+            altar.light(
+                AltarStick {
+                    burner: self.authority.key(),
+                    incense_id: plan.incense_id,
+                    amount: plan.amount,
+                    lit_at: self.now,
+                    burns_until: self.now + plan.burn_duration,
+                },
+                self.now,
+            );
+        }
+
+        emit!(IncenseBurned {
+            user: self.authority.key(),
+            incense_id: plan.incense_id,
+            amount: plan.amount,
+            timestamp: self.now,
         });
+        Ok(())
     }
 
-    // event
-    emit!(IncenseBurned {
-        user: ctx.accounts.authority.key(),
-        incense_id: incense_id,
-        amount: amount,
-        timestamp: clock.unix_timestamp,
-    });
+    /// Roll the amulet drop of one burned incense type, crediting it for a later mint
+    pub fn roll_amulet_drop(
+        &self,
+        randomness: &SlotHashesRandomness,
+        nonce: u64,
+        incense_id: u8,
+        pending_amulets: &mut PendingAmulets,
+        pending_amulets_bump: u8,
+    ) {
+        // Slot hashes are known in advance, mainnet has no incense amulet drops
+        if cfg!(feature = "mainnet") {
+            return;
+        }
 
-    Ok(())
+        let authority_key = self.authority.key();
+        let random_value =
+            randomness.roll_percent(INCENSE_AMULET_DROP_DOMAIN, &authority_key, nonce);
+
+        if let Some(amulet_kind) = incense_amulet_drop(incense_id, random_value) {
+            // Credit pending amulet, minted later through mint_amulet_nft
+            pending_amulets.ensure_initialized(authority_key, pending_amulets_bump);
+            pending_amulets.credit(amulet_kind, 1);
+
+            // Emit amulet dropped event with type information
+            emit!(crate::state::event::AmuletDropped {
+                user: authority_key,
+                amulet_type: amulet_kind as u8,
+                source: format!("burn_incense_{}", incense_id),
+                timestamp: self.now,
+            });
+        }
+    }
+}
+
+/// Burned merit plus the merit amulet (+10%) and burning streak bonuses
+pub(crate) fn merit_with_bonuses(
+    merit: u64,
+    has_merit_amulet: bool,
+    streak_bonus_percent: u16,
+) -> Result<u64> {
+    let mut final_merit = merit;
+    if has_merit_amulet {
+        let bonus_merit = merit / 10;
+        final_merit = final_merit
+            .checked_add(bonus_merit)
+            .ok_or(ErrorCode::MathOverflow)?;
+        msg!("Merit Amulet activated: +10% merit bonus ({})", bonus_merit);
+    }
+    if streak_bonus_percent > 0 {
+        let bonus_merit = merit
+            .checked_mul(u64::from(streak_bonus_percent))
+            .ok_or(ErrorCode::MathOverflow)?
+            / 100;
+        final_merit = final_merit
+            .checked_add(bonus_merit)
+            .ok_or(ErrorCode::MathOverflow)?;
+        msg!(
+            "Burning streak: +{}% merit bonus ({})",
+            streak_bonus_percent,
+            bonus_merit
+        );
+    }
+    Ok(final_merit)
+}
+
+/// Count today in the user's burning streak, returns the merit bonus (percent) it earns
+pub(crate) fn update_streak(
+    user_streak: &mut UserStreak,
+    bump: u8,
+    temple_config: &TempleConfig,
+    user: Pubkey,
+    now: i64,
+) -> u16 {
+    user_streak.ensure_initialized(user, bump);
+    let bonus_percent =
+        |streak: &UserStreak| temple_config.streak_bonus_percent(streak.current_streak);
+
+    if let Some(protection_used) = user_streak.record_burn_day(temple_config.day_index(now)) {
+        emit!(StreakUpdated {
+            user,
+            current_streak: user_streak.current_streak,
+            longest_streak: user_streak.longest_streak,
+            protection_used,
+            streak_protections: user_streak.streak_protections,
+            bonus_percent: bonus_percent(user_streak),
+            timestamp: now,
+        });
+    }
+    bonus_percent(user_streak)
+}
+
+/// Amulet dropped by burning an incense type, for a 0-99 roll
+pub(crate) fn incense_amulet_drop(incense_id: u8, random_value: u8) -> Option<AmuletType> {
+    match incense_id {
+        1 if random_value < 5 => {
+            msg!("Congratulations! Obtained Fortune Amulet from burning Clear Incense!");
+            Some(AmuletType::Fortune)
+        }
+        5 if random_value < 10 => {
+            msg!("Congratulations! Obtained Merit Amulet from burning Supreme Spirit Incense!");
+            Some(AmuletType::Merit)
+        }
+        _ => None,
+    }
 }
 
 #[derive(Accounts)]
@@ -221,6 +616,14 @@ pub struct BurnIncense<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
 
+    /// Shared altar, lights a stick per burn (optional, best effort, so burns never serialize on it)
+    #[account(
+        mut,
+        seeds = [Altar::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = altar.bump,
+    )]
+    pub altar: Option<Box<Account<'info, Altar>>>,
+
     /// User account
     #[account(
         mut,
@@ -237,6 +640,50 @@ pub struct BurnIncense<'info> {
     )]
     pub user_incense_state: Box<Account<'info, UserIncenseState>>,
 
+    /// Beneficiary's incense state, when dedicating to another user (optional)
+    #[account(mut)]
+    pub beneficiary_incense_state: Option<Box<Account<'info, UserIncenseState>>>,
+
+    /// Shared pool credited by memorial dedications (optional)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + DedicationPool::INIT_SPACE,
+        seeds = [DedicationPool::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump,
+    )]
+    pub dedication_pool: Option<Box<Account<'info, DedicationPool>>>,
+
+    /// User incense inventory
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserIncenseInventory::INIT_SPACE,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_inventory: Box<Account<'info, UserIncenseInventory>>,
+
+    /// User burning streak
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserStreak::INIT_SPACE,
+        seeds = [UserStreak::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_streak: Box<Account<'info, UserStreak>>,
+
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
     /// NFT mint
     #[account(
         mut,
@@ -270,6 +717,38 @@ pub struct BurnIncense<'info> {
     )]
     pub meta_account: UncheckedAccount<'info>,
 
+    /// User's medal, for holder-only daily caps (optional)
+    #[account(
+        seeds = [MedalNFT::SEED_PREFIX.as_bytes(), b"account", temple_config.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub medal_nft_account: Option<Box<Account<'info, MedalNFT>>>,
+
+    /// SPL token paying instead of SOL (optional)
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Payer's token account for the payment mint
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Temple treasury ATA for the payment mint
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SOL/USD price feed for USD prices (optional), validated in price::load_sol_usd_price
+    pub sol_usd_price: Option<UncheckedAccount<'info>>,
+
+    /// Merit amulet held by the user (optional)
+    #[account(mut)]
+    pub merit_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the merit amulet
+    pub merit_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SlotHashes sysvar, randomness for amulet drops
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
     // Program accounts
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
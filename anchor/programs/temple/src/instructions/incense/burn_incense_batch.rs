@@ -0,0 +1,364 @@
+use crate::error::ErrorCode;
+use crate::incense_nft::IncenseNFT;
+use crate::instructions::incense::burn_incense::{
+    merit_with_bonuses, update_streak, BurnPlan, IncenseBurner, IncenseMintAccounts,
+};
+use crate::payment::resolve_token_payment;
+use crate::price::load_sol_usd_price;
+use crate::randomness::SlotHashesRandomness;
+use crate::state::altar::Altar;
+use crate::state::amulet::{apply_amulet, AmuletNFT, AmuletType};
+use crate::state::event::IncenseBatchBurned;
+use crate::state::global_stats::GlobalStats;
+use crate::state::medal_nft::MedalNFT;
+use crate::state::pending_amulets::PendingAmulets;
+use crate::state::temple_config::*;
+use crate::state::user_incense_inventory::UserIncenseInventory;
+use crate::state::user_state::{UserIncenseState, UserState};
+use crate::state::user_streak::UserStreak;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_spl::associated_token::{
+    create_idempotent, get_associated_token_address, AssociatedToken, Create,
+};
+use anchor_spl::token::Mint;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+/// Max incense types burned in one batch
+pub const MAX_BATCH_ENTRIES: usize = 6;
+
+/// One incense type and amount to burn in a batch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IncenseBurnEntry {
+    pub incense_id: u8,
+    pub amount: u64,
+}
+
+/// Burn several incense types in one instruction.
+/// Remaining accounts: (incense mint, user's ATA) per entry, in entry order.
+pub fn burn_incense_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BurnIncenseBatch<'info>>,
+    entries: Vec<IncenseBurnEntry>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::BurnIncense,
+        current_time,
+    )?;
+
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_BATCH_ENTRIES,
+        ErrorCode::InvalidIncenseBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() == entries.len() * 2,
+        ErrorCode::InvalidBatchAccounts
+    );
+
+    let authority_key = ctx.accounts.authority.key();
+    let temple_config_key = ctx.accounts.temple_config.key();
+
+    // Users who skipped migrate_incense_inventory get their legacy slots moved on first use
+    ctx.accounts.user_incense_inventory.ensure_initialized(
+        ctx.accounts.authority.key(),
+        ctx.bumps.user_incense_inventory,
+    );
+    ctx.accounts
+        .user_incense_inventory
+        .absorb_legacy(&mut ctx.accounts.user_incense_state)?;
+
+    let burner = IncenseBurner {
+        authority: &ctx.accounts.authority,
+        temple_config: &ctx.accounts.temple_config,
+        temple_treasury: &ctx.accounts.temple_treasury,
+        payment_mint: ctx.accounts.payment_mint.as_deref(),
+        payer_token_account: ctx.accounts.payer_token_account.as_deref(),
+        treasury_token_account: ctx.accounts.treasury_token_account.as_deref(),
+        token_program: &ctx.accounts.token_program,
+        system_program: &ctx.accounts.system_program,
+        token_payment: resolve_token_payment(
+            &ctx.accounts.temple_config,
+            ctx.accounts.payment_mint.as_deref(),
+            ctx.accounts.payer_token_account.as_deref(),
+            ctx.accounts.treasury_token_account.as_deref(),
+            &authority_key,
+        )?,
+        sol_usd: load_sol_usd_price(ctx.accounts.sol_usd_price.as_deref())?,
+        medal_level: ctx
+            .accounts
+            .medal_nft_account
+            .as_ref()
+            .map_or(0, |medal| medal.level),
+        now: clock.unix_timestamp,
+    };
+
+    // Validate every entry and check daily limits before moving any funds
+    let mut plans: Vec<BurnPlan<'info>> = Vec::with_capacity(entries.len());
+    let mut total_fee: u64 = 0;
+    let mut total_points: u64 = 0;
+    let mut total_merit: u64 = 0;
+
+    for (i, entry) in entries.iter().enumerate() {
+        require!(
+            entries[..i]
+                .iter()
+                .all(|other| other.incense_id != entry.incense_id),
+            ErrorCode::InvalidIncenseBatch
+        );
+
+        // Incense mint PDA and the user's ATA for it
+        let mint = &ctx.remaining_accounts[i * 2];
+        let token_account = &ctx.remaining_accounts[i * 2 + 1];
+        let (mint_key, mint_bump) = Pubkey::find_program_address(
+            &[
+                IncenseNFT::SEED_PREFIX.as_bytes(),
+                temple_config_key.as_ref(),
+                &[entry.incense_id],
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(mint.key(), mint_key, ErrorCode::InvalidBatchAccounts);
+        require_keys_eq!(
+            token_account.key(),
+            get_associated_token_address(&authority_key, &mint_key),
+            ErrorCode::InvalidBatchAccounts
+        );
+        Account::<Mint>::try_from(mint)?;
+
+        let plan = burner.plan(
+            &ctx.accounts.user_incense_state,
+            &ctx.accounts.user_incense_inventory,
+            entry.incense_id,
+            entry.amount,
+            IncenseMintAccounts {
+                mint: mint.clone(),
+                mint_bump,
+                token_account: token_account.clone(),
+            },
+        )?;
+        total_fee = total_fee
+            .checked_add(plan.fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+        total_points = total_points
+            .checked_add(plan.incense_points)
+            .ok_or(ErrorCode::MathOverflow)?;
+        total_merit = total_merit
+            .checked_add(plan.merit)
+            .ok_or(ErrorCode::MathOverflow)?;
+        plans.push(plan);
+    }
+
+    // Pay for every shortfall in a single transfer
+    burner.pay(&mut ctx.accounts.global_stats, total_fee)?;
+
+    let mut total_amount: u64 = 0;
+    for plan in plans.iter() {
+        create_idempotent(CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.authority.to_account_info(),
+                associated_token: plan.accounts.token_account.clone(),
+                authority: ctx.accounts.authority.to_account_info(),
+                mint: plan.accounts.mint.clone(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ))?;
+
+        burner.consume(
+            plan,
+            &mut ctx.accounts.user_incense_state,
+            &mut ctx.accounts.user_incense_inventory,
+            ctx.accounts.altar.as_deref_mut(),
+        )?;
+        total_amount += plan.amount;
+    }
+
+    // Verify merit amulet on-chain and consume one charge for the whole batch
+    let has_merit_amulet = apply_amulet(
+        ctx.accounts.merit_amulet.as_deref_mut(),
+        ctx.accounts.merit_amulet_token_account.as_deref(),
+        &authority_key,
+        AmuletType::Merit,
+        clock.unix_timestamp,
+    )?;
+
+    // Burning streak bonus, counted once for the batch
+    let streak_bonus_percent = update_streak(
+        &mut ctx.accounts.user_streak,
+        ctx.bumps.user_streak,
+        &ctx.accounts.temple_config,
+        authority_key,
+        clock.unix_timestamp,
+    );
+    let final_merit = merit_with_bonuses(total_merit, has_merit_amulet, streak_bonus_percent)?;
+
+    // Roll one amulet drop per entry (not on mainnet), keyed by the incense points before the batch
+    let drop_nonce = ctx.accounts.user_incense_state.incense_points;
+    let randomness = SlotHashesRandomness::load(&ctx.accounts.slot_hashes)?;
+    for (i, plan) in plans.iter().enumerate() {
+        burner.roll_amulet_drop(
+            &randomness,
+            drop_nonce.wrapping_add(i as u64),
+            plan.incense_id,
+            &mut ctx.accounts.pending_amulets,
+            ctx.bumps.pending_amulets,
+        );
+    }
+
+    // Update user's incense points and merit
+    ctx.accounts
+        .user_incense_state
+        .add_incense_value_and_merit(total_points, final_merit);
+
+    // Update global stats with merit and incense points
+    ctx.accounts
+        .global_stats
+        .add_incense_value_and_merit(total_points, total_merit);
+
+    emit!(IncenseBatchBurned {
+        user: authority_key,
+        entries,
+        total_amount,
+        total_incense_points: total_points,
+        total_merit: final_merit,
+        total_fee,
+        payment_mint: burner
+            .token_payment
+            .as_ref()
+            .map(|payment_mint| payment_mint.mint),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // New incense types may have outgrown the inventory allocation
+    UserIncenseInventory::grow_to_fit(
+        &ctx.accounts.user_incense_inventory,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BurnIncenseBatch<'info> {
+    /// User account (payer, signer)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Temple SOL storage account
+    #[account(
+        mut,
+        constraint = temple_treasury.key() == temple_config.treasury @ ErrorCode::InvalidTempleTreasury
+    )]
+    pub temple_treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    #[account(
+        mut,
+        seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+
+    /// Shared altar, lights a stick per entry (optional, best effort, so burns never serialize on it)
+    #[account(
+        mut,
+        seeds = [Altar::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = altar.bump,
+    )]
+    pub altar: Option<Box<Account<'info, Altar>>>,
+
+    /// User account
+    #[account(
+        mut,
+        seeds = [UserState::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_state: Box<Account<'info, UserState>>,
+
+    /// User incense state
+    #[account(
+        mut,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: Box<Account<'info, UserIncenseState>>,
+
+    /// User incense inventory
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserIncenseInventory::INIT_SPACE,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_inventory: Box<Account<'info, UserIncenseInventory>>,
+
+    /// User burning streak
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserStreak::INIT_SPACE,
+        seeds = [UserStreak::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_streak: Box<Account<'info, UserStreak>>,
+
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    /// User's medal, for holder-only daily caps (optional)
+    #[account(
+        seeds = [MedalNFT::SEED_PREFIX.as_bytes(), b"account", temple_config.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub medal_nft_account: Option<Box<Account<'info, MedalNFT>>>,
+
+    /// SPL token paying instead of SOL (optional)
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Payer's token account for the payment mint
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Temple treasury ATA for the payment mint
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SOL/USD price feed for USD prices (optional), validated in price::load_sol_usd_price
+    pub sol_usd_price: Option<UncheckedAccount<'info>>,
+
+    /// Merit amulet held by the user (optional)
+    #[account(mut)]
+    pub merit_amulet: Option<Box<Account<'info, AmuletNFT>>>,
+
+    /// User's token account holding the merit amulet
+    pub merit_amulet_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SlotHashes sysvar, randomness for amulet drops
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    // Program accounts
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
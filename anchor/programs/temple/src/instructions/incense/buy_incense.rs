@@ -0,0 +1,215 @@
+use crate::error::ErrorCode;
+use crate::incense_nft::IncenseNFT;
+use crate::payment::{resolve_token_payment, transfer_token_payment};
+use crate::price::load_sol_usd_price;
+use crate::state::event::IncenseBought;
+use crate::state::global_stats::GlobalStats;
+use crate::state::temple_config::*;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::transfer;
+use anchor_lang::system_program::Transfer;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::mint_to;
+use anchor_spl::token::Mint;
+use anchor_spl::token::MintTo;
+use anchor_spl::token::Token;
+use anchor_spl::token::TokenAccount;
+
+pub fn buy_incense(ctx: Context<BuyIncense>, incense_id: u8, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp as u64;
+
+    // Check temple status
+    ctx.accounts.temple_config.can_perform_operation(
+        crate::state::temple_config::TempleStatusBitIndex::BuyIncense,
+        current_time,
+    )?;
+
+    if amount == 0 {
+        return err!(ErrorCode::InvalidAmount);
+    }
+
+    ctx.accounts
+        .temple_config
+        .find_incense_type(incense_id)
+        .ok_or(ErrorCode::InvalidIncenseId)?;
+
+    // Donation-only incense can never be bought
+    if let Some(special_incense) = ctx
+        .accounts
+        .temple_config
+        .find_special_incense_type(incense_id)
+    {
+        require!(
+            !special_incense.is_donation_only,
+            ErrorCode::DonationOnlyIncense
+        );
+    }
+
+    let token_payment = resolve_token_payment(
+        &ctx.accounts.temple_config,
+        ctx.accounts.payment_mint.as_deref(),
+        ctx.accounts.payer_token_account.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.authority.key(),
+    )?;
+
+    let total_fee = if let Some(payment_mint) = &token_payment {
+        // Pay in a whitelisted SPL token
+        let total_price = payment_mint
+            .incense_price(incense_id)
+            .ok_or(ErrorCode::TokenPriceNotSet)?
+            .checked_mul(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        transfer_token_payment(
+            &ctx.accounts.token_program,
+            ctx.accounts.payment_mint.as_deref(),
+            ctx.accounts.payer_token_account.as_deref(),
+            ctx.accounts.treasury_token_account.as_deref(),
+            &ctx.accounts.authority,
+            total_price,
+        )?;
+        ctx.accounts
+            .global_stats
+            .add_token_payment(payment_mint.mint, total_price);
+        total_price
+    } else {
+        let sol_usd = load_sol_usd_price(ctx.accounts.sol_usd_price.as_deref())?;
+        let fee_per_incense = ctx
+            .accounts
+            .temple_config
+            .get_fee_per_incense(incense_id, sol_usd.as_ref())?;
+        let total_fee = fee_per_incense
+            .checked_mul(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // enough balance
+        if ctx.accounts.authority.lamports() < total_fee {
+            return err!(ErrorCode::InsufficientSolBalance);
+        }
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.temple_treasury.to_account_info(),
+                },
+            ),
+            total_fee,
+        )?;
+        total_fee
+    };
+
+    // Mint incense tokens to the buyer, who can burn, gift or trade them
+    let temple_config_key: Pubkey = ctx.accounts.temple_config.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        IncenseNFT::SEED_PREFIX.as_bytes(),
+        temple_config_key.as_ref(),
+        &[incense_id],
+        &[ctx.bumps.nft_mint_account],
+    ]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.nft_mint_account.to_account_info(),
+                to: ctx.accounts.nft_associated_token_account.to_account_info(),
+                authority: ctx.accounts.nft_mint_account.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    msg!(
+        "User bought {} of incense type {} (total fee: {})",
+        amount,
+        incense_id,
+        total_fee
+    );
+
+    emit!(IncenseBought {
+        user: ctx.accounts.authority.key(),
+        incense_id,
+        amount,
+        total_fee,
+        payment_mint: token_payment.map(|payment_mint| payment_mint.mint),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(incense_id: u8)]
+pub struct BuyIncense<'info> {
+    /// User account (payer, signer)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Temple admin account
+    #[account(
+        constraint = temple_authority.key() == temple_config.owner @ ErrorCode::InvalidOwner)]
+    pub temple_authority: AccountInfo<'info>,
+
+    /// CHECK: Temple SOL storage account
+    #[account(
+        mut,
+        constraint = temple_treasury.key() == temple_config.treasury @ ErrorCode::InvalidTempleTreasury
+    )]
+    pub temple_treasury: AccountInfo<'info>,
+
+    #[account(
+        seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub temple_config: Box<Account<'info, TempleConfig>>,
+
+    #[account(
+        mut,
+        seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub global_stats: Box<Account<'info, GlobalStats>>,
+
+    /// SPL token paying instead of SOL (optional)
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Payer's token account for the payment mint
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Temple treasury ATA for the payment mint
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SOL/USD price feed for USD prices (optional), validated in price::load_sol_usd_price
+    pub sol_usd_price: Option<UncheckedAccount<'info>>,
+
+    /// NFT mint
+    #[account(
+        mut,
+        seeds = [IncenseNFT::SEED_PREFIX.as_bytes(), temple_config.key().as_ref(), &[incense_id]],
+        bump,
+        mint::decimals = IncenseNFT::TOKEN_DECIMALS,
+        mint::authority = nft_mint_account.key(),
+        mint::freeze_authority = temple_authority.key(), // Temple has freeze authority
+    )]
+    pub nft_mint_account: Box<Account<'info, Mint>>,
+
+    /// User's NFT associated account
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = nft_mint_account,
+        associated_token::authority = authority,
+    )]
+    pub nft_associated_token_account: Account<'info, TokenAccount>,
+
+    // Program accounts
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,2 +1,6 @@
 pub mod burn_incense;
 pub use burn_incense::*;
+pub mod burn_incense_batch;
+pub use burn_incense_batch::*;
+pub mod buy_incense;
+pub use buy_incense::*;
@@ -1,12 +1,12 @@
 use crate::error::ErrorCode;
 use crate::state::buddha_nft::*;
 use crate::state::global_stats::GlobalStats;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
 use crate::state::temple_config::*;
 use crate::state::user_state::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::create_metadata_accounts_v3;
-use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 use anchor_spl::metadata::CreateMetadataAccountsV3;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::token::mint_to;
@@ -44,10 +44,14 @@ pub fn mint_buddha_nft(ctx: Context<MintBuddhaNFT>) -> Result<()> {
     // Mint Buddha NFT
     let serial_number = ctx.accounts.temple_config.total_buddha_nft;
 
-    let nft_name = format!(
-        "Buddha NFT #{}",
-        ctx.accounts.temple_config.total_buddha_nft
-    );
+    let nft_data = ctx
+        .accounts
+        .metadata_config
+        .template(NftFamily::Buddha)
+        .to_data(&MetadataVars {
+            serial: serial_number as u64,
+            ..Default::default()
+        })?;
 
     // Create metadata account
     let temple_signer_seeds: &[&[&[u8]]] = &[&[
@@ -69,15 +73,7 @@ pub fn mint_buddha_nft(ctx: Context<MintBuddhaNFT>) -> Result<()> {
             },
             temple_signer_seeds,
         ),
-        DataV2 {
-            name: nft_name,
-            symbol: BuddhaNFT::TOKEN_SYMBOL.to_string(),
-            uri: BuddhaNFT::TOKEN_URL.to_string(),
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        },
+        nft_data,
         false, // immutable
         true,
         None,
@@ -151,6 +147,12 @@ pub struct MintBuddhaNFT<'info> {
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
 
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
     #[account(
         mut,
         seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
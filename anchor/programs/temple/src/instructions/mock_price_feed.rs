@@ -0,0 +1,40 @@
+use crate::error::ErrorCode;
+use crate::state::mock_price_feed::MockPriceFeed;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetMockPrice<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + MockPriceFeed::INIT_SPACE,
+        seeds = [MockPriceFeed::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub mock_price_feed: Box<Account<'info, MockPriceFeed>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Publish a SOL/USD price at the current time, like a Pyth price update
+pub fn set_mock_price(
+    ctx: Context<SetMockPrice>,
+    price: i64,
+    conf: u64,
+    exponent: i32,
+) -> Result<()> {
+    require!(price > 0, ErrorCode::InvalidPriceFeed);
+    let feed = &mut ctx.accounts.mock_price_feed;
+
+    feed.authority = ctx.accounts.authority.key();
+    feed.price = price;
+    feed.conf = conf;
+    feed.exponent = exponent;
+    feed.publish_time = Clock::get()?.unix_timestamp;
+    feed.bump = ctx.bumps.mock_price_feed;
+
+    Ok(())
+}
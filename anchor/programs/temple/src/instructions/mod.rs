@@ -16,6 +16,11 @@ pub use donation::*;
 pub mod stake_medal_nft;
 pub use stake_medal_nft::*;
 
+#[cfg(feature = "localnet")]
+pub mod mock_price_feed;
+#[cfg(feature = "localnet")]
+pub use mock_price_feed::*;
+
 pub mod fortune;
 pub use fortune::*;
 
@@ -20,6 +20,7 @@ pub fn get_shop_items(ctx: Context<GetShopItems>) -> Result<ShopItemsResult> {
             name: item.name.clone(),
             description: item.description.clone(),
             price: item.price,
+            price_usd_cents: item.price_usd_cents,
             item_type: item.item_type.clone(),
             stock: item.stock,
             is_available: item.is_available,
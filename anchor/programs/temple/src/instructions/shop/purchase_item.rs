@@ -1,11 +1,22 @@
 use crate::error::ErrorCode;
+use crate::payment::{resolve_token_payment, transfer_token_payment};
+use crate::price::load_sol_usd_price;
+use crate::state::amulet::AmuletType;
+use crate::state::global_stats::GlobalStats;
+use crate::state::pending_amulets::PendingAmulets;
 use crate::state::shop_config::ShopConfig;
 use crate::state::shop_item::ShopItemType;
 use crate::state::temple_config::TempleConfig;
+use crate::state::user_incense_inventory::UserIncenseInventory;
 use crate::state::user_state::{UserIncenseState, UserState};
+use crate::state::user_streak::UserStreak;
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::transfer;
 use anchor_lang::system_program::Transfer;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Prop item saving one missed day of a burning streak
+pub const STREAK_PROTECTION_ITEM_ID: u8 = 110;
 
 pub fn purchase_item(ctx: Context<PurchaseItem>, item_id: u8, quantity: u64) -> Result<()> {
     let clock = Clock::get()?;
@@ -39,28 +50,58 @@ pub fn purchase_item(ctx: Context<PurchaseItem>, item_id: u8, quantity: u64) ->
         return err!(ErrorCode::InsufficientStock);
     }
 
-    // 6. Calculate total price
-    let total_price = shop_item
-        .price
+    // 6. Calculate total price, in lamports or in the payment mint
+    let token_payment = resolve_token_payment(
+        &ctx.accounts.temple_config,
+        ctx.accounts.payment_mint.as_deref(),
+        ctx.accounts.payer_token_account.as_deref(),
+        ctx.accounts.treasury_token_account.as_deref(),
+        &ctx.accounts.authority.key(),
+    )?;
+    let unit_price = match &token_payment {
+        Some(payment_mint) => payment_mint
+            .item_price(item_id)
+            .ok_or(ErrorCode::TokenPriceNotSet)?,
+        None => {
+            let sol_usd = load_sol_usd_price(ctx.accounts.sol_usd_price.as_deref())?;
+            shop_item.unit_price_lamports(sol_usd.as_ref())?
+        }
+    };
+    let total_price = unit_price
         .checked_mul(quantity)
         .ok_or(ErrorCode::MathOverflow)?;
 
-    // 7. Validate user SOL balance
-    if ctx.accounts.authority.lamports() < total_price {
-        return err!(ErrorCode::InsufficientSolBalance);
-    }
+    if let Some(payment_mint) = &token_payment {
+        // 7-8. Transfer SPL tokens to the temple treasury ATA
+        transfer_token_payment(
+            &ctx.accounts.token_program,
+            ctx.accounts.payment_mint.as_deref(),
+            ctx.accounts.payer_token_account.as_deref(),
+            ctx.accounts.treasury_token_account.as_deref(),
+            &ctx.accounts.authority,
+            total_price,
+        )?;
+        ctx.accounts
+            .global_stats
+            .add_token_payment(payment_mint.mint, total_price);
+    } else {
+        // 7. Validate user SOL balance
+        if ctx.accounts.authority.lamports() < total_price {
+            return err!(ErrorCode::InsufficientSolBalance);
+        }
 
-    // 8. Transfer SOL to temple
-    transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.authority.to_account_info(),
-                to: ctx.accounts.temple_treasury.to_account_info(),
-            },
-        ),
-        total_price,
-    )?;
+        // 8. Transfer SOL to temple
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.temple_treasury.to_account_info(),
+                },
+            ),
+            total_price,
+        )?;
+    }
 
     // 9. Handle purchase result based on item type
     match shop_item.item_type {
@@ -68,9 +109,18 @@ pub fn purchase_item(ctx: Context<PurchaseItem>, item_id: u8, quantity: u64) ->
             // For incense items, increase user incense balance
             if let Some(incense_config) = &shop_item.incense_config {
                 // Increase user incense balance
+                ctx.accounts.user_incense_inventory.ensure_initialized(
+                    ctx.accounts.authority.key(),
+                    ctx.bumps.user_incense_inventory,
+                );
                 ctx.accounts
-                    .user_incense_state
-                    .add_incense_balance(item_id, quantity);
+                    .user_incense_inventory
+                    .add_balance(item_id, quantity)?;
+                UserIncenseInventory::grow_to_fit(
+                    &ctx.accounts.user_incense_inventory,
+                    &ctx.accounts.authority.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                )?;
             } else {
                 return err!(ErrorCode::InvalidShopItemId); // Incense items must have config
             }
@@ -78,23 +128,34 @@ pub fn purchase_item(ctx: Context<PurchaseItem>, item_id: u8, quantity: u64) ->
 
         ShopItemType::Amulet => {
             // For amulet items, emit drop event for frontend to mint
-            let amulet_type = match item_id {
+            let amulet_kind = match item_id {
                 100 => {
                     msg!("Purchased Fortune Amulet NFT");
-                    0 // Fortune Amulet
+                    AmuletType::Fortune
                 }
                 101 => {
                     msg!("Purchased Protection Amulet NFT");
-                    1 // Protection Amulet
+                    AmuletType::Protection
                 }
                 102 => {
                     msg!("Purchased Merit Amulet NFT");
-                    2 // Merit Amulet
+                    AmuletType::Merit
                 }
                 _ => {
                     return err!(ErrorCode::InvalidShopItemId);
                 }
             };
+            let amulet_type = amulet_kind as u8;
+
+            // Credit pending amulets, minted later through mint_amulet_nft
+            let authority_key = ctx.accounts.authority.key();
+            ctx.accounts
+                .pending_amulets
+                .ensure_initialized(authority_key, ctx.bumps.pending_amulets);
+            ctx.accounts.pending_amulets.credit(
+                amulet_kind,
+                u32::try_from(quantity).map_err(|_| ErrorCode::InvalidAmount)?,
+            );
 
             // Emit amulet dropped event for purchase
             emit!(crate::state::event::AmuletDropped {
@@ -104,10 +165,22 @@ pub fn purchase_item(ctx: Context<PurchaseItem>, item_id: u8, quantity: u64) ->
                 timestamp: clock.unix_timestamp,
             });
         }
-        ShopItemType::Prop => {
-            // TODO: Implement prop system
-            msg!("Prop item purchased - implementation pending");
-        }
+        ShopItemType::Prop => match item_id {
+            STREAK_PROTECTION_ITEM_ID => {
+                let authority_key = ctx.accounts.authority.key();
+                ctx.accounts
+                    .user_streak
+                    .ensure_initialized(authority_key, ctx.bumps.user_streak);
+                ctx.accounts.user_streak.add_protections(
+                    u32::try_from(quantity).map_err(|_| ErrorCode::InvalidAmount)?,
+                );
+                msg!("Purchased {} streak protection(s)", quantity);
+            }
+            _ => {
+                // TODO: Implement prop system
+                msg!("Prop item purchased - implementation pending");
+            }
+        },
         ShopItemType::Special => {
             // TODO: Implement special item system
             msg!("Special item purchased - implementation pending");
@@ -117,10 +190,10 @@ pub fn purchase_item(ctx: Context<PurchaseItem>, item_id: u8, quantity: u64) ->
     // 10. Update stock (need to modify temple_config, requires PDA signature)
 
     msg!(
-        "User purchased {} of item {} (total price: {} SOL)",
+        "User purchased {} of item {} (total price: {})",
         quantity,
         item_id,
-        total_price as f64 / 1e9
+        total_price
     );
 
     Ok(())
@@ -148,6 +221,27 @@ pub struct PurchaseItem<'info> {
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
 
+    #[account(
+        mut,
+        seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
+        bump,
+    )]
+    pub global_stats: Box<Account<'info, GlobalStats>>,
+
+    /// SPL token paying instead of SOL (optional)
+    pub payment_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// Payer's token account for the payment mint
+    #[account(mut)]
+    pub payer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Temple treasury ATA for the payment mint
+    #[account(mut)]
+    pub treasury_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: SOL/USD price feed for USD prices (optional), validated in price::load_sol_usd_price
+    pub sol_usd_price: Option<UncheckedAccount<'info>>,
+
     // User state account (needed for amulet purchases)
     #[account(
         mut,
@@ -166,5 +260,36 @@ pub struct PurchaseItem<'info> {
     )]
     pub user_incense_state: Box<Account<'info, UserIncenseState>>,
 
+    // User incense inventory, credited when purchasing incense items
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserIncenseInventory::INIT_SPACE,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_inventory: Box<Account<'info, UserIncenseInventory>>,
+
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    /// Burning streak, credited when buying streak protections
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + UserStreak::INIT_SPACE,
+        seeds = [UserStreak::SEED_PREFIX.as_bytes(), authority.key().as_ref()],
+        bump,
+    )]
+    pub user_streak: Box<Account<'info, UserStreak>>,
+
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -1,4 +1,6 @@
 use crate::state::global_stats::GlobalStats;
+use crate::state::pending_amulets::PendingAmulets;
+use crate::state::user_incense_inventory::UserIncenseInventory;
 use crate::state::user_state::{
     DailyIncenseCount, IncenseBalance, UserDonationState, UserIncenseState, UserState,
 };
@@ -33,6 +35,24 @@ pub struct InitUser<'info> {
     )]
     pub user_donation_state: Box<Account<'info, UserDonationState>>,
 
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + UserIncenseInventory::INIT_SPACE,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_incense_inventory: Box<Account<'info, UserIncenseInventory>>,
+
     #[account(
         mut,
         seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
@@ -138,6 +158,22 @@ pub fn init_user(ctx: Context<InitUser>) -> Result<()> {
     user_donation_state.last_donation_time = 0;
     user_donation_state.bump = ctx.bumps.user_donation_state;
 
+    // Initialize pending amulets ledger
+    let pending_amulets = &mut ctx.accounts.pending_amulets;
+    pending_amulets.user = user.key();
+    pending_amulets.fortune = 0;
+    pending_amulets.protection = 0;
+    pending_amulets.merit = 0;
+    pending_amulets.total_credited = 0;
+    pending_amulets.total_minted = 0;
+    pending_amulets.bump = ctx.bumps.pending_amulets;
+
+    // Initialize incense inventory
+    let user_incense_inventory = &mut ctx.accounts.user_incense_inventory;
+    user_incense_inventory.user = user.key();
+    user_incense_inventory.bump = ctx.bumps.user_incense_inventory;
+    user_incense_inventory.slots = Vec::new();
+
     // Update global stats user count
     ctx.accounts.global_stats.increment_users();
 
@@ -0,0 +1,79 @@
+use crate::state::legacy::LegacyUserIncenseState;
+use crate::state::user_incense_inventory::UserIncenseInventory;
+use crate::state::user_state::UserIncenseState;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+#[derive(Accounts)]
+pub struct MigrateIncenseInventory<'info> {
+    /// CHECK: Incense state on the first release layout, decoded through LegacyUserIncenseState
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [UserIncenseState::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_incense_state: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + UserIncenseInventory::INIT_SPACE,
+        seeds = [UserIncenseInventory::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump
+    )]
+    pub user_incense_inventory: Box<Account<'info, UserIncenseInventory>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Legacy slots are cleared once moved, so running this again is a no-op
+pub fn migrate_incense_inventory(ctx: Context<MigrateIncenseInventory>) -> Result<()> {
+    let user_incense_state_info = ctx.accounts.user_incense_state.to_account_info();
+    let mut user_incense_state =
+        LegacyUserIncenseState::decode(&user_incense_state_info.try_borrow_data()?)?.upgrade();
+
+    let user_incense_inventory = &mut ctx.accounts.user_incense_inventory;
+    user_incense_inventory
+        .ensure_initialized(ctx.accounts.user.key(), ctx.bumps.user_incense_inventory);
+    user_incense_inventory.absorb_legacy(&mut user_incense_state)?;
+
+    // Rewrite the incense state in the current layout, growing it if the layout outgrew it
+    let required_space = 8 + borsh::to_vec(&user_incense_state)?.len();
+    if required_space > user_incense_state_info.data_len() {
+        let rent_due = Rent::get()?
+            .minimum_balance(required_space)
+            .saturating_sub(user_incense_state_info.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: user_incense_state_info.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        user_incense_state_info.resize(required_space)?;
+    }
+    user_incense_state
+        .try_serialize(&mut &mut user_incense_state_info.try_borrow_mut_data()?[..])?;
+
+    UserIncenseInventory::grow_to_fit(
+        &ctx.accounts.user_incense_inventory,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Incense inventory migrated for {}: {} types",
+        ctx.accounts.user.key(),
+        ctx.accounts.user_incense_inventory.slots.len()
+    );
+    Ok(())
+}
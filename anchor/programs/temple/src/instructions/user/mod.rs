@@ -1,2 +1,4 @@
 pub mod init_user;
 pub use init_user::*;
+pub mod migrate_incense_inventory;
+pub use migrate_incense_inventory::*;
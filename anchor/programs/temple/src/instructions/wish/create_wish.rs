@@ -1,11 +1,16 @@
 use crate::error::ErrorCode;
+#[cfg(not(feature = "localnet"))]
+use crate::randomness::{RandomnessSource, SlotHashesRandomness, WISH_AMULET_DROP_DOMAIN};
+use crate::state::amulet::AmuletType;
 use crate::state::event::{WishCreated, WishTowerUpdated};
 use crate::state::global_stats::GlobalStats;
+use crate::state::pending_amulets::PendingAmulets;
 use crate::state::temple_config::TempleConfig;
 use crate::state::user_state::{UserIncenseState, UserState};
 use crate::state::wish::*;
 use crate::state::wish_tower::*;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 
 #[derive(Accounts)]
 pub struct CreateWish<'info> {
@@ -45,6 +50,16 @@ pub struct CreateWish<'info> {
     )]
     pub user_incense_state: Box<Account<'info, UserIncenseState>>,
 
+    /// Pending amulets ledger, credited when an amulet drops
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + PendingAmulets::INIT_SPACE,
+        seeds = [PendingAmulets::SEED_PREFIX.as_bytes(), user.key().as_ref()],
+        bump,
+    )]
+    pub pending_amulets: Box<Account<'info, PendingAmulets>>,
+
     #[account(
         seeds = [TempleConfig::SEED_PREFIX.as_bytes()],
         bump,
@@ -58,6 +73,10 @@ pub struct CreateWish<'info> {
     )]
     pub global_stats: Account<'info, GlobalStats>,
 
+    /// CHECK: SlotHashes sysvar, randomness for amulet drops
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -76,24 +95,26 @@ pub fn create_wish(
     )?;
 
     // User state modification
+    let temple_config = &ctx.accounts.temple_config;
     let user_incense_state = &mut ctx.accounts.user_incense_state;
 
     // Calculate new wish id from total_wishes + 1
     let new_wish_id = (user_incense_state.total_wishes + 1) as u64;
 
     // Check daily limit
-    if !user_incense_state.can_wish_free() {
-        user_incense_state.consume_merit_for_wish(5)?;
+    let rates = temple_config.activity_rates(&user_incense_state.title);
+    if !user_incense_state.can_wish_free(temple_config) {
+        user_incense_state.consume_merit_for_wish(rates.wish_merit_cost)?;
     }
     // Update
-    user_incense_state.update_wish_count();
+    user_incense_state.update_wish_count(temple_config);
 
     // Update global stats
     ctx.accounts.global_stats.increment_wishes();
 
     // Give merit reward
-    if user_incense_state.can_wish_free() {
-        user_incense_state.add_incense_value_and_merit(0, 1);
+    if user_incense_state.can_wish_free(temple_config) {
+        user_incense_state.add_incense_value_and_merit(0, rates.wish_merit_reward);
     }
 
     // Create wish
@@ -113,13 +134,24 @@ pub fn create_wish(
     #[cfg(feature = "localnet")]
     let amulet_dropped = true; // Test environment: 100% drop rate for amulet
 
+    // Slot hashes are known in advance, mainnet has no wish amulet drops
     #[cfg(not(feature = "localnet"))]
-    let amulet_dropped = {
-        let random_seed = (clock.unix_timestamp as u64).wrapping_add(new_wish_id);
-        let amulet_drop_random = (random_seed % 100) as u8;
+    let amulet_dropped = !cfg!(feature = "mainnet") && {
+        let randomness = SlotHashesRandomness::load(&ctx.accounts.slot_hashes)?;
+        let amulet_drop_random =
+            randomness.roll_percent(WISH_AMULET_DROP_DOMAIN, &user.key(), new_wish_id);
         amulet_drop_random < 10
     };
     if amulet_dropped {
+        // Credit pending amulet, minted later through mint_amulet_nft
+        let user_key = ctx.accounts.user.key();
+        ctx.accounts
+            .pending_amulets
+            .ensure_initialized(user_key, ctx.bumps.pending_amulets);
+        ctx.accounts
+            .pending_amulets
+            .credit(AmuletType::Protection, 1);
+
         // Emit amulet dropped event with type information
         msg!("Congratulations! Got 1 Protection Amulet NFT from making a wish!");
         emit!(crate::state::event::AmuletDropped {
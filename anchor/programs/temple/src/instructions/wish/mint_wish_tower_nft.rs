@@ -1,13 +1,13 @@
 use crate::error::ErrorCode;
 use crate::state::event::WishTowerNFTMinted;
 use crate::state::global_stats::GlobalStats;
+use crate::state::metadata_config::{MetadataConfig, MetadataVars, NftFamily};
 use crate::state::temple_config::*;
 use crate::state::wish_tower::*;
 use crate::state::wish_tower_nft::*;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::metadata::create_metadata_accounts_v3;
-use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 use anchor_spl::metadata::CreateMetadataAccountsV3;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::token::mint_to;
@@ -44,10 +44,15 @@ pub fn mint_wish_tower_nft(ctx: Context<MintWishTowerNFT>) -> Result<()> {
     let wish_count = ctx.accounts.wish_tower_account.wish_count;
     let level = ctx.accounts.wish_tower_account.level;
 
-    let nft_name = format!("Wish Tower ({} wishes, Level {})", wish_count, level);
-    let nft_uri =
-        "https://solji.mypinata.cloud/ipfs/QmUrEHCc9PH6EEMg22RVigx4X5QF7A6bNDBrHjPZVq6SJb"
-            .to_string();
+    let nft_data = ctx
+        .accounts
+        .metadata_config
+        .template(NftFamily::WishTower)
+        .to_data(&MetadataVars {
+            count: wish_count as u64,
+            level,
+            ..Default::default()
+        })?;
 
     // Create metadata account
     let temple_signer_seeds: &[&[&[u8]]] = &[&[
@@ -69,15 +74,7 @@ pub fn mint_wish_tower_nft(ctx: Context<MintWishTowerNFT>) -> Result<()> {
             },
             temple_signer_seeds,
         ),
-        DataV2 {
-            name: nft_name,
-            symbol: "WISH_TOWER".to_string(),
-            uri: nft_uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        },
+        nft_data,
         false, // immutable
         true,
         None,
@@ -145,6 +142,12 @@ pub struct MintWishTowerNFT<'info> {
     )]
     pub temple_config: Box<Account<'info, TempleConfig>>,
 
+    #[account(
+        seeds = [MetadataConfig::SEED_PREFIX.as_bytes(), temple_config.key().as_ref()],
+        bump = metadata_config.bump,
+    )]
+    pub metadata_config: Box<Account<'info, MetadataConfig>>,
+
     #[account(
         mut,
         seeds = [GlobalStats::SEED_PREFIX.as_bytes()],
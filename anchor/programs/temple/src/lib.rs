@@ -1,13 +1,22 @@
 #![allow(clippy::result_large_err)]
 
 use anchor_lang::prelude::*;
+pub mod attestation;
 pub mod error;
 pub mod instructions;
+pub mod payment;
+pub mod price;
+pub mod randomness;
 pub mod state;
 
+use crate::state::fortune_table::FortuneTier;
+use crate::state::metadata_config::{NftFamily, NftMetadataTemplate};
 use crate::state::shop_item::ShopItem;
 use crate::state::temple_config::{
-    DonationLevelConfig, DonationRewardConfig, FortuneConfig, IncenseType, TempleLevelConfig,
+    ActivityEconomyConfig, DedicationConfig, DonationLevelConfig, DonationRewardConfig,
+    FortuneConfig, FortuneReleaseConfig, FortuneRerollConfig, FortuneResolveConfig,
+    FortuneSetConfig, IncenseType, PaymentMint, ResolvePayment, StreakMultiplier,
+    TempleLevelConfig,
 };
 use instructions::*;
 use state::*;
@@ -33,8 +42,6 @@ pub mod temple {
     pub fn create_temple_config(
         ctx: Context<CreateTempleConfig>,
         treasury: Pubkey,
-        regular_fortune: FortuneConfig,
-        buddha_fortune: FortuneConfig,
         donation_levels: Vec<DonationLevelConfig>,
         donation_rewards: Vec<DonationRewardConfig>,
         temple_levels: Vec<TempleLevelConfig>,
@@ -42,8 +49,6 @@ pub mod temple {
         instructions::create_temple_config(
             ctx,
             treasury,
-            regular_fortune,
-            buddha_fortune,
             donation_levels,
             donation_rewards,
             temple_levels,
@@ -65,18 +70,27 @@ pub mod temple {
     //     instructions::distribute_buddha_nfts(ctx)
     // }
 
-    // /// Buy incense
-    // pub fn buy_incense(ctx: Context<BuyIncense>, incense_id: u8, amount: u64) -> Result<()> {
-    //     instructions::buy_incense::buy_incense(ctx, incense_id, amount)
-    // }
+    /// Buy incense tokens
+    pub fn buy_incense(ctx: Context<BuyIncense>, incense_id: u8, amount: u64) -> Result<()> {
+        instructions::buy_incense::buy_incense(ctx, incense_id, amount)
+    }
+
     /// Burn incense
     pub fn burn_incense(
         ctx: Context<BurnIncense>,
         incense_id: u8,
         amount: u64,
-        has_merit_amulet: bool,
+        beneficiary: Option<Beneficiary>,
     ) -> Result<()> {
-        instructions::burn_incense::burn_incense(ctx, incense_id, amount, has_merit_amulet)
+        instructions::burn_incense::burn_incense(ctx, incense_id, amount, beneficiary)
+    }
+
+    /// Burn several incense types in one instruction
+    pub fn burn_incense_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BurnIncenseBatch<'info>>,
+        entries: Vec<IncenseBurnEntry>,
+    ) -> Result<()> {
+        instructions::burn_incense_batch::burn_incense_batch(ctx, entries)
     }
 
     /// Initialize user state
@@ -84,19 +98,105 @@ pub mod temple {
         instructions::init_user(ctx)
     }
 
+    /// Move legacy six-slot incense balances and counts into the incense inventory
+    pub fn migrate_incense_inventory(ctx: Context<MigrateIncenseInventory>) -> Result<()> {
+        instructions::migrate_incense_inventory(ctx)
+    }
+
     /// Draw fortune
-    pub fn draw_fortune(
-        ctx: Context<DrawFortune>,
-        use_merit: bool,
-        has_fortune_amulet: bool,
-        has_protection_amulet: bool,
-    ) -> Result<DrawResult> {
-        instructions::draw_fortune(ctx, use_merit, has_fortune_amulet, has_protection_amulet)
+    pub fn draw_fortune(ctx: Context<DrawFortune>, use_merit: bool) -> Result<DrawResult> {
+        instructions::draw_fortune(ctx, use_merit)
     }
 
-    /// Share fortune to get rewards
-    pub fn share_fortune(ctx: Context<ShareFortune>, share_hash: [u8; 32]) -> Result<()> {
-        instructions::share_fortune(ctx, share_hash)
+    /// Request fortune, binding a committed randomness account
+    pub fn request_fortune(ctx: Context<RequestFortune>, use_merit: bool) -> Result<()> {
+        instructions::request_fortune(ctx, use_merit)
+    }
+
+    /// Settle requested fortune once its randomness is revealed
+    pub fn settle_fortune(ctx: Context<SettleFortune>) -> Result<DrawResult> {
+        instructions::settle_fortune(ctx)
+    }
+
+    /// Draw fortune without minting, keeping the result as a pending fortune
+    pub fn draw_only(ctx: Context<DrawOnly>, use_merit: bool) -> Result<DrawResult> {
+        instructions::draw_only(ctx, use_merit)
+    }
+
+    /// Settle requested fortune without minting, keeping the result as a pending fortune
+    pub fn settle_fortune_only(ctx: Context<SettleFortuneOnly>) -> Result<DrawResult> {
+        instructions::settle_fortune_only(ctx)
+    }
+
+    /// Resolve a bad fortune by burning incense or spending merit
+    pub fn resolve_fortune(
+        ctx: Context<ResolveFortune>,
+        draw_index: u32,
+        payment: ResolvePayment,
+    ) -> Result<()> {
+        instructions::resolve_fortune(ctx, draw_index, payment)
+    }
+
+    /// Request a redraw of a Fortune NFT's result, paying escalating merit and binding a committed randomness account
+    pub fn request_reroll(ctx: Context<RequestReroll>, draw_index: u32) -> Result<()> {
+        instructions::request_reroll(ctx, draw_index)
+    }
+
+    /// Settle requested reroll once its randomness is revealed
+    pub fn settle_reroll(ctx: Context<SettleReroll>, draw_index: u32) -> Result<()> {
+        instructions::settle_reroll(ctx, draw_index)
+    }
+
+    /// Trade one Fortune NFT of each result for a Complete Fortune NFT and merit
+    pub fn claim_fortune_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimFortuneSet<'info>>,
+    ) -> Result<()> {
+        instructions::claim_fortune_set(ctx)
+    }
+
+    /// Burn a Fortune NFT and close its accounts, refunding the rent with a merit reward
+    pub fn release_fortune(ctx: Context<ReleaseFortune>, draw_index: u32) -> Result<()> {
+        instructions::release_fortune(ctx, draw_index)
+    }
+
+    /// Mint the Fortune NFT of a pending fortune
+    pub fn claim_fortune_nft(ctx: Context<ClaimFortuneNFT>, draw_index: u32) -> Result<()> {
+        instructions::claim_fortune_nft(ctx, draw_index)
+    }
+
+    /// Commit mock randomness (localnet only)
+    #[cfg(feature = "localnet")]
+    pub fn commit_mock_randomness(ctx: Context<CommitMockRandomness>) -> Result<()> {
+        instructions::commit_mock_randomness(ctx)
+    }
+
+    /// Reveal mock randomness (localnet only)
+    #[cfg(feature = "localnet")]
+    pub fn reveal_mock_randomness(
+        ctx: Context<RevealMockRandomness>,
+        value: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_mock_randomness(ctx, value)
+    }
+
+    /// Publish a mock SOL/USD price (localnet only)
+    #[cfg(feature = "localnet")]
+    pub fn set_mock_price(
+        ctx: Context<SetMockPrice>,
+        price: i64,
+        conf: u64,
+        exponent: i32,
+    ) -> Result<()> {
+        instructions::set_mock_price(ctx, price, conf, exponent)
+    }
+
+    /// Share a drawn fortune once to get rewards
+    pub fn share_fortune(
+        ctx: Context<ShareFortune>,
+        serial_number: u32,
+        share_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::share_fortune(ctx, serial_number, share_hash)
     }
     /// Make wish
     pub fn create_wish(
@@ -167,13 +267,14 @@ pub mod temple {
         instructions::update_incense_types(ctx, incense_types)
     }
 
-    /// Update fortune drawing configuration
-    pub fn update_fortune_config(
-        ctx: Context<UpdateDynamicConfig>,
-        regular_fortune: FortuneConfig,
-        buddha_fortune: FortuneConfig,
+    /// Update fortune table (chunked, replaces tiers from offset)
+    pub fn update_fortune_tiers(
+        ctx: Context<UpdateFortuneTiers>,
+        offset: u8,
+        total_tiers: u8,
+        tiers: Vec<FortuneTier>,
     ) -> Result<()> {
-        instructions::update_fortune_config(ctx, regular_fortune, buddha_fortune)
+        instructions::update_fortune_tiers(ctx, offset, total_tiers, tiers)
     }
 
     /// Update donation level configuration
@@ -200,6 +301,23 @@ pub mod temple {
         instructions::update_temple_levels(ctx, temple_levels)
     }
 
+    /// Update temple timezone and day boundary for daily resets
+    pub fn update_day_boundary(
+        ctx: Context<UpdateDynamicConfig>,
+        utc_offset_minutes: i16,
+        day_boundary_hour: u8,
+    ) -> Result<()> {
+        instructions::update_day_boundary(ctx, utc_offset_minutes, day_boundary_hour)
+    }
+
+    /// Update burning streak merit multipliers
+    pub fn update_streak_multipliers(
+        ctx: Context<UpdateDynamicConfig>,
+        streak_multipliers: Vec<StreakMultiplier>,
+    ) -> Result<()> {
+        instructions::update_streak_multipliers(ctx, streak_multipliers)
+    }
+
     /// Update temple status
     pub fn update_temple_status(ctx: Context<UpdateTempleStatus>, status: u8) -> Result<()> {
         instructions::update_temple_status(ctx, status)
@@ -214,6 +332,16 @@ pub mod temple {
         instructions::update_temple_status_by_bit(ctx, bit, disabled)
     }
 
+    /// Rewrite a first release temple config in the current layout
+    pub fn migrate_temple_config(ctx: Context<MigrateTempleConfig>) -> Result<()> {
+        instructions::migrate_temple_config(ctx)
+    }
+
+    /// Grow global stats allocated before token payment totals
+    pub fn migrate_global_stats(ctx: Context<MigrateGlobalStats>) -> Result<()> {
+        instructions::migrate_global_stats(ctx)
+    }
+
     /// Update NFT URI
     pub fn update_nft_uri(
         ctx: Context<UpdateNftUri>,
@@ -234,6 +362,72 @@ pub mod temple {
         instructions::purchase_item(ctx, item_id, quantity)
     }
 
+    /// Update whitelisted SPL payment mints and their prices
+    pub fn update_payment_mints(
+        ctx: Context<UpdateConfigSection>,
+        payment_mints: Vec<PaymentMint>,
+    ) -> Result<()> {
+        instructions::update_payment_mints(ctx, payment_mints)
+    }
+
+    /// Update draw, share and wish pricing and rewards
+    pub fn update_activity_economy(
+        ctx: Context<UpdateConfigSection>,
+        activity_economy: ActivityEconomyConfig,
+    ) -> Result<()> {
+        instructions::update_activity_economy(ctx, activity_economy)
+    }
+
+    /// Update the cost, window and reward of resolving bad fortunes
+    pub fn update_fortune_resolve(
+        ctx: Context<UpdateConfigSection>,
+        fortune_resolve: FortuneResolveConfig,
+    ) -> Result<()> {
+        instructions::update_fortune_resolve(ctx, fortune_resolve)
+    }
+
+    /// Update the escalating merit cost and daily limit of fortune rerolls
+    pub fn update_fortune_reroll(
+        ctx: Context<UpdateConfigSection>,
+        fortune_reroll: FortuneRerollConfig,
+    ) -> Result<()> {
+        instructions::update_fortune_reroll(ctx, fortune_reroll)
+    }
+
+    /// Update the reward of completing a fortune set
+    pub fn update_fortune_set(
+        ctx: Context<UpdateConfigSection>,
+        fortune_set: FortuneSetConfig,
+    ) -> Result<()> {
+        instructions::update_fortune_set(ctx, fortune_set)
+    }
+
+    /// Update the merit reward of releasing a Fortune NFT
+    pub fn update_fortune_release(
+        ctx: Context<UpdateConfigSection>,
+        fortune_release: FortuneReleaseConfig,
+    ) -> Result<()> {
+        instructions::update_fortune_release(ctx, fortune_release)
+    }
+
+    /// Update the giver's share of a dedicated burn's merit
+    pub fn update_dedication(
+        ctx: Context<UpdateConfigSection>,
+        dedication: DedicationConfig,
+    ) -> Result<()> {
+        instructions::update_dedication(ctx, dedication)
+    }
+
+    /// Create the temple treasury token account for a payment mint
+    pub fn create_payment_treasury(ctx: Context<CreatePaymentTreasury>) -> Result<()> {
+        instructions::create_payment_treasury(ctx)
+    }
+
+    /// Withdraw SPL token payments from the temple treasury token account
+    pub fn withdraw_token_treasury(ctx: Context<WithdrawTokenTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_token_treasury(ctx, amount)
+    }
+
     /// Create shop configuration
     pub fn create_shop_config(
         ctx: Context<CreateShopConfig>,
@@ -242,6 +436,39 @@ pub mod temple {
         instructions::create_shop_config(ctx, shop_items)
     }
 
+    /// Create the shared altar showing sticks currently burning
+    pub fn create_altar(ctx: Context<CreateAltar>, slot_count: u16) -> Result<()> {
+        instructions::create_altar(ctx, slot_count)
+    }
+
+    /// Change the number of altar slots
+    pub fn resize_altar(ctx: Context<ResizeAltar>, slot_count: u16) -> Result<()> {
+        instructions::resize_altar(ctx, slot_count)
+    }
+
+    /// Create the NFT metadata templates with their defaults
+    pub fn create_metadata_config(ctx: Context<CreateMetadataConfig>) -> Result<()> {
+        instructions::create_metadata_config(ctx)
+    }
+
+    /// Create the fortune table with the default five tiers
+    pub fn create_fortune_table(
+        ctx: Context<CreateFortuneTable>,
+        regular_fortune: FortuneConfig,
+        buddha_fortune: FortuneConfig,
+    ) -> Result<()> {
+        instructions::create_fortune_table(ctx, regular_fortune, buddha_fortune)
+    }
+
+    /// Update the metadata template of one NFT family
+    pub fn update_metadata_template(
+        ctx: Context<UpdateMetadataConfig>,
+        family: NftFamily,
+        template: NftMetadataTemplate,
+    ) -> Result<()> {
+        instructions::update_metadata_template(ctx, family, template)
+    }
+
     /// Update shop items configuration
     pub fn update_shop_items(
         ctx: Context<UpdateShopItems>,
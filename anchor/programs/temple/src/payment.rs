@@ -0,0 +1,79 @@
+use crate::error::ErrorCode;
+use crate::state::temple_config::{PaymentMint, TempleConfig};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+/// Resolve the optional SPL token payment path of a payment instruction.
+/// Either all three accounts are passed or none, None means paying in SOL.
+pub fn resolve_token_payment(
+    temple_config: &Account<TempleConfig>,
+    payment_mint: Option<&Account<Mint>>,
+    payer_token_account: Option<&Account<TokenAccount>>,
+    treasury_token_account: Option<&Account<TokenAccount>>,
+    payer: &Pubkey,
+) -> Result<Option<PaymentMint>> {
+    match (payment_mint, payer_token_account, treasury_token_account) {
+        (None, None, None) => Ok(None),
+        (Some(mint), Some(payer_token_account), Some(treasury_token_account)) => {
+            let config = temple_config
+                .find_payment_mint(&mint.key())
+                .filter(|config| config.enabled)
+                .ok_or(ErrorCode::PaymentMintNotAccepted)?;
+            require!(
+                config.decimals == mint.decimals,
+                ErrorCode::InvalidPaymentMintConfig
+            );
+
+            require_keys_eq!(
+                payer_token_account.mint,
+                mint.key(),
+                ErrorCode::InvalidPaymentAccounts
+            );
+            require_keys_eq!(
+                payer_token_account.owner,
+                *payer,
+                ErrorCode::InvalidPaymentAccounts
+            );
+            // Treasury is the temple's ATA for the mint
+            require_keys_eq!(
+                treasury_token_account.key(),
+                get_associated_token_address(&temple_config.key(), &mint.key()),
+                ErrorCode::InvalidPaymentAccounts
+            );
+            Ok(Some(config.clone()))
+        }
+        _ => err!(ErrorCode::InvalidPaymentAccounts),
+    }
+}
+
+/// Move `amount` of the payment mint from the payer to the temple treasury ATA
+pub fn transfer_token_payment<'info>(
+    token_program: &Program<'info, Token>,
+    payment_mint: Option<&Account<'info, Mint>>,
+    payer_token_account: Option<&Account<'info, TokenAccount>>,
+    treasury_token_account: Option<&Account<'info, TokenAccount>>,
+    payer: &Signer<'info>,
+    amount: u64,
+) -> Result<()> {
+    let (Some(mint), Some(from), Some(to)) =
+        (payment_mint, payer_token_account, treasury_token_account)
+    else {
+        return err!(ErrorCode::InvalidPaymentAccounts);
+    };
+    require!(from.amount >= amount, ErrorCode::InsufficientTokenBalance);
+
+    transfer_checked(
+        CpiContext::new(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: payer.to_account_info(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )
+}
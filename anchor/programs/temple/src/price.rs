@@ -0,0 +1,139 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+#[cfg(feature = "localnet")]
+use crate::state::mock_price_feed::MockPriceFeed;
+#[cfg(not(feature = "localnet"))]
+use pyth_solana_receiver_sdk::{
+    error::GetPriceError,
+    price_update::{get_feed_id_from_hex, PriceUpdateV2},
+};
+
+/// Pyth SOL/USD price feed id
+#[cfg(not(feature = "localnet"))]
+const SOL_USD_FEED_ID: &str = "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+
+/// Oldest price accepted when converting USD prices
+pub const MAX_PRICE_AGE_SECONDS: u64 = 60;
+/// Widest confidence interval accepted, in basis points of the price
+pub const MAX_CONFIDENCE_BPS: u64 = 200;
+
+const LAMPORTS_PER_USD_CENT_NUMERATOR: u128 = 10_000_000; // 1e9 lamports / 100 cents
+
+/// SOL/USD price read at instruction time, price * 10^exponent USD per SOL
+#[derive(Clone, Copy, Debug)]
+pub struct SolUsdPrice {
+    pub price: i64,
+    pub exponent: i32,
+}
+
+impl SolUsdPrice {
+    /// Build a price after the staleness and confidence checks
+    pub fn checked(
+        price: i64,
+        conf: u64,
+        exponent: i32,
+        publish_time: i64,
+        now: i64,
+    ) -> Result<Self> {
+        require!(price > 0, ErrorCode::InvalidPriceFeed);
+        require!(
+            now.saturating_sub(publish_time) <= MAX_PRICE_AGE_SECONDS as i64,
+            ErrorCode::StalePriceFeed
+        );
+        require!(
+            (conf as u128) * 10_000 <= (price as u128) * MAX_CONFIDENCE_BPS as u128,
+            ErrorCode::PriceConfidenceTooWide
+        );
+        Ok(Self { price, exponent })
+    }
+
+    /// Convert a USD price in cents to lamports at this SOL price
+    pub fn usd_cents_to_lamports(&self, usd_cents: u64) -> Result<u64> {
+        let scale = 10u128
+            .checked_pow(self.exponent.unsigned_abs())
+            .ok_or(ErrorCode::MathOverflow)?;
+        let mut numerator = (usd_cents as u128)
+            .checked_mul(LAMPORTS_PER_USD_CENT_NUMERATOR)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let mut denominator = self.price as u128;
+        if self.exponent < 0 {
+            numerator = numerator
+                .checked_mul(scale)
+                .ok_or(ErrorCode::MathOverflow)?;
+        } else {
+            denominator = denominator
+                .checked_mul(scale)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        u64::try_from(numerator / denominator).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
+/// Load the SOL/USD price from an optional feed account, None when not passed
+pub fn load_sol_usd_price(account: Option<&AccountInfo>) -> Result<Option<SolUsdPrice>> {
+    account.map(load_price_account).transpose()
+}
+
+/// Load a Pyth `PriceUpdateV2` account
+#[cfg(not(feature = "localnet"))]
+fn load_price_account(account: &AccountInfo) -> Result<SolUsdPrice> {
+    require_keys_eq!(
+        *account.owner,
+        pyth_solana_receiver_sdk::ID,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let data = account.try_borrow_data()?;
+    let update =
+        PriceUpdateV2::try_deserialize(&mut &data[..]).map_err(|_| ErrorCode::InvalidPriceFeed)?;
+    let feed_id = get_feed_id_from_hex(SOL_USD_FEED_ID).map_err(|_| ErrorCode::InvalidPriceFeed)?;
+
+    let clock = Clock::get()?;
+    let price = update
+        .get_price_no_older_than(&clock, MAX_PRICE_AGE_SECONDS, &feed_id)
+        .map_err(|e| match e {
+            GetPriceError::PriceTooOld => ErrorCode::StalePriceFeed,
+            _ => ErrorCode::InvalidPriceFeed,
+        })?;
+
+    SolUsdPrice::checked(
+        price.price,
+        price.conf,
+        price.exponent,
+        price.publish_time,
+        clock.unix_timestamp,
+    )
+}
+
+/// Load a mock price account (localnet builds have no Pyth receiver)
+#[cfg(feature = "localnet")]
+fn load_price_account(account: &AccountInfo) -> Result<SolUsdPrice> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidPriceFeed);
+
+    let data = account.try_borrow_data()?;
+    let mock =
+        MockPriceFeed::try_deserialize(&mut &data[..]).map_err(|_| ErrorCode::InvalidPriceFeed)?;
+
+    SolUsdPrice::checked(
+        mock.price,
+        mock.conf,
+        mock.exponent,
+        mock.publish_time,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+/// Resolve a price that may be set in USD cents, falling back to lamports
+pub fn resolve_lamports(
+    price_lamports: u64,
+    price_usd_cents: Option<u64>,
+    sol_usd: Option<&SolUsdPrice>,
+) -> Result<u64> {
+    match price_usd_cents {
+        Some(usd_cents) => sol_usd
+            .ok_or(ErrorCode::PriceFeedRequired)?
+            .usd_cents_to_lamports(usd_cents),
+        None => Ok(price_lamports),
+    }
+}
@@ -0,0 +1,171 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+
+#[cfg(feature = "localnet")]
+use crate::state::mock_randomness::MockRandomness;
+
+/// Switchboard on-demand program that owns randomness accounts
+#[cfg(all(feature = "mainnet", not(feature = "localnet")))]
+const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+#[cfg(not(any(feature = "mainnet", feature = "localnet")))]
+const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("Aio4gaXjXzJNVLtzwtNVmSqGKpANtXhybbkhtAC94ji2");
+
+// Roll domains, so rolls sharing one source stay independent
+pub const FORTUNE_DOMAIN: &[u8] = b"fortune";
+pub const FORTUNE_REROLL_DOMAIN: &[u8] = b"fortune_reroll";
+pub const FORTUNE_AMULET_DROP_DOMAIN: &[u8] = b"fortune_amulet_drop";
+pub const INCENSE_AMULET_DROP_DOMAIN: &[u8] = b"incense_amulet_drop";
+pub const WISH_AMULET_DROP_DOMAIN: &[u8] = b"wish_amulet_drop";
+
+/// Source of 32 bytes of entropy for an instruction
+pub trait RandomnessSource {
+    /// Raw entropy provided by the source
+    fn seed(&self) -> [u8; 32];
+
+    /// Derive an independent value for one roll from the source seed
+    fn derive(&self, domain: &[u8], user: &Pubkey, nonce: u64) -> [u8; 32] {
+        hashv(&[domain, &self.seed(), user.as_ref(), &nonce.to_le_bytes()]).to_bytes()
+    }
+
+    /// Derive a uniform u64 roll
+    fn roll_u64(&self, domain: &[u8], user: &Pubkey, nonce: u64) -> u64 {
+        bytes_to_u64(&self.derive(domain, user, nonce))
+    }
+
+    /// Derive a 0-99 roll
+    fn roll_percent(&self, domain: &[u8], user: &Pubkey, nonce: u64) -> u8 {
+        (self.roll_u64(domain, user, nonce) % 100) as u8
+    }
+}
+
+/// Newest entry of the SlotHashes sysvar, the hash of the previous slot.
+/// Public before the transaction lands, so rolls on it can be predicted and ground;
+/// mainnet builds never roll on it.
+pub struct SlotHashesRandomness {
+    hash: [u8; 32],
+}
+
+impl SlotHashesRandomness {
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(*account.key, slot_hashes::ID, ErrorCode::InvalidRandomness);
+
+        // Layout: u64 entry count, then (slot: u64, hash: [u8; 32]) entries, newest first
+        let data = account.try_borrow_data()?;
+        require!(data.len() >= 48, ErrorCode::RandomnessNotReady);
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(&data[16..48]);
+
+        Ok(Self { hash })
+    }
+}
+
+impl RandomnessSource for SlotHashesRandomness {
+    fn seed(&self) -> [u8; 32] {
+        self.hash
+    }
+}
+
+/// Value revealed by a Switchboard on-demand oracle
+#[cfg(not(feature = "localnet"))]
+pub struct SwitchboardRandomness {
+    value: [u8; 32],
+}
+
+#[cfg(not(feature = "localnet"))]
+impl RandomnessSource for SwitchboardRandomness {
+    fn seed(&self) -> [u8; 32] {
+        self.value
+    }
+}
+
+/// Value injected by tests through a mock randomness account
+#[cfg(feature = "localnet")]
+pub struct InjectedRandomness {
+    value: [u8; 32],
+}
+
+#[cfg(feature = "localnet")]
+impl RandomnessSource for InjectedRandomness {
+    fn seed(&self) -> [u8; 32] {
+        self.value
+    }
+}
+
+/// Source that settles commit-reveal draws in this build
+#[cfg(not(feature = "localnet"))]
+pub type RevealedRandomness = SwitchboardRandomness;
+#[cfg(feature = "localnet")]
+pub type RevealedRandomness = InjectedRandomness;
+
+/// Commit/reveal state read from a randomness account
+pub struct RandomnessSnapshot {
+    pub seed_slot: u64,   // Slot whose slothash seeds the randomness (commit slot)
+    pub reveal_slot: u64, // Slot the value was revealed in
+    pub value: [u8; 32],  // Revealed value
+}
+
+impl RandomnessSnapshot {
+    /// Whether the account is committed to the slot right before `clock_slot`
+    pub fn is_fresh_commit(&self, clock_slot: u64) -> bool {
+        self.seed_slot == clock_slot.saturating_sub(1)
+    }
+
+    /// Revealed value, only readable in the reveal slot like Switchboard's `get_value`
+    pub fn revealed_value(&self, clock_slot: u64) -> Option<[u8; 32]> {
+        if self.reveal_slot == clock_slot && self.reveal_slot > self.seed_slot {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+
+    /// Revealed value as a randomness source
+    pub fn revealed(&self, clock_slot: u64) -> Option<RevealedRandomness> {
+        self.revealed_value(clock_slot)
+            .map(|value| RevealedRandomness { value })
+    }
+}
+
+/// Load a Switchboard randomness account
+#[cfg(not(feature = "localnet"))]
+pub fn load_randomness(account: &AccountInfo) -> Result<RandomnessSnapshot> {
+    require_keys_eq!(
+        *account.owner,
+        SWITCHBOARD_PROGRAM_ID,
+        ErrorCode::InvalidRandomness
+    );
+
+    let data = switchboard_on_demand::RandomnessAccountData::parse(account.data.borrow())
+        .map_err(|_| ErrorCode::InvalidRandomness)?;
+
+    Ok(RandomnessSnapshot {
+        seed_slot: data.seed_slot,
+        reveal_slot: data.reveal_slot,
+        value: data.value,
+    })
+}
+
+/// Load a mock randomness account (localnet builds have no Switchboard oracle)
+#[cfg(feature = "localnet")]
+pub fn load_randomness(account: &AccountInfo) -> Result<RandomnessSnapshot> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::InvalidRandomness);
+
+    let data = account.try_borrow_data()?;
+    let mock = MockRandomness::try_deserialize(&mut &data[..])
+        .map_err(|_| ErrorCode::InvalidRandomness)?;
+
+    Ok(RandomnessSnapshot {
+        seed_slot: mock.seed_slot,
+        reveal_slot: mock.reveal_slot,
+        value: mock.value,
+    })
+}
+
+/// Read a u64 from the leading random bytes
+fn bytes_to_u64(value: &[u8; 32]) -> u64 {
+    let mut random_bytes = [0u8; 8];
+    random_bytes.copy_from_slice(&value[..8]);
+    u64::from_le_bytes(random_bytes)
+}
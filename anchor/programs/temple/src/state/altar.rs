@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+// One stick currently burning on the altar, empty when burner is the default key
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct AltarStick {
+    pub burner: Pubkey,
+    pub incense_id: u8,
+    pub amount: u64,      // Sticks lit together
+    pub lit_at: i64,      // Burn time
+    pub burns_until: i64, // Smoke stops rising
+}
+
+impl AltarStick {
+    pub fn is_empty(&self) -> bool {
+        self.burner == Pubkey::default()
+    }
+
+    pub fn is_burning(&self, now: i64) -> bool {
+        !self.is_empty() && self.burns_until > now
+    }
+}
+
+// Shared altar, a fixed ring buffer of the sticks currently burning
+#[account]
+pub struct Altar {
+    pub temple_config: Pubkey,
+    pub head: u16, // Next slot to light, the oldest stick once the altar is full
+    pub total_sticks_lit: u64, // Sticks ever lit on the altar
+    pub bump: u8,
+    pub sticks: Vec<AltarStick>, // Ring buffer, always holds slot_count entries
+}
+
+impl Altar {
+    pub const SEED_PREFIX: &str = "altar";
+    pub const DEFAULT_SLOTS: u16 = 64;
+    pub const MAX_SLOTS: u16 = 128;
+
+    /// Account space for an altar with `slot_count` slots
+    pub fn space(slot_count: u16) -> usize {
+        8 + 32 + 2 + 8 + 1 + 4 + slot_count as usize * AltarStick::INIT_SPACE
+    }
+
+    /// Clear sticks that have burned out
+    pub fn evict_expired(&mut self, now: i64) {
+        for stick in self.sticks.iter_mut() {
+            if !stick.is_empty() && !stick.is_burning(now) {
+                *stick = AltarStick::default();
+            }
+        }
+    }
+
+    /// Light a stick in the first free slot from head, or over the oldest stick when full
+    pub fn light(&mut self, stick: AltarStick, now: i64) {
+        self.evict_expired(now);
+        let slot_count = self.sticks.len();
+        if slot_count == 0 {
+            return;
+        }
+
+        let head = self.head as usize % slot_count;
+        let slot = (0..slot_count)
+            .map(|offset| (head + offset) % slot_count)
+            .find(|&i| self.sticks[i].is_empty())
+            .unwrap_or(head);
+        self.sticks[slot] = stick;
+        self.head = ((slot + 1) % slot_count) as u16;
+        self.total_sticks_lit = self.total_sticks_lit.saturating_add(stick.amount);
+    }
+
+    /// Change the slot count, keeping the most recently lit sticks
+    pub fn set_slot_count(&mut self, slot_count: u16) {
+        let len = self.sticks.len();
+        let head = if len == 0 {
+            0
+        } else {
+            self.head as usize % len
+        };
+        let mut burning: Vec<AltarStick> = (0..len)
+            .map(|offset| self.sticks[(head + offset) % len])
+            .filter(|stick| !stick.is_empty())
+            .collect();
+        burning.sort_by_key(|stick| stick.lit_at);
+
+        let keep_from = burning.len().saturating_sub(slot_count as usize);
+        let mut sticks = burning.split_off(keep_from);
+        let lit = sticks.len();
+        sticks.resize(slot_count as usize, AltarStick::default());
+
+        self.sticks = sticks;
+        self.head = if slot_count == 0 {
+            0
+        } else {
+            (lit % slot_count as usize) as u16
+        };
+    }
+}
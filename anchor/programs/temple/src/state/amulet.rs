@@ -1,4 +1,45 @@
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+// Amulet type, stored as u8 in AmuletNFT.amulet_type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmuletType {
+    Fortune = 0,    // +20% to Great Luck and Good Luck when drawing fortune
+    Protection = 1, // -20% to Bad Luck and Great Bad Luck when drawing fortune
+    Merit = 2,      // +10% merit when burning incense
+}
+
+impl AmuletType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(AmuletType::Fortune),
+            1 => Some(AmuletType::Protection),
+            2 => Some(AmuletType::Merit),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AmuletType::Fortune => "Fortune",
+            AmuletType::Protection => "Protection",
+            AmuletType::Merit => "Merit",
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            AmuletType::Fortune => {
+                "Raises the chance of Great Luck and Good Luck when drawing fortune"
+            }
+            AmuletType::Protection => {
+                "Lowers the chance of Bad Luck and Great Bad Luck when drawing fortune"
+            }
+            AmuletType::Merit => "Grants +10% merit when burning incense",
+        }
+    }
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -19,6 +60,13 @@ pub struct AmuletNFT {
     pub source: u8,
     // Serial number
     pub serial_number: u32,
+    // Amulet type (0=Fortune, 1=Protection, 2=Merit)
+    pub amulet_type: u8,
+    // Remaining buff charges
+    pub remaining_uses: u8,
+    // Last time the buff was applied
+    pub last_used_at: i64,
+    pub bump: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
@@ -30,6 +78,8 @@ pub enum AmuletSource {
 impl AmuletNFT {
     pub const SEED_PREFIX: &'static str = "amulet_nft";
     pub const TOKEN_DECIMALS: u8 = 0;
+    pub const MAX_USES: u8 = 10; // Buff charges per amulet
+    pub const USE_COOLDOWN_SECONDS: i64 = 24 * 60 * 60; // One buff per amulet per day
 
     // Get amulet URI
     pub fn get_amulet_uri(&self) -> String {
@@ -47,5 +97,58 @@ impl AmuletNFT {
             _ => "Unknown source",
         }
     }
+
+    // Verify the holder owns this amulet through its token account and consume one charge
+    pub fn use_buff(
+        &mut self,
+        holder: &Pubkey,
+        token_account: &TokenAccount,
+        expected_type: AmuletType,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            token_account.mint == self.mint
+                && token_account.owner == *holder
+                && token_account.amount >= 1,
+            ErrorCode::InvalidAmuletOwner
+        );
+        require!(
+            self.amulet_type == expected_type as u8,
+            ErrorCode::AmuletTypeMismatch
+        );
+        require!(self.remaining_uses > 0, ErrorCode::AmuletDepleted);
+        require!(
+            self.last_used_at == 0 || now - self.last_used_at >= Self::USE_COOLDOWN_SECONDS,
+            ErrorCode::AmuletOnCooldown
+        );
+
+        self.remaining_uses -= 1;
+        self.last_used_at = now;
+        Ok(())
+    }
 }
 
+// Apply an optional amulet passed to an instruction, returns whether the buff is active
+pub fn apply_amulet<'info>(
+    amulet: Option<&mut Account<'info, AmuletNFT>>,
+    token_account: Option<&Account<'info, TokenAccount>>,
+    holder: &Pubkey,
+    expected_type: AmuletType,
+    now: i64,
+) -> Result<bool> {
+    match (amulet, token_account) {
+        (Some(amulet), Some(token_account)) => {
+            amulet.use_buff(holder, token_account, expected_type, now)?;
+            emit!(crate::state::event::AmuletUsed {
+                user: *holder,
+                amulet_mint: amulet.mint,
+                amulet_type: amulet.amulet_type,
+                remaining_uses: amulet.remaining_uses,
+                timestamp: now,
+            });
+            Ok(true)
+        }
+        (None, None) => Ok(false),
+        _ => err!(ErrorCode::InvalidAmuletOwner),
+    }
+}
@@ -15,7 +15,4 @@ impl BuddhaNFT {
     pub const SEED_PREFIX: &'static str = "BuddhaNFT";
     pub const TOKEN_DECIMALS: u8 = 0;
     pub const TOKEN_NAME: &'static str = "BuddhaNFT";
-    pub const TOKEN_SYMBOL: &'static str = "MTK";
-    pub const TOKEN_URL: &'static str =
-        "https://solji.mypinata.cloud/ipfs/QmYHsbSuCpSUMksQP89VHawf21iJ7K6UE899uBrUmJgbVg";
 }
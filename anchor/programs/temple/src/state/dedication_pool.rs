@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+// Merit and incense points dedicated to memorials, shared by the whole temple
+#[account]
+#[derive(InitSpace)]
+pub struct DedicationPool {
+    pub temple_config: Pubkey,
+    pub total_incense_points: u64, // Incense points dedicated to memorials
+    pub total_merit: u64,          // Merit dedicated to memorials
+    pub total_dedications: u64,    // Dedicated burns
+    pub bump: u8,
+}
+
+impl DedicationPool {
+    pub const SEED_PREFIX: &str = "dedication_pool";
+
+    // Set owner on first use (account is created with init_if_needed)
+    pub fn ensure_initialized(&mut self, temple_config: Pubkey, bump: u8) {
+        if self.temple_config == Pubkey::default() {
+            self.temple_config = temple_config;
+            self.bump = bump;
+        }
+    }
+
+    // Credit a memorial dedication
+    pub fn add_dedication(&mut self, incense_points: u64, merit: u64) {
+        self.total_incense_points = self.total_incense_points.saturating_add(incense_points);
+        self.total_merit = self.total_merit.saturating_add(merit);
+        self.total_dedications = self.total_dedications.saturating_add(1);
+    }
+}
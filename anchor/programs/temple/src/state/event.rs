@@ -1,3 +1,5 @@
+use crate::instructions::incense::IncenseBurnEntry;
+use crate::state::fortune_nft::FortuneResult;
 use anchor_lang::prelude::*;
 
 // Donation Events
@@ -5,7 +7,8 @@ use anchor_lang::prelude::*;
 #[event]
 pub struct DonationCompleted {
     pub user: Pubkey,
-    pub amount: u64,
+    pub amount: u64,                  // Lamports, or SOL value of the token donation
+    pub payment_mint: Option<Pubkey>, // SPL token donated, None for SOL
     pub total_donated: u64,
     pub level: u8,
     pub timestamp: i64,
@@ -39,6 +42,94 @@ pub struct FortuneDrawn {
     pub timestamp: i64,
 }
 
+// FortuneShared Events
+
+#[event]
+pub struct FortuneShared {
+    pub user: Pubkey,
+    pub serial_number: u32,
+    pub share_hash: [u8; 32],
+    pub merit_reward: u64,
+    pub attested: bool,
+    pub timestamp: i64,
+}
+
+// FortuneResolved Events
+
+#[event]
+pub struct FortuneResolved {
+    pub user: Pubkey,
+    pub fortune_nft_mint: Pubkey,
+    pub serial_number: u32,
+    pub paid_with_incense: bool,
+    pub merit_reward: u64,
+    pub timestamp: i64,
+}
+
+// FortuneRerolled Events
+
+#[event]
+pub struct FortuneRerolled {
+    pub user: Pubkey,
+    pub fortune_nft_mint: Pubkey,
+    pub serial_number: u32,
+    pub previous_tier_index: u8,
+    pub previous_result: FortuneResult,
+    pub tier_index: u8,
+    pub fortune_result: FortuneResult,
+    pub merit_cost: u64,
+    pub reroll_count: u8, // Rerolls of this draw so far
+    pub timestamp: i64,
+}
+
+// FortuneRerollRequested Events
+
+#[event]
+pub struct FortuneRerollRequested {
+    pub user: Pubkey,
+    pub fortune_nft_mint: Pubkey,
+    pub randomness_account: Pubkey,
+    pub commit_slot: u64,
+    pub merit_cost: u64,
+    pub timestamp: i64,
+}
+
+// FortuneSetClaimed Events
+
+#[event]
+pub struct FortuneSetClaimed {
+    pub user: Pubkey,
+    pub set_nft_mint: Pubkey,
+    pub serial_numbers: Vec<u32>, // Fortune NFTs of the set, in FortuneResult order
+    pub burned: bool,             // Burned, else frozen in the user's wallet
+    pub merit_reward: u64,
+    pub timestamp: i64,
+}
+
+// FortuneReleased Events
+
+#[event]
+pub struct FortuneReleased {
+    pub user: Pubkey,
+    pub fortune_nft_mint: Pubkey,
+    pub serial_number: u32,
+    pub fortune_result: FortuneResult,
+    pub rent_refunded: u64, // Lamports of the closed FortuneNFT and token accounts
+    pub merit_reward: u64,
+    pub timestamp: i64,
+}
+
+// FortuneRequested Events
+
+#[event]
+pub struct FortuneRequested {
+    pub user: Pubkey,
+    pub randomness_account: Pubkey,
+    pub commit_slot: u64,
+    pub used_merit: bool,
+    pub timestamp: i64,
+}
+
 // Amulet Dropped Events
 #[event]
 pub struct AmuletDropped {
@@ -58,6 +149,16 @@ pub struct AmuletMinted {
     pub timestamp: i64,
 }
 
+// AmuletUsed Events
+#[event]
+pub struct AmuletUsed {
+    pub user: Pubkey,
+    pub amulet_mint: Pubkey,
+    pub amulet_type: u8,
+    pub remaining_uses: u8,
+    pub timestamp: i64,
+}
+
 // WishCreated Events
 #[event]
 pub struct WishCreated {
@@ -106,6 +207,17 @@ pub struct WishTowerNFTMinted {
     pub timestamp: i64,
 }
 
+// BuyIncense Events
+#[event]
+pub struct IncenseBought {
+    pub user: Pubkey,
+    pub incense_id: u8,
+    pub amount: u64,
+    pub total_fee: u64,               // In lamports, or base units of payment_mint
+    pub payment_mint: Option<Pubkey>, // SPL token paid with, None for SOL
+    pub timestamp: i64,
+}
+
 // BurnIncense Events
 #[event]
 pub struct IncenseBurned {
@@ -115,6 +227,45 @@ pub struct IncenseBurned {
     pub timestamp: i64,
 }
 
+// Burning streak Events
+#[event]
+pub struct StreakUpdated {
+    pub user: Pubkey,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub protection_used: bool, // A streak protection saved a missed day
+    pub streak_protections: u32,
+    pub bonus_percent: u16, // Merit bonus now applied
+    pub timestamp: i64,
+}
+
+// Dedicated incense Events
+#[event]
+pub struct IncenseDedicated {
+    pub giver: Pubkey,
+    pub beneficiary: Option<Pubkey>,     // Beneficiary user
+    pub memorial_hash: Option<[u8; 32]>, // Memorial record, credited to the dedication pool
+    pub incense_id: u8,
+    pub amount: u64,
+    pub incense_points: u64, // Credited to the beneficiary
+    pub merit: u64,          // Credited to the beneficiary
+    pub giver_merit: u64,    // Kept by the giver
+    pub timestamp: i64,
+}
+
+// BurnIncenseBatch Events
+#[event]
+pub struct IncenseBatchBurned {
+    pub user: Pubkey,
+    pub entries: Vec<IncenseBurnEntry>,
+    pub total_amount: u64,
+    pub total_incense_points: u64,
+    pub total_merit: u64,
+    pub total_fee: u64,               // In lamports, or base units of payment_mint
+    pub payment_mint: Option<Pubkey>, // SPL token paid with, None for SOL
+    pub timestamp: i64,
+}
+
 // ShopConfig Events
 #[event]
 pub struct ShopConfigUpdated {
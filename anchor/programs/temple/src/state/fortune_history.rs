@@ -0,0 +1,166 @@
+use crate::state::fortune_nft::FortuneResult;
+use crate::state::temple_config::TempleConfig;
+use anchor_lang::prelude::*;
+
+// One draw kept in the fortune history
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct FortuneRecord {
+    pub serial_number: u32,      // Fortune NFT serial number
+    pub tier_index: u8,          // Index in the fortune table as drawn
+    pub category: FortuneResult, // Luck category of the tier as drawn
+    pub drawn_at: i64,           // Draw time
+    pub merit_cost: u8,          // Merit spent, 0 for free draws
+    pub fortune_amulet: bool,    // Fortune amulet applied
+    pub protection_amulet: bool, // Protection amulet applied
+    pub rerolls: u8,             // Times rerolled, the Fortune NFT holds the latest result
+}
+
+// Per-user ledger of recent draws and lifetime outcome counts
+#[account]
+#[derive(InitSpace)]
+pub struct FortuneHistory {
+    pub user: Pubkey,
+    pub head: u8,                 // Next record to overwrite once the buffer is full
+    pub total_draws: u64,         // Draws recorded since the ledger was created
+    pub outcome_counts: [u32; 5], // Lifetime results per FortuneResult in enum order, rerolls move their draw
+    #[max_len(20)]
+    pub records: Vec<FortuneRecord>, // Ring buffer of the last MAX_RECORDS draws
+    pub daily_rerolls: u8,        // Rerolls on the day of last_reroll_time
+    pub last_reroll_time: i64,
+    pub bump: u8,
+    pub sets_claimed: u32, // Complete Fortune sets claimed, seeds the next set claim
+}
+
+impl FortuneHistory {
+    pub const SEED_PREFIX: &str = "fortune_history";
+    pub const MAX_RECORDS: usize = 20;
+
+    // Set owner on first use (account is created with init_if_needed)
+    pub fn ensure_initialized(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    /// Append a draw, overwriting the oldest record once full
+    pub fn record(&mut self, record: FortuneRecord) {
+        let count = &mut self.outcome_counts[record.category as usize];
+        *count = count.saturating_add(1);
+        self.total_draws = self.total_draws.saturating_add(1);
+
+        if self.records.len() < Self::MAX_RECORDS {
+            self.records.push(record);
+        } else {
+            self.records[self.head as usize] = record;
+            self.head = ((self.head as usize + 1) % Self::MAX_RECORDS) as u8;
+        }
+    }
+
+    /// Rerolls already made on the current temple day
+    pub fn rerolls_today(&self, temple_config: &TempleConfig, now: i64) -> u8 {
+        if temple_config.is_same_day(now, self.last_reroll_time) {
+            self.daily_rerolls
+        } else {
+            0
+        }
+    }
+
+    /// Count a requested reroll against the daily limit
+    pub fn count_reroll(&mut self, temple_config: &TempleConfig, now: i64) {
+        self.daily_rerolls = self.rerolls_today(temple_config, now).saturating_add(1);
+        self.last_reroll_time = now;
+    }
+
+    /// Move a rerolled draw from its previous result to the new one.
+    /// The record keeps the tier as drawn, draws that left the buffer still move their count.
+    pub fn record_reroll(
+        &mut self,
+        serial_number: u32,
+        previous: FortuneResult,
+        category: FortuneResult,
+    ) {
+        let previous_count = &mut self.outcome_counts[previous as usize];
+        *previous_count = previous_count.saturating_sub(1);
+        let count = &mut self.outcome_counts[category as usize];
+        *count = count.saturating_add(1);
+
+        if let Some(record) = self
+            .records
+            .iter_mut()
+            .find(|record| record.serial_number == serial_number)
+        {
+            record.rerolls = record.rerolls.saturating_add(1);
+        }
+    }
+
+    /// Lifetime draws of one outcome
+    pub fn outcome_count(&self, result: FortuneResult) -> u32 {
+        self.outcome_counts[result as usize]
+    }
+
+    /// Recorded draw by serial number, None once it left the buffer
+    pub fn find(&self, serial_number: u32) -> Option<&FortuneRecord> {
+        self.records
+            .iter()
+            .find(|record| record.serial_number == serial_number)
+    }
+
+    /// Recorded draws, newest first
+    pub fn recent(&self) -> impl Iterator<Item = &FortuneRecord> {
+        let (newer, older) = self.records.split_at(self.head as usize);
+        newer.iter().rev().chain(older.iter().rev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(serial_number: u32, category: FortuneResult) -> FortuneRecord {
+        FortuneRecord {
+            serial_number,
+            tier_index: category as u8,
+            category,
+            drawn_at: serial_number as i64,
+            merit_cost: 0,
+            fortune_amulet: false,
+            protection_amulet: false,
+            rerolls: 0,
+        }
+    }
+
+    #[test]
+    fn reroll_moves_counts_of_draws_outside_the_buffer() {
+        let mut history = FortuneHistory {
+            user: Pubkey::new_unique(),
+            head: 0,
+            total_draws: 0,
+            outcome_counts: [0; 5],
+            records: Vec::new(),
+            daily_rerolls: 0,
+            last_reroll_time: 0,
+            bump: 0,
+            sets_claimed: 0,
+        };
+        for serial_number in 1..=FortuneHistory::MAX_RECORDS as u32 + 1 {
+            history.record(draw(serial_number, FortuneResult::BadLuck));
+        }
+        assert!(history.find(1).is_none());
+
+        // Serial 1 left the buffer, its count still moves
+        history.record_reroll(1, FortuneResult::BadLuck, FortuneResult::GreatLuck);
+        history.record_reroll(2, FortuneResult::BadLuck, FortuneResult::GoodLuck);
+
+        assert_eq!(history.outcome_count(FortuneResult::BadLuck), 19);
+        assert_eq!(history.outcome_count(FortuneResult::GreatLuck), 1);
+        assert_eq!(history.outcome_count(FortuneResult::GoodLuck), 1);
+        assert_eq!(history.total_draws, 21);
+
+        // The record keeps the tier as drawn
+        let record = history.find(2).unwrap();
+        assert_eq!(record.category as u8, FortuneResult::BadLuck as u8);
+        assert_eq!(record.tier_index, FortuneResult::BadLuck as u8);
+        assert_eq!(record.rerolls, 1);
+    }
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
-#[derive(Clone, AnchorSerialize, AnchorDeserialize, InitSpace)]
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, InitSpace)]
 pub enum FortuneResult {
     GreatLuck,
     GoodLuck,
@@ -9,13 +10,145 @@ pub enum FortuneResult {
     GreatBadLuck,
 }
 
+impl FortuneResult {
+    pub const ALL: [FortuneResult; 5] = [
+        FortuneResult::GreatLuck,
+        FortuneResult::GoodLuck,
+        FortuneResult::Neutral,
+        FortuneResult::BadLuck,
+        FortuneResult::GreatBadLuck,
+    ];
+
+    pub fn is_lucky(&self) -> bool {
+        matches!(self, FortuneResult::GreatLuck | FortuneResult::GoodLuck)
+    }
+
+    pub fn is_unlucky(&self) -> bool {
+        matches!(self, FortuneResult::BadLuck | FortuneResult::GreatBadLuck)
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct FortuneNFT {
     pub owner: Pubkey,
     pub mint: Pubkey,
-    pub fortune_result: FortuneResult,
+    pub fortune_result: FortuneResult, // Category of the drawn tier
     pub minted_at: i64,
     pub merit_cost: u8,
     pub serial_number: u32,
+    // Fields below were appended after the first release, see load_upgraded
+    pub tier_index: u8, // Index in the fortune table
+    pub resolved: bool, // Bad fortune resolved through resolve_fortune
+    pub rerolls: u8,    // Times rerolled through settle_reroll
+    pub in_set: bool,   // Traded into a Complete Fortune set, frozen or burned
+}
+
+impl FortuneNFT {
+    /// Size of accounts minted before tier_index was appended
+    pub const LEGACY_SPACE: usize = 8 + 32 + 32 + 1 + 8 + 1 + 4;
+
+    /// Load a Fortune NFT account, growing ones minted before any appended field existed.
+    /// The payer funds the extra rent and the upgraded account is written back.
+    pub fn load_upgraded<'info>(
+        fortune_nft: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<Self> {
+        let required_space = 8 + Self::INIT_SPACE;
+        let baseline = fortune_nft.data_len() <= Self::LEGACY_SPACE;
+        let legacy = fortune_nft.data_len() < required_space;
+        if legacy {
+            let rent_due = Rent::get()?
+                .minimum_balance(required_space)
+                .saturating_sub(fortune_nft.lamports());
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: fortune_nft.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            fortune_nft.resize(required_space)?;
+        }
+        let fortune_nft_data = Self::decode(&fortune_nft.try_borrow_data()?, baseline)?;
+        if legacy {
+            fortune_nft_data.try_serialize(&mut &mut fortune_nft.try_borrow_mut_data()?[..])?;
+        }
+        Ok(fortune_nft_data)
+    }
+
+    /// Decode account data zero-padded to the current size.
+    /// First release draws used the default table, whose tiers follow FortuneResult order.
+    fn decode(data: &[u8], baseline: bool) -> Result<Self> {
+        let mut fortune_nft = Self::try_deserialize(&mut &data[..])?;
+        if baseline {
+            fortune_nft.tier_index = fortune_nft.fortune_result as u8;
+        }
+        Ok(fortune_nft)
+    }
+
+    /// Close a Fortune NFT account loaded through load_upgraded, refunding its rent
+    pub fn close<'info>(
+        fortune_nft: &AccountInfo<'info>,
+        destination: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let lamports = fortune_nft.get_lamports();
+        fortune_nft.sub_lamports(lamports)?;
+        destination.add_lamports(lamports)?;
+        fortune_nft.assign(&System::id());
+        fortune_nft.resize(0)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // FortuneNFT layout of the first release
+    #[derive(AnchorSerialize)]
+    struct BaselineFortuneNFT {
+        owner: Pubkey,
+        mint: Pubkey,
+        fortune_result: FortuneResult,
+        minted_at: i64,
+        merit_cost: u8,
+        serial_number: u32,
+    }
+
+    #[test]
+    fn loads_baseline_layout() {
+        let baseline = BaselineFortuneNFT {
+            owner: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            fortune_result: FortuneResult::BadLuck,
+            minted_at: 1_700_000_000,
+            merit_cost: 5,
+            serial_number: 42,
+        };
+        let mut data = FortuneNFT::DISCRIMINATOR.to_vec();
+        data.extend(borsh::to_vec(&baseline).unwrap());
+        assert_eq!(data.len(), FortuneNFT::LEGACY_SPACE);
+
+        // load_upgraded zero-pads the account to the current size before decoding
+        data.resize(8 + FortuneNFT::INIT_SPACE, 0);
+        let fortune_nft = FortuneNFT::decode(&data, true).unwrap();
+
+        assert_eq!(fortune_nft.owner, baseline.owner);
+        assert_eq!(fortune_nft.mint, baseline.mint);
+        assert!(matches!(fortune_nft.fortune_result, FortuneResult::BadLuck));
+        assert_eq!(fortune_nft.minted_at, baseline.minted_at);
+        assert_eq!(fortune_nft.merit_cost, baseline.merit_cost);
+        assert_eq!(fortune_nft.serial_number, baseline.serial_number);
+        assert_eq!(fortune_nft.tier_index, FortuneResult::BadLuck as u8);
+        assert!(!fortune_nft.resolved);
+        assert_eq!(fortune_nft.rerolls, 0);
+        assert!(!fortune_nft.in_set);
+    }
 }
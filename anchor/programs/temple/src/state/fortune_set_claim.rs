@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+// Completed fortune set, one per set a user claims.
+// Fortune NFTs are marked in_set so none counts towards a second set.
+#[account]
+#[derive(InitSpace)]
+pub struct FortuneSetClaim {
+    pub user: Pubkey,
+    pub set_index: u32, // Sets the user claimed before this one, seeds this record
+    pub set_nft_mint: Pubkey, // Complete Fortune NFT minted for the set
+    #[max_len(5)]
+    pub serial_numbers: Vec<u32>, // Fortune NFTs of the set, one per table category in FortuneResult order
+    pub burned: bool, // Burned, else frozen in the user's wallet
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl FortuneSetClaim {
+    pub const SEED_PREFIX: &str = "fortune_set_claim";
+    pub const MAX_SET_SIZE: usize = 5; // One Fortune NFT per FortuneResult in the table
+}
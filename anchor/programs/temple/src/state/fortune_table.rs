@@ -0,0 +1,390 @@
+use crate::error::ErrorCode;
+use crate::state::fortune_nft::FortuneResult;
+use crate::state::temple_config::FortuneConfig;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+// One slip of the fortune table
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct FortuneTier {
+    #[max_len(18)]
+    pub name: String, // Tier name, shown in NFT name
+    #[max_len(64)]
+    pub description: String, // Fortune explanation
+    #[max_len(96)]
+    pub uri: String, // NFT metadata URI
+    pub weight_bps: u16,         // Regular user weight (basis points)
+    pub buddha_weight_bps: u16,  // Buddha NFT holder weight (basis points)
+    pub merit_reward: u64,       // Merit reward for free draws
+    pub category: FortuneResult, // Luck category, used by amulet effects
+}
+
+impl FortuneTier {
+    pub const MAX_TIERS: usize = 128; // Enough for a 100-slip set
+    pub const TOTAL_WEIGHT_BPS: u64 = 10000;
+    pub const AMULET_SHIFT_BPS: u64 = 2000; // Amulets move a category by 20 probability points
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty() && self.name.len() <= 18,
+            ErrorCode::InvalidFortuneConfig
+        );
+        require!(
+            self.description.len() <= 64,
+            ErrorCode::InvalidFortuneConfig
+        );
+        require!(
+            !self.uri.is_empty() && self.uri.len() <= 96,
+            ErrorCode::InvalidFortuneConfig
+        );
+        Ok(())
+    }
+
+    /// Default five-tier table, weighted by the legacy percentage config
+    pub fn default_tiers(regular: &FortuneConfig, buddha: &FortuneConfig) -> Vec<FortuneTier> {
+        let tier = |category: FortuneResult,
+                    name: &str,
+                    description: &str,
+                    uri: &str,
+                    regular_prob: u8,
+                    buddha_prob: u8| FortuneTier {
+            name: name.to_string(),
+            description: description.to_string(),
+            uri: uri.to_string(),
+            weight_bps: regular_prob as u16 * 100,
+            buddha_weight_bps: buddha_prob as u16 * 100,
+            merit_reward: 2,
+            category,
+        };
+
+        vec![
+            tier(
+                FortuneResult::GreatLuck,
+                "Great Luck",
+                "Everything goes smoothly, wishes come true",
+                "https://solji.mypinata.cloud/ipfs/QmeYUSLgMKYL8128quaieDUXfbdeKVsGBRQLmWjgAsqw2y",
+                regular.great_luck_prob,
+                buddha.great_luck_prob,
+            ),
+            tier(
+                FortuneResult::GoodLuck,
+                "Good Luck",
+                "All things go well, gradually improving",
+                "https://solji.mypinata.cloud/ipfs/Qmdkcptk4783ej2sKNsK39UrNXXjjCxoFLbsYLt5KbhzDA",
+                regular.good_luck_prob,
+                buddha.good_luck_prob,
+            ),
+            tier(
+                FortuneResult::Neutral,
+                "Neutral",
+                "Plain and simple, steady progress",
+                "https://solji.mypinata.cloud/ipfs/QmZkYr6vMhSYA37TEPpN2pAC7Cw3DZSHQKo6mNfadbwKik",
+                regular.neutral_prob,
+                buddha.neutral_prob,
+            ),
+            tier(
+                FortuneResult::BadLuck,
+                "Bad Luck",
+                "Be careful, turn misfortune into fortune",
+                "https://solji.mypinata.cloud/ipfs/QmSiaGHzMyCijCSRf5tc9oFh7Ajs7Nnr2DMq6WVtiM4D8B",
+                regular.bad_luck_prob,
+                buddha.bad_luck_prob,
+            ),
+            tier(
+                FortuneResult::GreatBadLuck,
+                "Great Bad Luck",
+                "Be extra careful, observe quietly",
+                "https://solji.mypinata.cloud/ipfs/QmVQSbuJJnwYQ9ZJYvMcTDyxc1X75mjdVxNkjxWf6D2HsT",
+                regular.great_bad_luck_prob,
+                buddha.great_bad_luck_prob,
+            ),
+        ]
+    }
+}
+
+// Fortune table of a temple, kept out of TempleConfig so only fortune instructions pay for it.
+// Draws read it in place: a 100-slip table does not fit the program heap once deserialized.
+#[account]
+#[derive(InitSpace)]
+pub struct FortuneTable {
+    pub temple_config: Pubkey, // Associated temple config
+    pub bump: u8,
+    #[max_len(5)]
+    pub tiers: Vec<FortuneTier>, // Initial allocation, grown by update_fortune_tiers
+}
+
+// Sampling fields of a tier, read without deserializing its strings
+struct TierWeights {
+    start: usize, // Offset of the tier in the account data
+    end: usize,
+    weight_bps: u16,
+    buddha_weight_bps: u16,
+    category: FortuneResult,
+}
+
+impl FortuneTable {
+    pub const SEED_PREFIX: &str = "fortune_table";
+    const TIERS_OFFSET: usize = 8 + 32 + 1;
+
+    /// Number of tiers in fortune table account data
+    fn tier_count(data: &[u8]) -> Result<usize> {
+        require!(
+            data.len() >= Self::TIERS_OFFSET + 4 && data[..8] == *Self::DISCRIMINATOR,
+            ErrorCode::InvalidFortuneConfig
+        );
+        let count = u32::from_le_bytes(
+            data[Self::TIERS_OFFSET..Self::TIERS_OFFSET + 4]
+                .try_into()
+                .unwrap(),
+        );
+        Ok(count as usize)
+    }
+
+    /// Walk the serialized tiers, reading only what sampling needs
+    fn tier_weights(data: &[u8]) -> Result<impl Iterator<Item = Result<TierWeights>> + '_> {
+        let count = Self::tier_count(data)?;
+        let mut offset = Self::TIERS_OFFSET + 4;
+        Ok((0..count).map(move |_| {
+            let start = offset;
+            let mut read = |len: usize| -> Result<&[u8]> {
+                let bytes = data
+                    .get(offset..offset + len)
+                    .ok_or(ErrorCode::InvalidFortuneConfig)?;
+                offset += len;
+                Ok(bytes)
+            };
+            // name, description, uri
+            for _ in 0..3 {
+                let len = u32::from_le_bytes(read(4)?.try_into().unwrap());
+                read(len as usize)?;
+            }
+            let weight_bps = u16::from_le_bytes(read(2)?.try_into().unwrap());
+            let buddha_weight_bps = u16::from_le_bytes(read(2)?.try_into().unwrap());
+            read(8)?; // merit_reward
+            let category = FortuneResult::deserialize(&mut read(1)?)?;
+            Ok(TierWeights {
+                start,
+                end: offset,
+                weight_bps,
+                buddha_weight_bps,
+                category,
+            })
+        }))
+    }
+
+    /// Byte offset of a tier, or of the end of the table when index equals the tier count
+    fn tier_offset(data: &[u8], index: usize) -> Result<usize> {
+        let mut offset = Self::TIERS_OFFSET + 4;
+        for tier in Self::tier_weights(data)?.take(index) {
+            offset = tier?.end;
+        }
+        Ok(offset)
+    }
+
+    /// Check a fortune table account passed unchecked and borrow its data
+    pub fn data<'a>(fortune_table: &'a AccountInfo) -> Result<std::cell::Ref<'a, &'a mut [u8]>> {
+        require_keys_eq!(
+            *fortune_table.owner,
+            crate::ID,
+            ErrorCode::InvalidFortuneConfig
+        );
+        let data = fortune_table.try_borrow_data()?;
+        Self::tier_count(&data)?;
+        Ok(data)
+    }
+
+    /// Deserialize a single tier
+    pub fn get_tier(data: &[u8], index: u8) -> Result<FortuneTier> {
+        let weights = Self::tier_weights(data)?
+            .nth(index as usize)
+            .ok_or(ErrorCode::InvalidFortuneConfig)??;
+        Ok(FortuneTier::deserialize(&mut &data[weights.start..])?)
+    }
+
+    /// Categories with at least one tier, in FortuneResult order
+    pub fn categories(data: &[u8]) -> Result<Vec<FortuneResult>> {
+        let mut present = [false; 5];
+        for tier in Self::tier_weights(data)? {
+            present[tier?.category as usize] = true;
+        }
+        Ok(FortuneResult::ALL
+            .into_iter()
+            .filter(|category| present[*category as usize])
+            .collect())
+    }
+
+    /// Sample a tier by cumulative weight, returning its index and the tier
+    pub fn sample_tier(
+        data: &[u8],
+        roll: u64,
+        has_buddha_nft: bool,
+        fortune_amulet: bool,
+        protection_amulet: bool,
+    ) -> Result<(u8, FortuneTier)> {
+        let base_weight = |tier: &TierWeights| {
+            if has_buddha_nft {
+                tier.buddha_weight_bps as u64
+            } else {
+                tier.weight_bps as u64
+            }
+        };
+        let mut category_weights = [0u64; 5];
+        for tier in Self::tier_weights(data)? {
+            let tier = tier?;
+            category_weights[tier.category as usize] += base_weight(&tier);
+        }
+
+        // Amulets shift a category by 20 points, split across its tiers by weight
+        let draw_weight = |tier: &TierWeights| {
+            let weight = base_weight(tier);
+            let category_weight = category_weights[tier.category as usize];
+            if category_weight == 0 {
+                return weight;
+            }
+            let shift = weight * FortuneTier::AMULET_SHIFT_BPS / category_weight;
+            if fortune_amulet && tier.category.is_lucky() {
+                // Fortune Amulet: +20% to Great Luck and Good Luck probabilities
+                weight + shift
+            } else if protection_amulet && tier.category.is_unlucky() {
+                // Protection Amulet: -20% to Bad Luck and Great Bad Luck probabilities
+                weight.saturating_sub(shift)
+            } else {
+                weight
+            }
+        };
+
+        let mut total_weight = 0u64;
+        for tier in Self::tier_weights(data)? {
+            total_weight += draw_weight(&tier?);
+        }
+        require!(total_weight > 0, ErrorCode::InvalidFortuneConfig);
+
+        let target = roll % total_weight;
+        let mut cumulative = 0u64;
+        for (index, tier) in Self::tier_weights(data)?.enumerate() {
+            let tier = tier?;
+            cumulative += draw_weight(&tier);
+            if target < cumulative {
+                let fortune_tier = FortuneTier::deserialize(&mut &data[tier.start..])?;
+                return Ok((index as u8, fortune_tier));
+            }
+        }
+
+        err!(ErrorCode::InvalidFortuneConfig)
+    }
+
+    /// Replace the tiers from `offset` on, growing the account when the table outgrows it.
+    /// Returns the regular and Buddha weight totals of the whole table.
+    pub fn write_tiers<'info>(
+        fortune_table: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        offset: usize,
+        tiers: &[FortuneTier],
+    ) -> Result<(u64, u64)> {
+        let (start, count) = {
+            let data = Self::data(fortune_table)?;
+            (Self::tier_offset(&data, offset)?, Self::tier_count(&data)?)
+        };
+        require!(offset <= count, ErrorCode::InvalidFortuneConfig);
+
+        let mut chunk = Vec::new();
+        for tier in tiers {
+            tier.serialize(&mut chunk)?;
+        }
+        let required_space = start + chunk.len();
+        if required_space > fortune_table.data_len() {
+            let rent_due = Rent::get()?
+                .minimum_balance(required_space)
+                .saturating_sub(fortune_table.lamports());
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: fortune_table.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            fortune_table.resize(required_space)?;
+        }
+
+        let mut data = fortune_table.try_borrow_mut_data()?;
+        data[start..required_space].copy_from_slice(&chunk);
+        let new_count = (offset + tiers.len()) as u32;
+        data[Self::TIERS_OFFSET..Self::TIERS_OFFSET + 4].copy_from_slice(&new_count.to_le_bytes());
+
+        let mut totals = (0u64, 0u64);
+        for tier in Self::tier_weights(&data)? {
+            let tier = tier?;
+            totals.0 += tier.weight_bps as u64;
+            totals.1 += tier.buddha_weight_bps as u64;
+        }
+        Ok(totals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table_data() -> Vec<u8> {
+        let config = FortuneConfig {
+            great_luck_prob: 10,
+            good_luck_prob: 20,
+            neutral_prob: 40,
+            bad_luck_prob: 20,
+            great_bad_luck_prob: 10,
+        };
+        let table = FortuneTable {
+            temple_config: Pubkey::new_unique(),
+            bump: 255,
+            tiers: FortuneTier::default_tiers(&config, &config),
+        };
+        let mut data = Vec::new();
+        table.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn reads_tiers_in_place() {
+        let data = table_data();
+        assert_eq!(FortuneTable::tier_count(&data).unwrap(), 5);
+        assert_eq!(FortuneTable::get_tier(&data, 3).unwrap().name, "Bad Luck");
+        assert!(FortuneTable::get_tier(&data, 5).is_err());
+        assert_eq!(FortuneTable::tier_offset(&data, 5).unwrap(), data.len());
+        assert_eq!(FortuneTable::categories(&data).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn samples_by_cumulative_weight() {
+        let data = table_data();
+        let sample = |roll| FortuneTable::sample_tier(&data, roll, false, false, false).unwrap();
+        assert_eq!(sample(0).0, 0);
+        assert_eq!(sample(999).0, 0);
+        assert_eq!(sample(1000).0, 1);
+        assert_eq!(sample(6999).0, 2);
+        assert_eq!(sample(9999).1.name, "Great Bad Luck");
+        assert_eq!(sample(10000).0, 0);
+    }
+
+    #[test]
+    fn amulets_shift_twenty_points() {
+        let data = table_data();
+
+        // 30 / 40 / 40 / 20 / 10 points
+        let sample = |roll| FortuneTable::sample_tier(&data, roll, false, true, false).unwrap();
+        assert_eq!(sample(2999).0, 0);
+        assert_eq!(sample(3000).0, 1);
+        assert_eq!(sample(6999).0, 1);
+        assert_eq!(sample(13999).0, 4);
+
+        // 10 / 20 / 40 / 0 / 0 points
+        let sample = |roll| FortuneTable::sample_tier(&data, roll, false, false, true).unwrap();
+        assert_eq!(sample(6999).0, 2);
+        assert_eq!(sample(7000).0, 0);
+    }
+}
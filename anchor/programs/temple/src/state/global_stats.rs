@@ -1,5 +1,12 @@
 use anchor_lang::prelude::*;
 
+// Total paid in one SPL payment mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct MintPaymentTotal {
+    pub mint: Pubkey,
+    pub total_amount: u64, // Base units of the mint
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct GlobalStats {
@@ -17,10 +24,14 @@ pub struct GlobalStats {
     pub total_buddha_lights: u64, // Total buddha lights
     // Metadata
     pub updated_at: i64, // Last updated time
+    // Token payment statistics
+    #[max_len(8)]
+    pub token_payments: Vec<MintPaymentTotal>, // Totals per payment mint
 }
 
 impl GlobalStats {
     pub const SEED_PREFIX: &str = "global_stats_v1";
+    pub const MAX_TOKEN_PAYMENT_MINTS: usize = 8;
 
     // Convert
     pub fn total_donations_sol(&self) -> f64 {
@@ -54,6 +65,21 @@ impl GlobalStats {
         self.updated_at = Clock::get().unwrap().unix_timestamp;
     }
 
+    // Token payment, mints past the allocation are not tracked
+    pub fn add_token_payment(&mut self, mint: Pubkey, amount: u64) {
+        if let Some(total) = self.token_payments.iter_mut().find(|t| t.mint == mint) {
+            total.total_amount = total.total_amount.saturating_add(amount);
+        } else if self.token_payments.len() < Self::MAX_TOKEN_PAYMENT_MINTS {
+            self.token_payments.push(MintPaymentTotal {
+                mint,
+                total_amount: amount,
+            });
+        } else {
+            msg!("Token payment totals full, {} not tracked", mint);
+        }
+        self.updated_at = Clock::get().unwrap().unix_timestamp;
+    }
+
     // Fortune NFT
     pub fn increment_fortune_nfts(&mut self) {
         self.total_fortune_nfts = self.total_fortune_nfts.saturating_add(1);
@@ -0,0 +1,419 @@
+use crate::state::temple_config::*;
+use crate::state::user_state::{DailyIncenseCount, IncenseBalance, UserIncenseState, UserTitle};
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+// ===== First release account layouts =====
+// Frozen copies read by the migration instructions, never change them
+
+// TempleConfig v1 incense type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyIncenseType {
+    pub id: u8,
+    pub name: String,
+    pub price_lamports: u64,
+    pub merit: u64,
+    pub incense_points: u64,
+    pub is_donation: bool,
+}
+
+// TempleConfig v1 fortune probabilities, now seeding the FortuneTable account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyFortuneConfig {
+    pub great_luck_prob: u8,
+    pub good_luck_prob: u8,
+    pub neutral_prob: u8,
+    pub bad_luck_prob: u8,
+    pub great_bad_luck_prob: u8,
+}
+
+// TempleConfig v1 donation level
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyDonationLevelConfig {
+    pub level: u8,
+    pub min_amount_sol: f64,
+    pub merit_reward: u64,
+    pub incense_reward: u64,
+}
+
+// TempleConfig v1 donation reward
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyDonationRewardConfig {
+    pub min_donation_sol: f64,
+    pub incense_id: u8,
+    pub incense_amount: u64,
+    pub burn_bonus_per_001_sol: u64,
+}
+
+// TempleConfig v1 special incense type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacySpecialIncenseType {
+    pub id: u8,
+    pub name: String,
+    pub required_donation_sol: f64,
+    pub amount_per_donation: u64,
+    pub merit: u64,
+    pub incense_points: u64,
+    pub is_donation_only: bool,
+}
+
+// TempleConfig v1 temple level
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyTempleLevelConfig {
+    pub level: u8,
+    pub required_incense_points: u64,
+    pub required_draw_fortune: u64,
+    pub required_wishes: u64,
+    pub required_donations_sol: f64,
+    pub required_fortune_nfts: u64,
+    pub required_amulets: u64,
+}
+
+// TempleConfig v1 dynamic config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyDynamicConfig {
+    pub incense_types: Vec<LegacyIncenseType>,
+    pub regular_fortune: LegacyFortuneConfig,
+    pub buddha_fortune: LegacyFortuneConfig,
+    pub donation_levels: Vec<LegacyDonationLevelConfig>,
+    pub donation_rewards: Vec<LegacyDonationRewardConfig>,
+    pub temple_levels: Vec<LegacyTempleLevelConfig>,
+    pub special_incense_types: Vec<LegacySpecialIncenseType>,
+}
+
+// TempleConfig v1, before the temple calendar and the dynamic config sections 7-13
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyTempleConfig {
+    pub owner: Pubkey,
+    pub treasury: Pubkey,
+    pub level: u8,
+    pub created_at: i64,
+    pub total_buddha_nft: u32,
+    pub total_medal_nft: u32,
+    pub total_amulets: u32,
+    pub status: u8,
+    pub open_time: u64,
+    pub donation_deadline: u64,
+    pub dynamic_config: LegacyDynamicConfig,
+}
+
+impl LegacyTempleConfig {
+    /// Daily cap the first release applied to every incense type
+    pub const DAILY_CAP: u8 = 10;
+    /// Altar burn time given to migrated incense types
+    pub const BURN_DURATION_SECONDS: u32 = 1800;
+
+    /// Decode TempleConfig account data still on the v1 layout
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == *TempleConfig::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..])
+            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+    }
+
+    /// Current layout, with the first release behaviour for the fields added since
+    pub fn upgrade(self) -> TempleConfig {
+        let dynamic_config = self.dynamic_config;
+        TempleConfig {
+            owner: self.owner,
+            treasury: self.treasury,
+            level: self.level,
+            created_at: self.created_at,
+            total_buddha_nft: self.total_buddha_nft,
+            total_medal_nft: self.total_medal_nft,
+            total_amulets: self.total_amulets,
+            status: self.status,
+            open_time: self.open_time,
+            donation_deadline: self.donation_deadline,
+            utc_offset_minutes: TempleConfig::DEFAULT_UTC_OFFSET_MINUTES,
+            day_boundary_hour: 0,
+            dynamic_config: DynamicConfig {
+                incense_types: dynamic_config
+                    .incense_types
+                    .into_iter()
+                    .map(|incense_type| IncenseType {
+                        id: incense_type.id,
+                        name: incense_type.name,
+                        price_lamports: incense_type.price_lamports,
+                        price_usd_cents: None,
+                        merit: incense_type.merit,
+                        incense_points: incense_type.incense_points,
+                        is_donation: incense_type.is_donation,
+                        daily_cap: Self::DAILY_CAP,
+                        cooldown_seconds: 0,
+                        burn_duration_seconds: Self::BURN_DURATION_SECONDS,
+                        holder_cap: None,
+                    })
+                    .collect(),
+                donation_levels: dynamic_config
+                    .donation_levels
+                    .into_iter()
+                    .map(|donation_level| DonationLevelConfig {
+                        level: donation_level.level,
+                        min_amount_sol: donation_level.min_amount_sol,
+                        min_amount_usd_cents: None,
+                        merit_reward: donation_level.merit_reward,
+                        incense_reward: donation_level.incense_reward,
+                    })
+                    .collect(),
+                donation_rewards: dynamic_config
+                    .donation_rewards
+                    .into_iter()
+                    .map(|reward| DonationRewardConfig {
+                        min_donation_sol: reward.min_donation_sol,
+                        incense_id: reward.incense_id,
+                        incense_amount: reward.incense_amount,
+                        burn_bonus_per_001_sol: reward.burn_bonus_per_001_sol,
+                    })
+                    .collect(),
+                temple_levels: dynamic_config
+                    .temple_levels
+                    .into_iter()
+                    .map(|temple_level| TempleLevelConfig {
+                        level: temple_level.level,
+                        required_incense_points: temple_level.required_incense_points,
+                        required_draw_fortune: temple_level.required_draw_fortune,
+                        required_wishes: temple_level.required_wishes,
+                        required_donations_sol: temple_level.required_donations_sol,
+                        required_fortune_nfts: temple_level.required_fortune_nfts,
+                        required_amulets: temple_level.required_amulets,
+                    })
+                    .collect(),
+                special_incense_types: dynamic_config
+                    .special_incense_types
+                    .into_iter()
+                    .map(|special_incense| SpecialIncenseType {
+                        id: special_incense.id,
+                        name: special_incense.name,
+                        required_donation_sol: special_incense.required_donation_sol,
+                        amount_per_donation: special_incense.amount_per_donation,
+                        merit: special_incense.merit,
+                        incense_points: special_incense.incense_points,
+                        is_donation_only: special_incense.is_donation_only,
+                    })
+                    .collect(),
+                streak_multipliers: StreakMultiplier::default_tiers(),
+                payment_mints: vec![],
+                activity_economy: ActivityEconomyConfig::default(),
+                fortune_resolve: FortuneResolveConfig::default(),
+                fortune_reroll: FortuneRerollConfig::default(),
+                fortune_set: FortuneSetConfig::default(),
+                fortune_release: FortuneReleaseConfig::default(),
+                dedication: DedicationConfig::default(),
+            },
+        }
+    }
+}
+
+// UserIncenseState v1 title
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub enum LegacyUserTitle {
+    Pilgrim,
+    Disciple,
+    Protector,
+    Patron,
+    Abbot,
+}
+
+// UserIncenseState v1 incense balance slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyIncenseBalance {
+    pub incense_id: u8,
+    pub balance: u64,
+}
+
+// UserIncenseState v1 daily count slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyDailyIncenseCount {
+    pub incense_id: u8,
+    pub count: u8,
+}
+
+// UserIncenseState v1, holding balances and daily counts in six fixed slots
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LegacyUserIncenseState {
+    pub user: Pubkey,
+    pub title: LegacyUserTitle,
+    pub incense_points: u64,
+    pub merit: u64,
+    pub incense_number: u8,
+    pub update_time: i64,
+    pub bump: u8,
+    pub incense_balance: [LegacyIncenseBalance; 6],
+    pub daily_incense_count: [LegacyDailyIncenseCount; 6],
+    pub daily_draw_count: u8,
+    pub last_draw_time: i64,
+    pub total_draws: u32,
+    pub daily_wish_count: u8,
+    pub last_wish_time: i64,
+    pub total_wishes: u32,
+}
+
+impl LegacyUserIncenseState {
+    /// Decode UserIncenseState account data on the v1 layout
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == *UserIncenseState::DISCRIMINATOR,
+            anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch
+        );
+        Self::deserialize(&mut &data[8..])
+            .map_err(|_| anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into())
+    }
+
+    /// Current layout, legacy slots still to be moved into the incense inventory
+    pub fn upgrade(self) -> UserIncenseState {
+        UserIncenseState {
+            user: self.user,
+            title: match self.title {
+                LegacyUserTitle::Pilgrim => UserTitle::Pilgrim,
+                LegacyUserTitle::Disciple => UserTitle::Disciple,
+                LegacyUserTitle::Protector => UserTitle::Protector,
+                LegacyUserTitle::Patron => UserTitle::Patron,
+                LegacyUserTitle::Abbot => UserTitle::Abbot,
+            },
+            incense_points: self.incense_points,
+            merit: self.merit,
+            incense_number: self.incense_number,
+            update_time: self.update_time,
+            bump: self.bump,
+            incense_balance: self.incense_balance.map(|item| IncenseBalance {
+                incense_id: item.incense_id,
+                balance: item.balance,
+            }),
+            daily_incense_count: self.daily_incense_count.map(|item| DailyIncenseCount {
+                incense_id: item.incense_id,
+                count: item.count,
+            }),
+            daily_draw_count: self.daily_draw_count,
+            last_draw_time: self.last_draw_time,
+            total_draws: self.total_draws,
+            daily_wish_count: self.daily_wish_count,
+            last_wish_time: self.last_wish_time,
+            total_wishes: self.total_wishes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_temple_config() -> LegacyTempleConfig {
+        LegacyTempleConfig {
+            owner: Pubkey::new_unique(),
+            treasury: Pubkey::new_unique(),
+            level: 2,
+            created_at: 1_700_000_000,
+            total_buddha_nft: 3,
+            total_medal_nft: 4,
+            total_amulets: 5,
+            status: 0,
+            open_time: 1_700_000_000,
+            donation_deadline: 1_800_000_000,
+            dynamic_config: LegacyDynamicConfig {
+                incense_types: vec![LegacyIncenseType {
+                    id: 1,
+                    name: "Fresh".to_string(),
+                    price_lamports: 10_000_000,
+                    merit: 10,
+                    incense_points: 100,
+                    is_donation: false,
+                }],
+                regular_fortune: LegacyFortuneConfig {
+                    great_luck_prob: 10,
+                    good_luck_prob: 20,
+                    neutral_prob: 40,
+                    bad_luck_prob: 20,
+                    great_bad_luck_prob: 10,
+                },
+                buddha_fortune: LegacyFortuneConfig {
+                    great_luck_prob: 15,
+                    good_luck_prob: 25,
+                    neutral_prob: 40,
+                    bad_luck_prob: 15,
+                    great_bad_luck_prob: 5,
+                },
+                donation_levels: vec![LegacyDonationLevelConfig {
+                    level: 1,
+                    min_amount_sol: 0.05,
+                    merit_reward: 65,
+                    incense_reward: 0,
+                }],
+                donation_rewards: vec![],
+                temple_levels: vec![],
+                special_incense_types: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn upgrades_v1_temple_config() {
+        let legacy = v1_temple_config();
+        let mut data = TempleConfig::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        // The first release allocated a fixed size, zero padded past the config
+        data.resize(8 + 7304, 0);
+
+        assert!(TempleConfig::try_deserialize(&mut &data[..]).is_err());
+
+        let temple_config = LegacyTempleConfig::decode(&data).unwrap().upgrade();
+        assert_eq!(temple_config.owner, legacy.owner);
+        assert_eq!(temple_config.total_amulets, 5);
+        assert_eq!(temple_config.donation_deadline, 1_800_000_000);
+
+        let incense_type = temple_config.find_incense_type(1).unwrap();
+        assert_eq!(incense_type.price_lamports, 10_000_000);
+        assert_eq!(incense_type.daily_cap, LegacyTempleConfig::DAILY_CAP);
+        assert!(incense_type.validate().is_ok());
+        assert_eq!(
+            temple_config.dynamic_config.donation_levels[0].merit_reward,
+            65
+        );
+
+        let mut upgraded = Vec::new();
+        temple_config.try_serialize(&mut upgraded).unwrap();
+        assert!(TempleConfig::try_deserialize(&mut &upgraded[..]).is_ok());
+    }
+
+    #[test]
+    fn upgrades_v1_user_incense_state() {
+        let legacy = LegacyUserIncenseState {
+            user: Pubkey::new_unique(),
+            title: LegacyUserTitle::Protector,
+            incense_points: 3100,
+            merit: 1200,
+            incense_number: 2,
+            update_time: 1_700_000_000,
+            bump: 254,
+            incense_balance: [1, 2, 0, 0, 0, 0].map(|incense_id| LegacyIncenseBalance {
+                incense_id,
+                balance: u64::from(incense_id) * 5,
+            }),
+            daily_incense_count: [3, 0, 0, 0, 0, 0].map(|incense_id| LegacyDailyIncenseCount {
+                incense_id,
+                count: 2,
+            }),
+            daily_draw_count: 1,
+            last_draw_time: 1_700_000_100,
+            total_draws: 7,
+            daily_wish_count: 0,
+            last_wish_time: 0,
+            total_wishes: 4,
+        };
+        let mut data = UserIncenseState::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+
+        let user_incense_state = LegacyUserIncenseState::decode(&data).unwrap().upgrade();
+        assert_eq!(user_incense_state.user, legacy.user);
+        assert_eq!(user_incense_state.title, UserTitle::Protector);
+        assert_eq!(user_incense_state.incense_balance[1].balance, 10);
+        assert_eq!(user_incense_state.daily_incense_count[0].incense_id, 3);
+        assert_eq!(user_incense_state.total_wishes, 4);
+
+        let mut upgraded = Vec::new();
+        user_incense_state.try_serialize(&mut upgraded).unwrap();
+        assert_eq!(upgraded, data);
+    }
+}
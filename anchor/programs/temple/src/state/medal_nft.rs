@@ -29,7 +29,12 @@ impl MedalNFT {
 
     // Get medal name by level
     pub fn get_medal_name(&self) -> &'static str {
-        match self.level {
+        Self::get_medal_name_by_level(self.level)
+    }
+
+    // Get medal name by level (static method)
+    pub fn get_medal_name_by_level(level: u8) -> &'static str {
+        match level {
             1 => "Entry Merit Bronze Medal",
             2 => "Diligent Silver Medal",
             3 => "Protector Gold Medal",
@@ -38,27 +43,6 @@ impl MedalNFT {
         }
     }
 
-    // Get medal URI by level
-    pub fn get_medal_uri(&self) -> String {
-        Self::get_medal_uri_by_level(self.level)
-    }
-
-    // Get medal URI by level (static method)
-    pub fn get_medal_uri_by_level(level: u8) -> String {
-        match level {
-            1 => "https://solji.mypinata.cloud/ipfs/QmPmFctW8BUPSvebp44rQaVTSmV8Lapd4axVoRsifzA24F"
-                .to_string(),
-            2 => "https://solji.mypinata.cloud/ipfs/QmSH2M4KFCfTLS4MgzXMh83tLoFqwzfY3GR6YyXrnkNRxN"
-                .to_string(),
-            3 => "https://solji.mypinata.cloud/ipfs/QmQSL1ERzKoDUMeudSePCscU8yxm4WVjMCobLjKyus1dcN"
-                .to_string(),
-            4 => "https://solji.mypinata.cloud/ipfs/Qmf1SHiMwMP7rycyzrGoFFV4iVW9F41tPbaBFc8ndoWPPq"
-                .to_string(),
-            _ => "https://solji.mypinata.cloud/ipfs/QmPmFctW8BUPSvebp44rQaVTSmV8Lapd4axVoRsifzA24F"
-                .to_string(),
-        }
-    }
-
     // Minimum donation amount for each level (SOL)
     pub fn get_level_min_donation_sol(level: u8) -> f64 {
         match level {
@@ -0,0 +1,248 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+
+// NFT families minted by the temple
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, Debug, InitSpace, PartialEq, Eq)]
+pub enum NftFamily {
+    Fortune,
+    Medal,
+    Amulet,
+    WishTower,
+    Buddha,
+    ResolvedFortune,
+    FortuneSet,
+}
+
+// Metadata template of one NFT family.
+// Templates may use {name}, {serial}, {level}, {count} and {variant}
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct NftMetadataTemplate {
+    #[max_len(64)]
+    pub name_template: String, // Rendered NFT name
+    #[max_len(10)]
+    pub symbol: String, // Token symbol
+    #[max_len(128)]
+    pub uri_template: String, // Rendered metadata URI
+    pub seller_fee_basis_points: u16, // Royalty
+    #[max_len(5, 64)]
+    pub variant_uris: Vec<String>, // {variant} per medal level / amulet type
+}
+
+// Values substituted into a template at the mint site
+#[derive(Default)]
+pub struct MetadataVars<'a> {
+    pub name: &'a str,    // Tier, medal or amulet type name
+    pub serial: u64,      // Serial number
+    pub level: u8,        // Medal or wish tower level
+    pub count: u64,       // Wish count of a wish tower
+    pub variant: &'a str, // Fortune tier URI, else taken from variant_uris
+}
+
+impl NftMetadataTemplate {
+    pub const MAX_NAME_TEMPLATE_LEN: usize = 64;
+    pub const MAX_SYMBOL_LEN: usize = 10;
+    pub const MAX_URI_TEMPLATE_LEN: usize = 128;
+    pub const MAX_VARIANTS: usize = 5;
+    pub const MAX_VARIANT_LEN: usize = 64;
+    // Token metadata program limits on rendered fields
+    pub const MAX_RENDERED_NAME_LEN: usize = 32;
+    pub const MAX_RENDERED_URI_LEN: usize = 200;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name_template.is_empty()
+                && self.name_template.len() <= Self::MAX_NAME_TEMPLATE_LEN,
+            ErrorCode::InvalidMetadataConfig
+        );
+        require!(
+            self.symbol.len() <= Self::MAX_SYMBOL_LEN,
+            ErrorCode::InvalidMetadataConfig
+        );
+        require!(
+            !self.uri_template.is_empty() && self.uri_template.len() <= Self::MAX_URI_TEMPLATE_LEN,
+            ErrorCode::InvalidMetadataConfig
+        );
+        require!(
+            self.seller_fee_basis_points <= 10_000,
+            ErrorCode::InvalidMetadataConfig
+        );
+        require!(
+            self.variant_uris.len() <= Self::MAX_VARIANTS
+                && self
+                    .variant_uris
+                    .iter()
+                    .all(|uri| uri.len() <= Self::MAX_VARIANT_LEN),
+            ErrorCode::InvalidMetadataConfig
+        );
+        // Reject templates whose fixed text already exceeds the rendered limits
+        require!(
+            Self::render(&self.name_template, &MetadataVars::default()).len()
+                <= Self::MAX_RENDERED_NAME_LEN,
+            ErrorCode::InvalidMetadataConfig
+        );
+        Ok(())
+    }
+
+    /// Variant URI part by index, empty when not configured
+    pub fn variant(&self, index: usize) -> &str {
+        self.variant_uris
+            .get(index)
+            .map(String::as_str)
+            .unwrap_or("")
+    }
+
+    fn render(template: &str, vars: &MetadataVars) -> String {
+        template
+            .replace("{name}", vars.name)
+            .replace("{serial}", &vars.serial.to_string())
+            .replace("{level}", &vars.level.to_string())
+            .replace("{count}", &vars.count.to_string())
+            .replace("{variant}", vars.variant)
+    }
+
+    /// Token metadata rendered from this template, checked against the token metadata limits
+    pub fn to_data(&self, vars: &MetadataVars) -> Result<DataV2> {
+        let name = Self::render(&self.name_template, vars);
+        let uri = Self::render(&self.uri_template, vars);
+        require!(
+            name.len() <= Self::MAX_RENDERED_NAME_LEN,
+            ErrorCode::MetadataTooLong
+        );
+        require!(
+            self.symbol.len() <= Self::MAX_SYMBOL_LEN,
+            ErrorCode::MetadataTooLong
+        );
+        require!(
+            uri.len() <= Self::MAX_RENDERED_URI_LEN,
+            ErrorCode::MetadataTooLong
+        );
+
+        Ok(DataV2 {
+            name,
+            symbol: self.symbol.clone(),
+            uri,
+            seller_fee_basis_points: self.seller_fee_basis_points,
+            creators: None,
+            collection: None,
+            uses: None,
+        })
+    }
+}
+
+// Metadata templates of all NFT families, editable without a program upgrade
+#[account]
+#[derive(InitSpace)]
+pub struct MetadataConfig {
+    pub temple_config: Pubkey, // Associated temple config
+    pub fortune: NftMetadataTemplate,
+    pub medal: NftMetadataTemplate,
+    pub amulet: NftMetadataTemplate,
+    pub wish_tower: NftMetadataTemplate,
+    pub buddha: NftMetadataTemplate,
+    pub resolved_fortune: NftMetadataTemplate,
+    pub fortune_set: NftMetadataTemplate,
+    pub bump: u8,
+}
+
+impl MetadataConfig {
+    pub const SEED_PREFIX: &str = "metadata_config";
+
+    pub fn template(&self, family: NftFamily) -> &NftMetadataTemplate {
+        match family {
+            NftFamily::Fortune => &self.fortune,
+            NftFamily::Medal => &self.medal,
+            NftFamily::Amulet => &self.amulet,
+            NftFamily::WishTower => &self.wish_tower,
+            NftFamily::Buddha => &self.buddha,
+            NftFamily::ResolvedFortune => &self.resolved_fortune,
+            NftFamily::FortuneSet => &self.fortune_set,
+        }
+    }
+
+    pub fn set_template(&mut self, family: NftFamily, template: NftMetadataTemplate) {
+        match family {
+            NftFamily::Fortune => self.fortune = template,
+            NftFamily::Medal => self.medal = template,
+            NftFamily::Amulet => self.amulet = template,
+            NftFamily::WishTower => self.wish_tower = template,
+            NftFamily::Buddha => self.buddha = template,
+            NftFamily::ResolvedFortune => self.resolved_fortune = template,
+            NftFamily::FortuneSet => self.fortune_set = template,
+        }
+    }
+
+    /// Templates matching the metadata minted before the config existed, except
+    /// Wish Tower names, shortened to fit the 32-byte limit at high wish counts
+    pub fn default_template(family: NftFamily) -> NftMetadataTemplate {
+        let template = |name: &str, symbol: &str, uri: &str, fee: u16, variants: &[&str]| {
+            NftMetadataTemplate {
+                name_template: name.to_string(),
+                symbol: symbol.to_string(),
+                uri_template: uri.to_string(),
+                seller_fee_basis_points: fee,
+                variant_uris: variants.iter().map(|v| v.to_string()).collect(),
+            }
+        };
+
+        match family {
+            // Fortune tiers carry their own URI
+            NftFamily::Fortune => template("Fortune NFT - {name}", "TMF", "{variant}", 0, &[]),
+            NftFamily::Medal => template(
+                "{name} #{serial}",
+                "TMM",
+                "https://solji.mypinata.cloud/ipfs/{variant}",
+                0,
+                &[
+                    "QmPmFctW8BUPSvebp44rQaVTSmV8Lapd4axVoRsifzA24F",
+                    "QmSH2M4KFCfTLS4MgzXMh83tLoFqwzfY3GR6YyXrnkNRxN",
+                    "QmQSL1ERzKoDUMeudSePCscU8yxm4WVjMCobLjKyus1dcN",
+                    "Qmf1SHiMwMP7rycyzrGoFFV4iVW9F41tPbaBFc8ndoWPPq",
+                ],
+            ),
+            NftFamily::Amulet => template(
+                "{name} Amulet #{serial}",
+                "AMULET",
+                "https://solji.mypinata.cloud/ipfs/QmYhnX7HCKQEPuq5mtTApaL4ccqxCrL1Pcawyvv2ReYc8Q",
+                500,
+                &[],
+            ),
+            // Was "Wish Tower ({count} wishes, Level {level})"
+            NftFamily::WishTower => template(
+                "Wish Tower Lv{level} ({count})",
+                "WISH_TOWER",
+                "https://solji.mypinata.cloud/ipfs/QmUrEHCc9PH6EEMg22RVigx4X5QF7A6bNDBrHjPZVq6SJb",
+                0,
+                &[],
+            ),
+            NftFamily::Buddha => template(
+                "Buddha NFT #{serial}",
+                "MTK",
+                "https://solji.mypinata.cloud/ipfs/QmYHsbSuCpSUMksQP89VHawf21iJ7K6UE899uBrUmJgbVg",
+                0,
+                &[],
+            ),
+            // Resolved bad fortunes keep their tier URI unless an art URI is configured
+            NftFamily::ResolvedFortune => template("Resolved - {name}", "TMF", "{variant}", 0, &[]),
+            NftFamily::FortuneSet => template(
+                "Complete Fortune",
+                "TMF",
+                "https://solji.mypinata.cloud/ipfs/QmeYUSLgMKYL8128quaieDUXfbdeKVsGBRQLmWjgAsqw2y",
+                0,
+                &[],
+            ),
+        }
+    }
+
+    pub fn initialize(&mut self, temple_config: Pubkey, bump: u8) {
+        self.temple_config = temple_config;
+        self.fortune = Self::default_template(NftFamily::Fortune);
+        self.medal = Self::default_template(NftFamily::Medal);
+        self.amulet = Self::default_template(NftFamily::Amulet);
+        self.wish_tower = Self::default_template(NftFamily::WishTower);
+        self.buddha = Self::default_template(NftFamily::Buddha);
+        self.resolved_fortune = Self::default_template(NftFamily::ResolvedFortune);
+        self.fortune_set = Self::default_template(NftFamily::FortuneSet);
+        self.bump = bump;
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Local stand-in for a Pyth SOL/USD price update account (localnet only)
+#[account]
+#[derive(InitSpace)]
+pub struct MockPriceFeed {
+    pub authority: Pubkey, // Account creator
+    pub price: i64,        // Price, scaled by 10^exponent
+    pub conf: u64,         // Confidence interval, same scale as price
+    pub exponent: i32,     // Price exponent
+    pub publish_time: i64, // Time the price was set
+    pub bump: u8,
+}
+
+impl MockPriceFeed {
+    pub const SEED_PREFIX: &str = "mock_price_feed";
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+// Local stand-in for a Switchboard randomness account (localnet only)
+#[account]
+#[derive(InitSpace)]
+pub struct MockRandomness {
+    pub authority: Pubkey, // Account creator
+    pub seed_slot: u64,    // Commit slot
+    pub reveal_slot: u64,  // Reveal slot, 0 until revealed
+    pub value: [u8; 32],   // Revealed value
+    pub bump: u8,
+}
+
+impl MockRandomness {
+    pub const SEED_PREFIX: &str = "mock_randomness";
+}
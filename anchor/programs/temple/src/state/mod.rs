@@ -1,14 +1,31 @@
+pub mod altar;
 pub mod amulet;
 pub mod buddha_nft;
+pub mod dedication_pool;
 pub mod event;
+pub mod fortune_history;
 pub mod fortune_nft;
+pub mod fortune_set_claim;
+pub mod fortune_table;
 pub mod global_stats;
 pub mod incense_nft;
+pub mod legacy;
 pub mod medal_nft;
+pub mod metadata_config;
+#[cfg(feature = "localnet")]
+pub mod mock_price_feed;
+#[cfg(feature = "localnet")]
+pub mod mock_randomness;
+pub mod pending_amulets;
+pub mod pending_fortune;
+pub mod pending_reroll;
+pub mod share_record;
 pub mod shop_config;
 pub mod shop_item;
 pub mod temple_config;
+pub mod user_incense_inventory;
 pub mod user_state;
+pub mod user_streak;
 pub mod wish;
 pub mod wish_tower;
 pub mod wish_tower_nft;
@@ -0,0 +1,63 @@
+use crate::error::ErrorCode;
+use crate::state::amulet::AmuletType;
+use anchor_lang::prelude::*;
+
+// Amulets a user has earned but not minted yet
+#[account]
+#[derive(InitSpace)]
+pub struct PendingAmulets {
+    pub user: Pubkey,
+    pub fortune: u32,    // Pending Fortune Amulets
+    pub protection: u32, // Pending Protection Amulets
+    pub merit: u32,      // Pending Merit Amulets
+    pub total_credited: u32,
+    pub total_minted: u32,
+    pub bump: u8,
+}
+
+impl PendingAmulets {
+    pub const SEED_PREFIX: &str = "pending_amulets";
+
+    // Set owner on first use (account is created with init_if_needed)
+    pub fn ensure_initialized(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    fn slot_mut(&mut self, amulet_type: AmuletType) -> &mut u32 {
+        match amulet_type {
+            AmuletType::Fortune => &mut self.fortune,
+            AmuletType::Protection => &mut self.protection,
+            AmuletType::Merit => &mut self.merit,
+        }
+    }
+
+    // Get pending count of specified amulet type
+    pub fn get_pending(&self, amulet_type: AmuletType) -> u32 {
+        match amulet_type {
+            AmuletType::Fortune => self.fortune,
+            AmuletType::Protection => self.protection,
+            AmuletType::Merit => self.merit,
+        }
+    }
+
+    // Credit amulets when a drop or purchase happens
+    pub fn credit(&mut self, amulet_type: AmuletType, amount: u32) {
+        let slot = self.slot_mut(amulet_type);
+        *slot = slot.saturating_add(amount);
+        self.total_credited = self.total_credited.saturating_add(amount);
+    }
+
+    // Debit one amulet when it is minted
+    pub fn debit(&mut self, amulet_type: AmuletType) -> Result<()> {
+        let slot = self.slot_mut(amulet_type);
+        if *slot == 0 {
+            return err!(ErrorCode::InsufficientPendingAmulets);
+        }
+        *slot -= 1;
+        self.total_minted = self.total_minted.saturating_add(1);
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::state::fortune_nft::FortuneResult;
+use anchor_lang::prelude::*;
+
+// Draw kept without minting, claimable as a Fortune NFT through claim_fortune_nft
+#[account]
+#[derive(InitSpace)]
+pub struct PendingFortune {
+    pub user: Pubkey,
+    pub draw_index: u32, // Total draws before this one, seeds this record and its NFT
+    pub serial_number: u32, // Serial the Fortune NFT is minted under
+    pub tier_index: u8,  // Index in the fortune table
+    pub fortune_result: FortuneResult, // Category of the drawn tier
+    pub drawn_at: i64,   // Draw time
+    pub merit_cost: u8,  // Merit spent, 0 for free draws
+    pub bump: u8,
+    #[max_len(18)]
+    pub tier_name: String, // Tier name at draw time, shown on the claimed NFT
+    #[max_len(96)]
+    pub tier_uri: String, // Tier URI at draw time
+}
+
+impl PendingFortune {
+    pub const SEED_PREFIX: &str = "pending_fortune";
+}
@@ -0,0 +1,36 @@
+use crate::error::ErrorCode;
+use crate::state::user_state::PendingDraw;
+use anchor_lang::prelude::*;
+
+// Reroll paid for by request_reroll, applied by settle_reroll once its randomness is revealed
+#[account]
+#[derive(InitSpace)]
+pub struct PendingReroll {
+    pub user: Pubkey,
+    pub draw_index: u32,            // Draw whose Fortune NFT is rerolled
+    pub randomness_account: Pubkey, // Randomness account bound by the request
+    pub commit_slot: u64,           // Randomness commit (seed) slot
+    pub merit_cost: u64,            // Merit paid up front
+    pub bump: u8,
+}
+
+impl PendingReroll {
+    pub const SEED_PREFIX: &str = "pending_reroll";
+
+    /// Fail while an unexpired reroll targets `draw_index`. The PDA is passed unchecked,
+    /// so users without a reroll can't skip the check by leaving the account out
+    pub fn ensure_not_pending(account: &AccountInfo, draw_index: u32, slot: u64) -> Result<()> {
+        if account.owner != &crate::ID || account.data_is_empty() {
+            return Ok(());
+        }
+        let pending = PendingReroll::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        let expires_at = pending
+            .commit_slot
+            .saturating_add(PendingDraw::EXPIRY_SLOTS);
+        require!(
+            pending.draw_index != draw_index || slot > expires_at,
+            ErrorCode::RerollPending
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+// Share of one draw, created once so a draw can only be shared once
+#[account]
+#[derive(InitSpace)]
+pub struct ShareRecord {
+    pub user: Pubkey,
+    pub serial_number: u32,   // Serial number of the shared draw
+    pub share_hash: [u8; 32], // Hash of the off-chain share
+    pub shared_at: i64,
+    pub attested: bool, // Co-signed by the share attester
+    pub bump: u8,
+}
+
+impl ShareRecord {
+    pub const SEED_PREFIX: &str = "share_record";
+
+    /// Message the share attester signs: user, serial number (LE) and share hash
+    pub fn attestation_message(
+        user: &Pubkey,
+        serial_number: u32,
+        share_hash: &[u8; 32],
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(32 + 4 + 32);
+        message.extend_from_slice(user.as_ref());
+        message.extend_from_slice(&serial_number.to_le_bytes());
+        message.extend_from_slice(share_hash);
+        message
+    }
+}
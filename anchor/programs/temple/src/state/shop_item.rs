@@ -1,3 +1,4 @@
+use crate::price::{resolve_lamports, SolUsdPrice};
 use anchor_lang::prelude::*;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
@@ -13,7 +14,8 @@ pub struct ShopItemInfo {
     pub id: u8,
     pub name: String,
     pub description: String,
-    pub price: u64, // lamports
+    pub price: u64,                   // lamports
+    pub price_usd_cents: Option<u64>, // USD price, converted through the SOL/USD feed when set
     pub item_type: ShopItemType,
     pub stock: u64,
     pub is_available: bool,
@@ -27,7 +29,8 @@ pub struct ShopItem {
     pub name: String,
     #[max_len(256)]
     pub description: String,
-    pub price: u64, // lamports
+    pub price: u64,                   // lamports
+    pub price_usd_cents: Option<u64>, // USD price, converted through the SOL/USD feed when set
     pub item_type: ShopItemType,
     pub stock: u64,
     pub is_available: bool,
@@ -57,6 +60,7 @@ impl ShopItem {
             name,
             description,
             price,
+            price_usd_cents: None,
             item_type,
             stock,
             is_available: true,
@@ -78,6 +82,7 @@ impl ShopItem {
             name,
             description,
             price,
+            price_usd_cents: None,
             item_type: ShopItemType::Incense,
             stock,
             is_available: true,
@@ -100,6 +105,11 @@ impl ShopItem {
         Ok(())
     }
 
+    /// Unit price in lamports, USD prices need the SOL/USD price
+    pub fn unit_price_lamports(&self, sol_usd: Option<&SolUsdPrice>) -> Result<u64> {
+        resolve_lamports(self.price, self.price_usd_cents, sol_usd)
+    }
+
     pub fn get_total_price(&self, quantity: u64) -> Result<u64> {
         self.price
             .checked_mul(quantity)
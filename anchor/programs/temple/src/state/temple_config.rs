@@ -9,9 +9,13 @@ pub enum TempleStatusBitIndex {
     MintNFT = 5,
 }
 
+use crate::error::ErrorCode;
+use crate::price::{resolve_lamports, SolUsdPrice};
 use crate::state::global_stats::GlobalStats;
 use crate::state::shop_item::{ShopItem, ShopItemType};
+use crate::state::user_state::{UserDonationState, UserTitle};
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
 // ===== Core dynamic configuration =====
 
@@ -22,9 +26,80 @@ pub struct IncenseType {
     #[max_len(10)]
     pub name: String, // Name
     pub price_lamports: u64, // Price per incense stick
+    pub price_usd_cents: Option<u64>, // USD price, converted through the SOL/USD feed when set
     pub merit: u64, // Merit value
     pub incense_points: u64, // Incense points
     pub is_donation: bool, // Whether it's donation incense
+    pub daily_cap: u8, // Max burns per day, 0 disables
+    pub cooldown_seconds: u32, // Min seconds between burns of this type, 0 disables
+    pub burn_duration_seconds: u32, // How long a stick stays lit on the altar
+    pub holder_cap: Option<HolderCap>, // Cap replacing daily_cap for qualifying holders
+}
+
+// 1a. Holder requirement for a special daily cap
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace, PartialEq, Eq)]
+pub enum CapRequirement {
+    Title(UserTitle), // Title at or above this rank
+    MedalLevel(u8),   // Medal NFT at or above this level
+}
+
+// 1b. Daily cap applying only to holders meeting the requirement
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct HolderCap {
+    pub requirement: CapRequirement,
+    pub daily_cap: u8,
+}
+
+impl IncenseType {
+    /// Longest allowed cooldown
+    pub const MAX_COOLDOWN_SECONDS: u32 = 86400;
+    /// Longest a stick may stay lit on the altar
+    pub const MAX_BURN_DURATION_SECONDS: u32 = 86400;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.id > 0, ErrorCode::InvalidIncenseType);
+        require!(!self.name.is_empty(), ErrorCode::InvalidIncenseType);
+        require!(self.price_lamports > 0, ErrorCode::InvalidIncenseType);
+        require!(
+            self.price_usd_cents != Some(0),
+            ErrorCode::InvalidIncenseType
+        );
+        require!(
+            self.cooldown_seconds <= Self::MAX_COOLDOWN_SECONDS,
+            ErrorCode::InvalidIncenseType
+        );
+        require!(
+            self.burn_duration_seconds > 0
+                && self.burn_duration_seconds <= Self::MAX_BURN_DURATION_SECONDS,
+            ErrorCode::InvalidIncenseType
+        );
+        if let Some(HolderCap {
+            requirement: CapRequirement::MedalLevel(level),
+            ..
+        }) = self.holder_cap
+        {
+            require!((1..=4).contains(&level), ErrorCode::InvalidIncenseType);
+        }
+        Ok(())
+    }
+
+    /// Daily cap for a user with this title and medal level (0 without a medal), 0 is uncapped
+    pub fn daily_cap_for(&self, title: &UserTitle, medal_level: u8) -> u8 {
+        match &self.holder_cap {
+            Some(holder_cap) => {
+                let qualifies = match &holder_cap.requirement {
+                    CapRequirement::Title(required) => title >= required,
+                    CapRequirement::MedalLevel(required) => medal_level >= *required,
+                };
+                if qualifies {
+                    holder_cap.daily_cap
+                } else {
+                    self.daily_cap
+                }
+            }
+            None => self.daily_cap,
+        }
+    }
 }
 
 // 2. Fortune drawing configuration
@@ -40,10 +115,11 @@ pub struct FortuneConfig {
 // 3. Donation level configuration
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
 pub struct DonationLevelConfig {
-    pub level: u8,           // Level (1-4)
-    pub min_amount_sol: f64, // Minimum amount (SOL)
-    pub merit_reward: u64,   // Merit reward
-    pub incense_reward: u64, // Incense reward
+    pub level: u8,                         // Level (1-4)
+    pub min_amount_sol: f64,               // Minimum amount (SOL)
+    pub min_amount_usd_cents: Option<u64>, // USD threshold, converted through the SOL/USD feed when set
+    pub merit_reward: u64,                 // Merit reward
+    pub incense_reward: u64,               // Incense reward
 }
 
 // 4. Donation reward configuration
@@ -80,15 +156,349 @@ pub struct TempleLevelConfig {
     pub required_amulets: u64,        // Required amulets
 }
 
+// 7. Burning streak multiplier, the highest reached tier applies
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct StreakMultiplier {
+    pub min_days: u32,      // Streak length reaching this tier
+    pub bonus_percent: u16, // Extra merit (percent)
+}
+
+impl StreakMultiplier {
+    pub const MAX_TIERS: usize = 5;
+    pub const MAX_BONUS_PERCENT: u16 = 100;
+
+    /// Default tiers: 7 days +10%, 30 days +25%
+    pub fn default_tiers() -> Vec<StreakMultiplier> {
+        vec![
+            StreakMultiplier {
+                min_days: 7,
+                bonus_percent: 10,
+            },
+            StreakMultiplier {
+                min_days: 30,
+                bonus_percent: 25,
+            },
+        ]
+    }
+}
+
+// 8a. Price of one incense stick or shop item in a payment mint
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct MintPrice {
+    pub id: u8,      // Incense type ID or shop item ID
+    pub amount: u64, // Price in base units of the mint
+}
+
+// 8. Whitelisted SPL token accepted for payments
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct PaymentMint {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub enabled: bool,
+    pub donation_lamports_per_token: u64, // SOL value of one whole token, for donation levels and rewards
+    #[max_len(10)]
+    pub incense_prices: Vec<MintPrice>,
+    #[max_len(10)]
+    pub item_prices: Vec<MintPrice>,
+}
+
+impl PaymentMint {
+    pub const MAX_MINTS: usize = 4;
+    pub const MAX_PRICES: usize = 10;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.incense_prices.len() <= Self::MAX_PRICES
+                && self.item_prices.len() <= Self::MAX_PRICES,
+            ErrorCode::InvalidPaymentMintConfig
+        );
+        for prices in [&self.incense_prices, &self.item_prices] {
+            for (i, price) in prices.iter().enumerate() {
+                require!(price.amount > 0, ErrorCode::InvalidPaymentMintConfig);
+                require!(
+                    prices[..i].iter().all(|other| other.id != price.id),
+                    ErrorCode::InvalidPaymentMintConfig
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Price of one stick of an incense type
+    pub fn incense_price(&self, incense_id: u8) -> Option<u64> {
+        self.incense_prices
+            .iter()
+            .find(|price| price.id == incense_id)
+            .map(|price| price.amount)
+    }
+
+    /// Price of one shop item
+    pub fn item_price(&self, item_id: u8) -> Option<u64> {
+        self.item_prices
+            .iter()
+            .find(|price| price.id == item_id)
+            .map(|price| price.amount)
+    }
+
+    /// SOL value of a token amount, in lamports
+    pub fn to_donation_lamports(&self, amount: u64) -> Result<u64> {
+        let lamports = amount as u128 * self.donation_lamports_per_token as u128
+            / 10u128.pow(self.decimals as u32);
+        u64::try_from(lamports).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
+// 9a. Activity rates replaced for users at or above a title
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct TitleEconomyOverride {
+    pub title: UserTitle,                // Applies at or above this title
+    pub free_draws_per_day: Option<u8>,  // Replaces free_draws_per_day
+    pub draw_merit_cost: Option<u64>,    // Replaces draw_merit_cost
+    pub share_merit_reward: Option<u64>, // Replaces share_merit_reward
+    pub free_wishes_per_day: Option<u8>, // Replaces free_wishes_per_day
+    pub wish_merit_cost: Option<u64>,    // Replaces wish_merit_cost
+    pub wish_merit_reward: Option<u64>,  // Replaces wish_merit_reward
+}
+
+// 9. Draw, share and wish pricing and rewards
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct ActivityEconomyConfig {
+    pub free_draws_per_day: u8,  // Free fortune draws per day
+    pub draw_merit_cost: u64,    // Merit paid for a draw beyond the free ones
+    pub share_merit_reward: u64, // Merit for sharing a fortune
+    pub free_wishes_per_day: u8, // Free wishes per day
+    pub wish_merit_cost: u64,    // Merit paid for a wish beyond the free ones
+    pub wish_merit_reward: u64,  // Merit for a free wish
+    #[max_len(5)]
+    pub title_overrides: Vec<TitleEconomyOverride>,
+    pub share_attester: Option<Pubkey>, // Shares only count when co-signed by this key
+}
+
+// Activity rates resolved for one user
+#[derive(Clone, Copy, Debug)]
+pub struct ActivityRates {
+    pub free_draws_per_day: u8,
+    pub draw_merit_cost: u64,
+    pub share_merit_reward: u64,
+    pub free_wishes_per_day: u8,
+    pub wish_merit_cost: u64,
+    pub wish_merit_reward: u64,
+}
+
+impl Default for ActivityEconomyConfig {
+    /// One free draw, 5 merit per extra draw, +1 merit per share,
+    /// three free wishes, 5 merit per extra wish, +1 merit per free wish
+    fn default() -> Self {
+        Self {
+            free_draws_per_day: 1,
+            draw_merit_cost: 5,
+            share_merit_reward: 1,
+            free_wishes_per_day: 3,
+            wish_merit_cost: 5,
+            wish_merit_reward: 1,
+            title_overrides: vec![],
+            share_attester: None,
+        }
+    }
+}
+
+impl ActivityEconomyConfig {
+    pub const MAX_TITLE_OVERRIDES: usize = 5;
+    /// Fortune NFTs record the draw cost in a u8
+    pub const MAX_DRAW_MERIT_COST: u64 = u8::MAX as u64;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.draw_merit_cost <= Self::MAX_DRAW_MERIT_COST,
+            ErrorCode::InvalidActivityEconomyConfig
+        );
+        require!(
+            self.title_overrides.len() <= Self::MAX_TITLE_OVERRIDES,
+            ErrorCode::InvalidActivityEconomyConfig
+        );
+        for (i, title_override) in self.title_overrides.iter().enumerate() {
+            require!(
+                !matches!(title_override.draw_merit_cost, Some(cost) if cost > Self::MAX_DRAW_MERIT_COST),
+                ErrorCode::InvalidActivityEconomyConfig
+            );
+            require!(
+                self.title_overrides[..i]
+                    .iter()
+                    .all(|other| other.title != title_override.title),
+                ErrorCode::InvalidActivityEconomyConfig
+            );
+        }
+        Ok(())
+    }
+
+    /// Rates for a title, the override with the highest title reached applies
+    pub fn rates_for(&self, title: &UserTitle) -> ActivityRates {
+        let mut rates = ActivityRates {
+            free_draws_per_day: self.free_draws_per_day,
+            draw_merit_cost: self.draw_merit_cost,
+            share_merit_reward: self.share_merit_reward,
+            free_wishes_per_day: self.free_wishes_per_day,
+            wish_merit_cost: self.wish_merit_cost,
+            wish_merit_reward: self.wish_merit_reward,
+        };
+
+        if let Some(o) = self
+            .title_overrides
+            .iter()
+            .filter(|o| *title >= o.title)
+            .max_by_key(|o| o.title)
+        {
+            rates.free_draws_per_day = o.free_draws_per_day.unwrap_or(rates.free_draws_per_day);
+            rates.draw_merit_cost = o.draw_merit_cost.unwrap_or(rates.draw_merit_cost);
+            rates.share_merit_reward = o.share_merit_reward.unwrap_or(rates.share_merit_reward);
+            rates.free_wishes_per_day = o.free_wishes_per_day.unwrap_or(rates.free_wishes_per_day);
+            rates.wish_merit_cost = o.wish_merit_cost.unwrap_or(rates.wish_merit_cost);
+            rates.wish_merit_reward = o.wish_merit_reward.unwrap_or(rates.wish_merit_reward);
+        }
+        rates
+    }
+}
+
+// 10. Resolving bad fortunes (化解)
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct FortuneResolveConfig {
+    pub window_seconds: u32, // Time after minting a bad fortune can be resolved, 0 disables
+    pub incense_id: Option<u8>, // Incense type burned from the inventory to resolve
+    pub incense_amount: u8,  // Sticks burned per resolve
+    pub merit_cost: Option<u64>, // Merit spent to resolve instead of incense
+    pub merit_reward: u64,   // Merit granted once resolved
+}
+
+// How a resolve ritual is paid
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+pub enum ResolvePayment {
+    Incense,
+    Merit,
+}
+
+impl Default for FortuneResolveConfig {
+    /// One day to resolve by burning one Fresh incense or spending 10 merit, +1 merit reward
+    fn default() -> Self {
+        Self {
+            window_seconds: 24 * 60 * 60,
+            incense_id: Some(1),
+            incense_amount: 1,
+            merit_cost: Some(10),
+            merit_reward: 1,
+        }
+    }
+}
+
+impl FortuneResolveConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.incense_id.is_none() || self.incense_amount > 0,
+            ErrorCode::InvalidFortuneResolveConfig
+        );
+        Ok(())
+    }
+}
+
+// 11. Merit-funded fortune rerolls
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct FortuneRerollConfig {
+    pub base_merit_cost: u64,    // Merit for the first reroll of the day
+    pub merit_cost_step: u64,    // Added for every earlier reroll that day
+    pub max_rerolls_per_day: u8, // Daily reroll limit, 0 disables rerolls
+}
+
+impl Default for FortuneRerollConfig {
+    /// Three rerolls a day costing 10, 20 and 30 merit
+    fn default() -> Self {
+        Self {
+            base_merit_cost: 10,
+            merit_cost_step: 10,
+            max_rerolls_per_day: 3,
+        }
+    }
+}
+
+impl FortuneRerollConfig {
+    /// Merit for a reroll after `rerolls_today` earlier ones
+    pub fn merit_cost(&self, rerolls_today: u8) -> Result<u64> {
+        self.merit_cost_step
+            .checked_mul(rerolls_today as u64)
+            .and_then(|step| step.checked_add(self.base_merit_cost))
+            .ok_or(error!(ErrorCode::MathOverflow))
+    }
+}
+
+// 12. Fortune set completion
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct FortuneSetConfig {
+    pub merit_reward: u64, // Merit granted with the Complete Fortune NFT
+    pub burn_nfts: bool,   // Burn the set's Fortune NFTs, else freeze them in place
+}
+
+impl Default for FortuneSetConfig {
+    /// 100 merit, the set stays in the wallet frozen
+    fn default() -> Self {
+        Self {
+            merit_reward: 100,
+            burn_nfts: false,
+        }
+    }
+}
+
+// 13. Releasing unwanted fortunes
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct FortuneReleaseConfig {
+    pub merit_reward: u64, // Merit for releasing a Fortune NFT
+}
+
+impl Default for FortuneReleaseConfig {
+    /// 1 merit per released slip
+    fn default() -> Self {
+        Self { merit_reward: 1 }
+    }
+}
+
+// 14. Dedicating burns to others (回向)
+#[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
+pub struct DedicationConfig {
+    pub giver_share_percent: u8, // Share of a dedicated burn's merit kept by the giver
+}
+
+impl Default for DedicationConfig {
+    /// The giver keeps 10%, the beneficiary receives 90%
+    fn default() -> Self {
+        Self {
+            giver_share_percent: 10,
+        }
+    }
+}
+
+impl DedicationConfig {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.giver_share_percent <= 100,
+            ErrorCode::InvalidDedicationConfig
+        );
+        Ok(())
+    }
+
+    /// Split a dedicated burn's merit into (giver, beneficiary) shares
+    pub fn split_merit(&self, merit: u64) -> Result<(u64, u64)> {
+        let giver_merit = merit
+            .checked_mul(u64::from(self.giver_share_percent))
+            .ok_or(error!(ErrorCode::MathOverflow))?
+            / 100;
+        Ok((giver_merit, merit - giver_merit))
+    }
+}
+
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Debug, InitSpace)]
 pub struct DynamicConfig {
     // 1. Incense type configuration
     #[max_len(10)]
     pub incense_types: Vec<IncenseType>,
 
-    // 2. Fortune drawing configuration
-    pub regular_fortune: FortuneConfig, // Regular user probability
-    pub buddha_fortune: FortuneConfig,  // Buddha NFT holder probability
+    // 2. Fortune table, moved to the FortuneTable account
 
     // 3. Donation level configuration
     #[max_len(4)]
@@ -105,6 +515,32 @@ pub struct DynamicConfig {
     // 6. Special incense types
     #[max_len(2)]
     pub special_incense_types: Vec<SpecialIncenseType>,
+
+    // 7. Burning streak multipliers
+    #[max_len(5)]
+    pub streak_multipliers: Vec<StreakMultiplier>,
+
+    // 8. Payment mints
+    #[max_len(4)]
+    pub payment_mints: Vec<PaymentMint>,
+
+    // 9. Draw, share and wish economy
+    pub activity_economy: ActivityEconomyConfig,
+
+    // 10. Resolving bad fortunes
+    pub fortune_resolve: FortuneResolveConfig,
+
+    // 11. Fortune rerolls
+    pub fortune_reroll: FortuneRerollConfig,
+
+    // 12. Fortune set completion
+    pub fortune_set: FortuneSetConfig,
+
+    // 13. Releasing unwanted fortunes
+    pub fortune_release: FortuneReleaseConfig,
+
+    // 14. Dedicating burns to others
+    pub dedication: DedicationConfig,
 }
 
 // Temple config - main account, responsible for configuration and core status
@@ -127,12 +563,85 @@ pub struct TempleConfig {
     pub open_time: u64, // Launch timestamp
     pub donation_deadline: u64, // Donation deadline timestamp, for Buddha NFT distribution
 
+    // Temple calendar, daily counters reset at the local day boundary
+    pub utc_offset_minutes: i16, // Temple timezone offset from UTC
+    pub day_boundary_hour: u8,   // Local hour the temple day starts
+
     // All configurations are placed in dynamic config
     pub dynamic_config: DynamicConfig,
 }
 
 impl TempleConfig {
     pub const SEED_PREFIX: &str = "temple_v1";
+    pub const SECONDS_PER_DAY: i64 = 86400;
+    pub const DEFAULT_UTC_OFFSET_MINUTES: i16 = 8 * 60; // UTC+8
+    pub const MIN_UTC_OFFSET_MINUTES: i16 = -12 * 60;
+    pub const MAX_UTC_OFFSET_MINUTES: i16 = 14 * 60;
+
+    /// Temple calendar day containing `timestamp`
+    pub fn day_index(&self, timestamp: i64) -> i64 {
+        let local_time =
+            timestamp + self.utc_offset_minutes as i64 * 60 - self.day_boundary_hour as i64 * 3600;
+        local_time.div_euclid(Self::SECONDS_PER_DAY)
+    }
+
+    /// Whether two timestamps fall on the same temple day
+    pub fn is_same_day(&self, a: i64, b: i64) -> bool {
+        self.day_index(a) == self.day_index(b)
+    }
+
+    /// Whitelisted payment mint
+    pub fn find_payment_mint(&self, mint: &Pubkey) -> Option<&PaymentMint> {
+        self.dynamic_config
+            .payment_mints
+            .iter()
+            .find(|payment_mint| payment_mint.mint == *mint)
+    }
+
+    /// Grow the account when the config outgrows its allocation, the payer funds the extra rent
+    pub fn grow_to_fit<'info>(
+        temple_config: &Account<'info, Self>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let required_space = 8 + borsh::to_vec(&**temple_config)?.len();
+        let temple_config_info = temple_config.to_account_info();
+        if required_space > temple_config_info.data_len() {
+            let rent_due = Rent::get()?
+                .minimum_balance(required_space)
+                .saturating_sub(temple_config_info.lamports());
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: temple_config_info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            temple_config_info.resize(required_space)?;
+        }
+        Ok(())
+    }
+
+    /// Draw, share and wish rates for a user's title
+    pub fn activity_rates(&self, title: &UserTitle) -> ActivityRates {
+        self.dynamic_config.activity_economy.rates_for(title)
+    }
+
+    /// Merit bonus (percent) for a burning streak of `streak` days
+    pub fn streak_bonus_percent(&self, streak: u32) -> u16 {
+        self.dynamic_config
+            .streak_multipliers
+            .iter()
+            .filter(|tier| streak >= tier.min_days)
+            .map(|tier| tier.bonus_percent)
+            .max()
+            .unwrap_or(0)
+    }
 
     // Get incense type
     pub fn find_incense_type(&self, id: u8) -> Option<&IncenseType> {
@@ -150,19 +659,15 @@ impl TempleConfig {
             .find(|t| t.id == id)
     }
 
-    // Get incense price
-    pub fn get_fee_per_incense(&self, incense_id: u8) -> u64 {
-        self.find_incense_type(incense_id)
-            .map(|t: &IncenseType| t.price_lamports)
-            .unwrap_or(0)
-    }
-
-    // Get fortune probability configuration
-    pub fn get_fortune_config(&self, has_buddha_nft: bool) -> &FortuneConfig {
-        if has_buddha_nft {
-            &self.dynamic_config.buddha_fortune
-        } else {
-            &self.dynamic_config.regular_fortune
+    // Get incense price in lamports, USD prices need the SOL/USD price
+    pub fn get_fee_per_incense(
+        &self,
+        incense_id: u8,
+        sol_usd: Option<&SolUsdPrice>,
+    ) -> Result<u64> {
+        match self.find_incense_type(incense_id) {
+            Some(t) => resolve_lamports(t.price_lamports, t.price_usd_cents, sol_usd),
+            None => Ok(0),
         }
     }
 
@@ -174,6 +679,21 @@ impl TempleConfig {
             .find(|d| d.level == level)
     }
 
+    // Donation level thresholds in lamports, USD thresholds need the SOL/USD price
+    pub fn donation_level_thresholds(
+        &self,
+        sol_usd: Option<&SolUsdPrice>,
+    ) -> Result<[u64; UserDonationState::MAX_DONATION_LEVEL as usize]> {
+        let mut thresholds = UserDonationState::DEFAULT_LEVEL_THRESHOLDS;
+        for (index, threshold) in thresholds.iter_mut().enumerate() {
+            if let Some(level_config) = self.get_donation_level_config(index as u8 + 1) {
+                *threshold =
+                    resolve_lamports(*threshold, level_config.min_amount_usd_cents, sol_usd)?;
+            }
+        }
+        Ok(thresholds)
+    }
+
     // Check if incense type exists
     pub fn is_incense_available(&self, incense_id: u8) -> bool {
         self.find_incense_type(incense_id).is_some()
@@ -0,0 +1,167 @@
+use crate::error::ErrorCode;
+use crate::state::user_state::UserIncenseState;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+// Holdings and daily burns of one incense type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct IncenseSlot {
+    pub incense_id: u8,
+    pub balance: u64,        // Prepaid incense balance
+    pub daily_count: u8,     // Burns today
+    pub last_burn_time: i64, // Last burn of this type, for cooldowns
+}
+
+// Per-user incense inventory, one slot per incense type, grown as new types show up
+#[account]
+#[derive(InitSpace)]
+pub struct UserIncenseInventory {
+    pub user: Pubkey,
+    pub bump: u8,
+    #[max_len(6)]
+    pub slots: Vec<IncenseSlot>, // Initial allocation, resized past six types
+}
+
+impl UserIncenseInventory {
+    pub const SEED_PREFIX: &str = "user_incense_inventory";
+    pub const MAX_SLOTS: usize = 32;
+
+    // Set owner on first use (account may be created with init_if_needed)
+    pub fn ensure_initialized(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    fn slot(&self, incense_id: u8) -> Option<&IncenseSlot> {
+        self.slots.iter().find(|slot| slot.incense_id == incense_id)
+    }
+
+    // Slot of an incense type, added on first use
+    fn slot_mut(&mut self, incense_id: u8) -> Result<&mut IncenseSlot> {
+        let index = match self
+            .slots
+            .iter()
+            .position(|slot| slot.incense_id == incense_id)
+        {
+            Some(index) => index,
+            None => {
+                require!(
+                    self.slots.len() < Self::MAX_SLOTS,
+                    ErrorCode::IncenseInventoryFull
+                );
+                self.slots.push(IncenseSlot {
+                    incense_id,
+                    balance: 0,
+                    daily_count: 0,
+                    last_burn_time: 0,
+                });
+                self.slots.len() - 1
+            }
+        };
+        Ok(&mut self.slots[index])
+    }
+
+    /// Get balance of specified incense type
+    pub fn get_balance(&self, incense_id: u8) -> u64 {
+        self.slot(incense_id).map_or(0, |slot| slot.balance)
+    }
+
+    /// Add balance of specified incense type
+    pub fn add_balance(&mut self, incense_id: u8, amount: u64) -> Result<()> {
+        let slot = self.slot_mut(incense_id)?;
+        slot.balance = slot.balance.saturating_add(amount);
+        Ok(())
+    }
+
+    /// Subtract balance of specified incense type
+    pub fn subtract_balance(&mut self, incense_id: u8, amount: u64) -> Result<()> {
+        require!(
+            self.get_balance(incense_id) >= amount,
+            ErrorCode::InsufficientIncenseBalance
+        );
+        let slot = self.slot_mut(incense_id)?;
+        slot.balance -= amount;
+        Ok(())
+    }
+
+    /// Get daily incense burn count of specified incense type
+    pub fn get_daily_count(&self, incense_id: u8) -> u8 {
+        self.slot(incense_id).map_or(0, |slot| slot.daily_count)
+    }
+
+    /// Last burn time of specified incense type, if ever burned
+    pub fn get_last_burn_time(&self, incense_id: u8) -> Option<i64> {
+        self.slot(incense_id)
+            .filter(|slot| slot.last_burn_time > 0)
+            .map(|slot| slot.last_burn_time)
+    }
+
+    /// Count a burn against today's count of its type
+    pub fn record_burn(&mut self, incense_id: u8, amount: u8, now: i64) -> Result<()> {
+        let slot = self.slot_mut(incense_id)?;
+        slot.daily_count = slot.daily_count.saturating_add(amount);
+        slot.last_burn_time = now;
+        Ok(())
+    }
+
+    /// Start a new day, keeping balances and cooldowns
+    pub fn reset_daily_counts(&mut self) {
+        for slot in self.slots.iter_mut() {
+            slot.daily_count = 0;
+        }
+    }
+
+    /// Move balances and today's counts left in the legacy six-slot arrays of the incense state.
+    /// Moved slots are cleared, so later calls are no-ops.
+    pub fn absorb_legacy(&mut self, user_incense_state: &mut UserIncenseState) -> Result<()> {
+        for item in user_incense_state.incense_balance.iter_mut() {
+            if item.incense_id != 0 && item.balance > 0 {
+                self.add_balance(item.incense_id, item.balance)?;
+            }
+            item.incense_id = 0;
+            item.balance = 0;
+        }
+
+        // The legacy layout kept no burn times, so no cooldown carries over
+        for item in user_incense_state.daily_incense_count.iter_mut() {
+            if item.incense_id != 0 && item.count > 0 {
+                let slot = self.slot_mut(item.incense_id)?;
+                slot.daily_count = slot.daily_count.saturating_add(item.count);
+            }
+            item.incense_id = 0;
+            item.count = 0;
+        }
+        Ok(())
+    }
+
+    /// Grow the account when its slots outgrow the allocation, the payer funds the extra rent
+    pub fn grow_to_fit<'info>(
+        inventory: &Account<'info, Self>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let required_space = 8 + borsh::to_vec(&**inventory)?.len();
+        let inventory_info = inventory.to_account_info();
+        if required_space > inventory_info.data_len() {
+            let rent_due = Rent::get()?
+                .minimum_balance(required_space)
+                .saturating_sub(inventory_info.lamports());
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: inventory_info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            inventory_info.resize(required_space)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,22 @@
 use crate::error::ErrorCode;
-use crate::state::temple_config::TempleConfig;
+use crate::state::temple_config::{IncenseType, TempleConfig};
+use crate::state::user_incense_inventory::UserIncenseInventory;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
 // User title enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+#[derive(
+    AnchorSerialize,
+    AnchorDeserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    InitSpace,
+)]
 pub enum UserTitle {
     Pilgrim,   // Pilgrim
     Disciple,  // Disciple
@@ -41,6 +54,20 @@ pub struct DailyIncenseCount {
     pub count: u8,
 }
 
+// Draw options paid for by request_fortune, applied by settle_fortune
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PendingDraw {
+    pub commit_slot: u64,        // Randomness commit (seed) slot
+    pub use_merit: bool,         // Paid with merit
+    pub fortune_amulet: bool,    // Fortune amulet consumed
+    pub protection_amulet: bool, // Protection amulet consumed
+}
+
+impl PendingDraw {
+    /// Switchboard can only reveal while the seed slothash is in the SlotHashes sysvar
+    pub const EXPIRY_SLOTS: u64 = 512;
+}
+
 // ==== Account structure definition ends ============
 
 // Main user state account
@@ -53,11 +80,42 @@ pub struct UserState {
     pub bump: u8,
 
     // Random request related
-    pub pending_random_request_id: Option<[u8; 32]>, // Pending random request ID
+    pub pending_random_request_id: Option<[u8; 32]>, // Pending random request ID (randomness account)
+    pub pending_draw: PendingDraw,                   // Draw bound to the pending request
 }
 
 impl UserState {
     pub const SEED_PREFIX: &str = "user_state";
+
+    /// Grow accounts created before pending_draw was added, the payer funds the extra rent.
+    /// Older accounts still decode since they never hold a pending request id.
+    pub fn grow_to_fit<'info>(
+        user_state: &Account<'info, Self>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let required_space = 8 + borsh::to_vec(&**user_state)?.len();
+        let user_state_info = user_state.to_account_info();
+        if required_space > user_state_info.data_len() {
+            let rent_due = Rent::get()?
+                .minimum_balance(required_space)
+                .saturating_sub(user_state_info.lamports());
+            if rent_due > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: payer.clone(),
+                            to: user_state_info.clone(),
+                        },
+                    ),
+                    rent_due,
+                )?;
+            }
+            user_state_info.resize(required_space)?;
+        }
+        Ok(())
+    }
 }
 
 // ===== Split sub-accounts =====
@@ -74,7 +132,7 @@ pub struct UserIncenseState {
     pub update_time: i64,    // Update time
     pub bump: u8,
 
-    // Incense balance and daily count
+    // Legacy incense balance and daily count, moved to UserIncenseInventory by migrate_incense_inventory
     pub incense_balance: [IncenseBalance; 6],
     pub daily_incense_count: [DailyIncenseCount; 6],
 
@@ -104,116 +162,61 @@ pub struct UserDonationState {
 impl UserIncenseState {
     pub const SEED_PREFIX: &str = "user_incense";
 
-    /// Get balance of specified incense type
-    pub fn get_incense_balance(&self, incense_id: u8) -> u64 {
-        self.incense_balance
-            .iter()
-            .find(|item| item.incense_id == incense_id)
-            .map(|item| item.balance)
-            .unwrap_or(0)
-    }
-
-    /// Set balance of specified incense type
-    pub fn set_incense_balance(&mut self, incense_id: u8, balance: u64) {
-        if let Some(item) = self
-            .incense_balance
-            .iter_mut()
-            .find(|item| item.incense_id == incense_id)
-        {
-            item.balance = balance;
-        } else {
-            // Find empty position or replace first empty record
-            for item in self.incense_balance.iter_mut() {
-                if item.incense_id == 0 || item.incense_id == incense_id {
-                    item.incense_id = incense_id;
-                    item.balance = balance;
-                    break;
-                }
-            }
-        }
-    }
-
-    /// Add balance of specified incense type
-    pub fn add_incense_balance(&mut self, incense_id: u8, amount: u64) {
-        let current_balance = self.get_incense_balance(incense_id);
-        self.set_incense_balance(incense_id, current_balance.saturating_add(amount));
-    }
-
-    /// Subtract balance of specified incense type
-    pub fn subtract_incense_balance(&mut self, incense_id: u8, amount: u64) -> Result<()> {
-        let current_balance: u64 = self.get_incense_balance(incense_id);
-        if current_balance < amount {
-            return err!(ErrorCode::InsufficientIncenseBalance);
-        }
-        self.set_incense_balance(incense_id, current_balance - amount);
-        Ok(())
-    }
-
-    /// Get daily incense burn count of specified incense type
-    pub fn get_daily_incense_count(&self, incense_id: u8) -> u8 {
-        self.daily_incense_count
-            .iter()
-            .find(|item| item.incense_id == incense_id)
-            .map(|item| item.count)
-            .unwrap_or(0)
-    }
-
-    /// Set daily incense burn count of specified incense type
-    pub fn set_daily_incense_count(&mut self, incense_id: u8, count: u8) {
-        if let Some(item) = self
-            .daily_incense_count
-            .iter_mut()
-            .find(|item| item.incense_id == incense_id)
-        {
-            item.count = count;
-        } else {
-            // Find empty position or replace first empty record
-            for item in self.daily_incense_count.iter_mut() {
-                if item.incense_id == 0 || item.incense_id == incense_id {
-                    item.incense_id = incense_id;
-                    item.count = count;
-                    break;
-                }
-            }
-        }
-    }
-
-    /// Check if incense amount exceeds limit
-    pub fn check_daily_incense_limit(&self, incense_id: u8, amount: u8) -> Result<()> {
+    /// Check the configured daily cap and cooldown of an incense type
+    pub fn check_daily_incense_limit(
+        &self,
+        inventory: &UserIncenseInventory,
+        incense_type: &IncenseType,
+        amount: u64,
+        medal_level: u8,
+        temple_config: &TempleConfig,
+    ) -> Result<()> {
         // 1. First check if day has changed, reset count if day changed
         let now = Clock::get()?.unix_timestamp;
-        let is_new_day = now - self.update_time >= 86400;
+        let is_new_day = !temple_config.is_same_day(now, self.update_time);
         let current_count = if is_new_day {
             0
         } else {
-            self.get_daily_incense_count(incense_id)
+            inventory.get_daily_count(incense_type.id)
         };
 
-        // 2. Validate count (single incense type daily ≤10)
-        if current_count + amount > 10 {
+        // 2. Validate cooldown since the last burn of this type
+        if incense_type.cooldown_seconds > 0 {
+            if let Some(last_burn_time) = inventory.get_last_burn_time(incense_type.id) {
+                if now - last_burn_time < incense_type.cooldown_seconds as i64 {
+                    return err!(ErrorCode::IncenseOnCooldown);
+                }
+            }
+        }
+
+        // 3. Validate count against the cap for this user
+        let daily_cap = incense_type.daily_cap_for(&self.title, medal_level);
+        if daily_cap > 0 && current_count as u64 + amount > daily_cap as u64 {
             return err!(ErrorCode::ExceedDailyIncenseLimit);
         }
         Ok(())
     }
 
-    /// Update daily incense burn count
-    pub fn update_daily_count(&mut self, incense_id: u8, amount: u8) {
-        let now = Clock::get().unwrap().unix_timestamp;
+    /// Update daily incense burn count, saturating for uncapped types
+    pub fn update_daily_count(
+        &mut self,
+        inventory: &mut UserIncenseInventory,
+        incense_id: u8,
+        amount: u64,
+        temple_config: &TempleConfig,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let amount = u8::try_from(amount).unwrap_or(u8::MAX);
         // Reset all counts if day changed + update reset time
-        let is_new_day = now - self.update_time >= 86400;
+        let is_new_day = !temple_config.is_same_day(now, self.update_time);
         if is_new_day {
-            // Manually reset fixed size array
-            for item in self.daily_incense_count.iter_mut() {
-                item.incense_id = 0;
-                item.count = 0;
-            }
+            inventory.reset_daily_counts();
             self.incense_number = 0;
             self.update_time = now;
         }
         // Accumulate current incense type count
-        self.incense_number += amount;
-        let current_count = self.get_daily_incense_count(incense_id);
-        self.set_daily_incense_count(incense_id, current_count + amount);
+        self.incense_number = self.incense_number.saturating_add(amount);
+        inventory.record_burn(incense_id, amount, now)
     }
 
     // Add user's incense points and merit, and automatically update title
@@ -229,23 +232,29 @@ impl UserIncenseState {
     }
 
     /// Check if can draw fortune for free
-    pub fn can_draw_free(&self) -> bool {
+    pub fn can_draw_free(&self, temple_config: &TempleConfig) -> bool {
         let now = Clock::get().unwrap().unix_timestamp;
-        let is_new_day = now - self.last_draw_time >= 86400; // 24 hours
+        let is_new_day = !temple_config.is_same_day(now, self.last_draw_time);
 
         if is_new_day {
             // New day, reset count
             true
         } else {
             // Same day, check free count
-            self.daily_draw_count < 1
+            self.daily_draw_count < temple_config.activity_rates(&self.title).free_draws_per_day
         }
     }
 
     /// Update draw fortune count
-    pub fn update_draw_count(&mut self) {
+    pub fn update_draw_count(&mut self, temple_config: &TempleConfig) {
+        self.record_daily_draw(temple_config);
+        self.total_draws = self.total_draws.saturating_add(1);
+    }
+
+    /// Count a draw against today's free draw, without advancing total draws
+    pub fn record_daily_draw(&mut self, temple_config: &TempleConfig) {
         let now = Clock::get().unwrap().unix_timestamp;
-        let is_new_day = now - self.last_draw_time >= 86400;
+        let is_new_day = !temple_config.is_same_day(now, self.last_draw_time);
 
         if is_new_day {
             // New day, reset count
@@ -256,7 +265,6 @@ impl UserIncenseState {
         }
 
         self.last_draw_time = now;
-        self.total_draws = self.total_draws.saturating_add(1);
     }
 
     /// Consume merit for extra draw fortune (using dynamic config cost)
@@ -269,9 +277,9 @@ impl UserIncenseState {
     }
 
     /// Update daily wish count
-    pub fn update_wish_count(&mut self) {
+    pub fn update_wish_count(&mut self, temple_config: &TempleConfig) {
         let now = Clock::get().unwrap().unix_timestamp;
-        let is_new_day = now - self.last_wish_time >= 86400;
+        let is_new_day = !temple_config.is_same_day(now, self.last_wish_time);
 
         if is_new_day {
             // New day, reset count
@@ -285,7 +293,7 @@ impl UserIncenseState {
         self.total_wishes = self.total_wishes.saturating_add(1);
     }
 
-    /// Wishing more than 3 times daily requires consuming merit
+    /// Wishing beyond the free wishes requires consuming merit
     pub fn consume_merit_for_wish(&mut self, merit_cost: u64) -> Result<()> {
         if self.merit < merit_cost {
             return err!(ErrorCode::InsufficientMerit);
@@ -295,41 +303,45 @@ impl UserIncenseState {
     }
 
     /// Check if can wish for free
-    pub fn can_wish_free(&self) -> bool {
+    pub fn can_wish_free(&self, temple_config: &TempleConfig) -> bool {
         let now = Clock::get().unwrap().unix_timestamp;
-        let is_new_day = now - self.last_wish_time >= 86400;
+        let is_new_day = !temple_config.is_same_day(now, self.last_wish_time);
 
         if is_new_day {
             true // New day can wish for free
         } else {
-            self.daily_wish_count < 3 // Same day check count
+            // Same day check count
+            self.daily_wish_count
+                < temple_config
+                    .activity_rates(&self.title)
+                    .free_wishes_per_day
         }
     }
 }
 
 impl UserDonationState {
     pub const SEED_PREFIX: &str = "user_donation";
-
-    /// Calculate level based on donation amount
-    pub fn calculate_donation_level(&self) -> u8 {
-        let donation_sol = self.donation_amount as f64 / 1_000_000_000.0; // Convert to SOL
-
-        if donation_sol >= 5.0 {
-            4 // Supreme Patron
-        } else if donation_sol >= 1.0 {
-            3 // Gold Protector
-        } else if donation_sol >= 0.2 {
-            2 // Silver Disciple
-        } else if donation_sol >= 0.05 {
-            1 // Bronze Believer
-        } else {
-            0 // No level
-        }
+    pub const MAX_DONATION_LEVEL: u8 = 4;
+    /// Minimum lamports donated for levels 1-4 when no USD threshold is set
+    pub const DEFAULT_LEVEL_THRESHOLDS: [u64; 4] = [
+        50_000_000,    // Bronze Believer, 0.05 SOL
+        200_000_000,   // Silver Disciple, 0.2 SOL
+        1_000_000_000, // Gold Protector, 1 SOL
+        5_000_000_000, // Supreme Patron, 5 SOL
+    ];
+
+    /// Calculate level based on donation amount and per-level lamport thresholds
+    pub fn calculate_donation_level(&self, thresholds: &[u64; 4]) -> u8 {
+        thresholds
+            .iter()
+            .rposition(|threshold| self.donation_amount >= *threshold)
+            .map(|index| index as u8 + 1)
+            .unwrap_or(0) // No level
     }
 
     /// Update donation level
-    pub fn update_donation_level(&mut self) {
-        self.donation_level = self.calculate_donation_level();
+    pub fn update_donation_level(&mut self, thresholds: &[u64; 4]) {
+        self.donation_level = self.calculate_donation_level(thresholds);
     }
 
     /// Check if can mint Buddha NFT for free (>0.5 SOL)
@@ -351,7 +363,7 @@ impl UserDonationState {
     }
 
     /// Process donation logic
-    pub fn process_donation(&mut self, amount_lamports: u64) {
+    pub fn process_donation(&mut self, amount_lamports: u64, level_thresholds: &[u64; 4]) {
         let now = Clock::get().unwrap().unix_timestamp;
 
         // Update donation amount
@@ -362,6 +374,6 @@ impl UserDonationState {
         self.last_donation_time = now;
 
         // 更新等级
-        self.update_donation_level();
+        self.update_donation_level(level_thresholds);
     }
 }
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+// Consecutive temple days with at least one burn
+#[account]
+#[derive(InitSpace)]
+pub struct UserStreak {
+    pub user: Pubkey,
+    pub current_streak: u32,     // Consecutive days up to last_burn_day
+    pub longest_streak: u32,     // Best streak ever
+    pub last_burn_day: i64,      // Temple day index of the last burn
+    pub streak_protections: u32, // Shop items that save one missed day each
+    pub bump: u8,
+}
+
+impl UserStreak {
+    pub const SEED_PREFIX: &str = "user_streak";
+
+    // Set owner on first use (account is created with init_if_needed)
+    pub fn ensure_initialized(&mut self, user: Pubkey, bump: u8) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.bump = bump;
+        }
+    }
+
+    /// Count a burn on temple day `day`, returns whether a protection was consumed,
+    /// or None when the streak already counts this day
+    pub fn record_burn_day(&mut self, day: i64) -> Option<bool> {
+        if self.current_streak > 0 && day <= self.last_burn_day {
+            return None;
+        }
+
+        let missed_days = day - self.last_burn_day - 1;
+        let mut protection_used = false;
+        if self.current_streak > 0 && missed_days == 0 {
+            self.current_streak = self.current_streak.saturating_add(1);
+        } else if self.current_streak > 0 && missed_days == 1 && self.streak_protections > 0 {
+            // One missed day saved by a protection
+            self.streak_protections -= 1;
+            self.current_streak = self.current_streak.saturating_add(1);
+            protection_used = true;
+        } else {
+            self.current_streak = 1;
+        }
+
+        self.last_burn_day = day;
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+        Some(protection_used)
+    }
+
+    pub fn add_protections(&mut self, amount: u32) {
+        self.streak_protections = self.streak_protections.saturating_add(amount);
+    }
+}
@@ -223,6 +223,15 @@ class ProductionInitializer {
             // Step 3: Initialize Shop Config
             await this.createShopConfig();
 
+            // Step 4: Create the shared altar
+            await this.createAltar();
+
+            // Step 5: Create the NFT metadata templates
+            await this.createMetadataConfig();
+
+            // Step 6: Create the fortune table
+            await this.createFortuneTable();
+
             console.log('✅ Production initialization completed successfully!');
 
         } catch (error) {
@@ -249,8 +258,6 @@ class ProductionInitializer {
         const tx = await this.program.methods
             .createTempleConfig(
                 this.treasury,
-                COMMON_CONFIG.regularFortune,
-                COMMON_CONFIG.buddhaFortune,
                 COMMON_CONFIG.donationLevels,
                 COMMON_CONFIG.donationRewards,
                 [] // Empty temple levels for now
@@ -350,8 +357,8 @@ class ProductionInitializer {
         const templeConfigPda = this.getTempleConfigPda();
         const shopConfigPda = this.getShopConfigPda();
 
-        // Create shop items from incense types
-        const shopItems = COMMON_CONFIG.incenseTypes.map(incenseType => ({
+        // Create shop items from incense types, plus the streak protection card
+        const incenseItems = COMMON_CONFIG.incenseTypes.map(incenseType => ({
             id: incenseType.id,
             name: incenseType.name,
             description: `${incenseType.name} - 寺庙供香`,
@@ -364,6 +371,19 @@ class ProductionInitializer {
                 incensePoints: incenseType.incensePoints,
             },
         }));
+        const shopItems = [
+            ...incenseItems,
+            {
+                id: 110,
+                name: '断签保护卡',
+                description: '漏掉一天烧香时保住连续天数',
+                price: new BN(10000000), // 0.01 SOL
+                itemType: { prop: {} },
+                stock: new BN(1000000),
+                isAvailable: true,
+                incenseConfig: null,
+            },
+        ];
 
         const tx = await this.program.methods
             .createShopConfig(shopItems)
@@ -384,6 +404,99 @@ class ProductionInitializer {
         console.log('Shop config verified with', shopConfig.shopItems.length, 'items');
     }
 
+    private async createAltar(): Promise<void> {
+        console.log('\n🪔 Step 4: Creating Altar...');
+
+        const templeConfigPda = this.getTempleConfigPda();
+        const [altarPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("altar"), templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+
+        try {
+            await this.program.account.altar.fetch(altarPda);
+            console.log('Altar already exists, skipping...');
+            return;
+        } catch {
+            // Not created yet
+        }
+
+        const tx = await this.program.methods
+            .createAltar(64)
+            .accounts({
+                owner: this.adminKeypair.publicKey,
+                altar: altarPda,
+                templeConfig: templeConfigPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([this.adminKeypair])
+            .rpc();
+
+        console.log('Altar created:', tx);
+    }
+
+    private async createMetadataConfig(): Promise<void> {
+        console.log('\n🏷️ Step 5: Creating Metadata Config...');
+
+        const templeConfigPda = this.getTempleConfigPda();
+        const [metadataConfigPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("metadata_config"), templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+
+        try {
+            await this.program.account.metadataConfig.fetch(metadataConfigPda);
+            console.log('Metadata config already exists, skipping...');
+            return;
+        } catch {
+            // Not created yet
+        }
+
+        const tx = await this.program.methods
+            .createMetadataConfig()
+            .accounts({
+                owner: this.adminKeypair.publicKey,
+                metadataConfig: metadataConfigPda,
+                templeConfig: templeConfigPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([this.adminKeypair])
+            .rpc();
+
+        console.log('Metadata config created:', tx);
+    }
+
+    private async createFortuneTable(): Promise<void> {
+        console.log('\n🎋 Step 6: Creating Fortune Table...');
+
+        const templeConfigPda = this.getTempleConfigPda();
+        const [fortuneTablePda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("fortune_table"), templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+
+        try {
+            await this.program.account.fortuneTable.fetch(fortuneTablePda);
+            console.log('Fortune table already exists, skipping...');
+            return;
+        } catch {
+            // Not created yet
+        }
+
+        const tx = await this.program.methods
+            .createFortuneTable(COMMON_CONFIG.regularFortune, COMMON_CONFIG.buddhaFortune)
+            .accounts({
+                owner: this.adminKeypair.publicKey,
+                fortuneTable: fortuneTablePda,
+                templeConfig: templeConfigPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([this.adminKeypair])
+            .rpc();
+
+        console.log('Fortune table created:', tx);
+    }
+
     async updateTempleStatus(status: number): Promise<void> {
         console.log(`\n🔄 Updating temple status to: ${status}`);
 
@@ -1,3 +1,4 @@
+import * as anchor from "@coral-xyz/anchor";
 import { getTestContext, generateUserKeypair, logTestStart, logTestEnd } from "./utils/setup";
 import { expect } from "chai";
 
@@ -66,6 +67,492 @@ describe("Buy incense and Burn Tests", () => {
 
             logTestEnd("Insufficient SOL Burn");
         });
+
+        it("should burn from the purchased incense balance without charging SOL", async function () {
+            logTestStart("Burn From Incense Balance");
+            this.timeout(30000);
+
+            const buyer = generateUserKeypair();
+            await ctx.airdropToUser(buyer.publicKey);
+            await ctx.initUser(buyer);
+
+            // buy 3 incense of type 1 in the shop
+            await ctx.program.methods
+                .purchaseItem(1, new anchor.BN(3))
+                .accounts({
+                    authority: buyer.publicKey,
+                    templeTreasury: ctx.treasury,
+                    shopConfig: ctx.getShopConfigPda(),
+                    templeConfig: ctx.templeConfigPda,
+                    userIncenseState: ctx.getUserIncenseStatePda(buyer.publicKey),
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                } as any)
+                .signers([buyer])
+                .rpc();
+
+            const balanceOf = (incenseId: number) => ctx.getIncenseBalance(buyer.publicKey, incenseId);
+
+            // first burn also creates the incense token account, so measure from the second one
+            await ctx.burnIncense(buyer, 1, 2);
+            expect(await balanceOf(1)).to.equal("1");
+
+            // burning from balance charges no SOL (fees are paid by the provider wallet)
+            let lamportsBefore = await ctx.provider.connection.getBalance(buyer.publicKey);
+            await ctx.burnIncense(buyer, 1, 1);
+            let lamportsAfter = await ctx.provider.connection.getBalance(buyer.publicKey);
+            expect(lamportsAfter).to.equal(lamportsBefore);
+            expect(await balanceOf(1)).to.equal("0");
+
+            // empty balance: pay the shortfall in SOL
+            const templeConfig = await ctx.getTempleConfig();
+            const price = templeConfig.dynamicConfig.incenseTypes
+                .find((t: any) => t.id === 1)!
+                .priceLamports.toNumber();
+            lamportsBefore = lamportsAfter;
+            await ctx.burnIncense(buyer, 1, 1);
+            lamportsAfter = await ctx.provider.connection.getBalance(buyer.publicKey);
+            expect(lamportsBefore - lamportsAfter).to.equal(price);
+
+            logTestEnd("Burn From Incense Balance");
+        });
+
+        it("should reject buying donation-only incense with SOL", async function () {
+            logTestStart("Donation Only Incense Burn");
+            this.timeout(30000);
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            try {
+                await ctx.burnIncense(burner, 5, 1);
+                expect.fail("Should have thrown donation only incense error");
+            } catch (error: any) {
+                expect(error.message).to.include("DonationOnlyIncense");
+            }
+
+            logTestEnd("Donation Only Incense Burn");
+        });
+
+        it("should burn several incense types in one batch", async function () {
+            logTestStart("Batch Burn Incense");
+            this.timeout(30000);
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            await ctx.burnIncenseBatch(burner, [
+                { incenseId: 1, amount: 2 },
+                { incenseId: 2, amount: 3 },
+            ]);
+
+            const state = await ctx.program.account.userIncenseState.fetch(
+                ctx.getUserIncenseStatePda(burner.publicKey)
+            );
+            expect(state.incensePoints.toString()).to.equal((2 * 100 + 3 * 600).toString());
+            expect(state.merit.toString()).to.equal((2 * 10 + 3 * 65).toString());
+
+            // Daily limits are checked for every entry before anything is burned
+            try {
+                await ctx.burnIncenseBatch(burner, [
+                    { incenseId: 1, amount: 1 },
+                    { incenseId: 2, amount: 8 },
+                ]);
+                expect.fail("Should have thrown daily limit error");
+            } catch (error: any) {
+                expect(error.message).to.include("ExceedDailyIncenseLimit");
+            }
+
+            const after = await ctx.program.account.userIncenseState.fetch(
+                ctx.getUserIncenseStatePda(burner.publicKey)
+            );
+            expect(after.merit.toString()).to.equal(state.merit.toString());
+
+            logTestEnd("Batch Burn Incense");
+        });
+
+        it("should enforce configured daily caps and cooldowns", async function () {
+            logTestStart("Incense Caps And Cooldowns");
+            this.timeout(30000);
+
+            const config = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+            const original = config.dynamicConfig.incenseTypes;
+            const withLimits = (dailyCap: number, cooldownSeconds: number) =>
+                original.map((t: any) => (t.id === 2 ? { ...t, dailyCap, cooldownSeconds, holderCap: null } : t));
+
+            // Cooldowns longer than a day are rejected
+            try {
+                await ctx.updateIncenseTypes(withLimits(3, 86401));
+                expect.fail("Should have thrown invalid incense type error");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidIncenseType");
+            }
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            try {
+                await ctx.updateIncenseTypes(withLimits(3, 3600));
+                await ctx.burnIncense(burner, 2, 2);
+
+                try {
+                    await ctx.burnIncense(burner, 2, 1);
+                    expect.fail("Should have thrown cooldown error");
+                } catch (error: any) {
+                    expect(error.message).to.include("IncenseOnCooldown");
+                }
+
+                await ctx.updateIncenseTypes(withLimits(3, 0));
+                try {
+                    await ctx.burnIncense(burner, 2, 2);
+                    expect.fail("Should have thrown daily limit error");
+                } catch (error: any) {
+                    expect(error.message).to.include("ExceedDailyIncenseLimit");
+                }
+                await ctx.burnIncense(burner, 2, 1);
+            } finally {
+                await ctx.updateIncenseTypes(original);
+            }
+
+            logTestEnd("Incense Caps And Cooldowns");
+        });
+
+        it("should grow the incense inventory past six incense types", async function () {
+            logTestStart("Incense Inventory Growth");
+            this.timeout(60000);
+
+            const config = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+            const original = config.dynamicConfig.incenseTypes;
+            const extraTypes = [7, 8, 9].map((id) => ({
+                ...original[0],
+                id,
+                name: `Extra ${id}`,
+                priceLamports: new anchor.BN(1_000_000),
+            }));
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            try {
+                await ctx.updateIncenseTypes([...original, ...extraTypes]);
+                for (const t of extraTypes) {
+                    await ctx.createNftMint(t.id);
+                }
+
+                await ctx.burnIncenseBatch(burner, [1, 2, 3, 4, 7, 8].map((incenseId) => ({ incenseId, amount: 1 })));
+                await ctx.burnIncense(burner, 9, 1);
+            } finally {
+                await ctx.updateIncenseTypes(original);
+            }
+
+            const inventoryPda = ctx.getUserIncenseInventoryPda(burner.publicKey);
+            const inventory = await ctx.program.account.userIncenseInventory.fetch(inventoryPda);
+            expect(inventory.slots.map((s: any) => s.incenseId)).to.deep.equal([1, 2, 3, 4, 7, 8, 9]);
+            expect(inventory.slots.every((s: any) => s.dailyCount === 1)).to.be.true;
+
+            logTestEnd("Incense Inventory Growth");
+        });
+
+        it("should dedicate incense merit to another user", async function () {
+            logTestStart("Dedicate Incense To User");
+            this.timeout(30000);
+
+            const giver = generateUserKeypair();
+            const receiver = generateUserKeypair();
+            for (const kp of [giver, receiver]) {
+                await ctx.airdropToUser(kp.publicKey);
+                await ctx.initUser(kp);
+            }
+
+            await ctx.burnIncense(giver, 1, 2, null, { user: [receiver.publicKey] });
+
+            const receiverState = await ctx.program.account.userIncenseState.fetch(ctx.getUserIncenseStatePda(receiver.publicKey));
+            expect(receiverState.incensePoints.toString()).to.equal("200");
+            expect(receiverState.merit.toString()).to.equal("18"); // 90% of the burned merit
+
+            const giverState = await ctx.program.account.userIncenseState.fetch(ctx.getUserIncenseStatePda(giver.publicKey));
+            expect(giverState.incensePoints.toString()).to.equal("0");
+            expect(giverState.merit.toString()).to.equal("2"); // 10% giver share
+
+            try {
+                await ctx.burnIncense(giver, 1, 1, null, { user: [giver.publicKey] });
+                expect.fail("Dedicating to oneself should fail");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidBeneficiary");
+            }
+
+            logTestEnd("Dedicate Incense To User");
+        });
+
+        it("should dedicate incense merit to a memorial", async function () {
+            logTestStart("Dedicate Incense To Memorial");
+            this.timeout(30000);
+
+            const giver = generateUserKeypair();
+            await ctx.airdropToUser(giver.publicKey);
+            await ctx.initUser(giver);
+
+            const poolPda = ctx.getDedicationPoolPda();
+            const before = await ctx.program.account.dedicationPool.fetchNullable(poolPda);
+
+            await ctx.burnIncense(giver, 1, 1, null, { memorial: [Array.from(Buffer.alloc(32, 7))] });
+
+            const pool = await ctx.program.account.dedicationPool.fetch(poolPda);
+            const prevPoints = before ? before.totalIncensePoints.toNumber() : 0;
+            const prevDedications = before ? before.totalDedications.toNumber() : 0;
+            expect(pool.totalIncensePoints.toNumber()).to.equal(prevPoints + 100);
+            expect(pool.totalDedications.toNumber()).to.equal(prevDedications + 1);
+            expect(pool.templeConfig.toBase58()).to.equal(ctx.templeConfigPda.toBase58());
+
+            logTestEnd("Dedicate Incense To Memorial");
+        });
+
+        it("should track burning streaks and apply the streak multiplier", async function () {
+            logTestStart("Burning Streak");
+            this.timeout(30000);
+
+            try {
+                await ctx.updateStreakMultipliers([{ minDays: 1, bonusPercent: 200 }]);
+                expect.fail("Should have thrown invalid streak config error");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidStreakConfig");
+            }
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            const config = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+            const original = config.dynamicConfig.streakMultipliers;
+            try {
+                // A one-day tier applies from the first burn
+                await ctx.updateStreakMultipliers([{ minDays: 1, bonusPercent: 50 }]);
+                await ctx.burnIncense(burner, 1, 2);
+                await ctx.burnIncense(burner, 1, 1);
+            } finally {
+                await ctx.updateStreakMultipliers(original);
+            }
+
+            const streak = await ctx.program.account.userStreak.fetch(ctx.getUserStreakPda(burner.publicKey));
+            expect(streak.currentStreak).to.equal(1); // Same temple day counts once
+            expect(streak.longestStreak).to.equal(1);
+
+            const userIncenseState = await ctx.program.account.userIncenseState.fetch(ctx.getUserIncenseStatePda(burner.publicKey));
+            expect(userIncenseState.merit.toString()).to.equal("45"); // 30 merit + 50%
+
+            logTestEnd("Burning Streak");
+        });
+
+        it("should keep a streak across a missed day with a streak protection", async function () {
+            logTestStart("Streak Protection");
+            this.timeout(60000);
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            // Streak protection (item 110) is seeded into the shop by create_shop_config
+            await ctx.program.methods
+                .purchaseItem(110, new anchor.BN(1))
+                .accounts({
+                    authority: burner.publicKey,
+                    templeTreasury: ctx.treasury,
+                    shopConfig: ctx.getShopConfigPda(),
+                    templeConfig: ctx.templeConfigPda,
+                    userIncenseState: ctx.getUserIncenseStatePda(burner.publicKey),
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                } as any)
+                .signers([burner])
+                .rpc();
+
+            const config = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+            try {
+                // Moving the temple day start 48 hours earlier skips exactly one temple day
+                await ctx.updateDayBoundary(-12 * 60, 22);
+                await ctx.burnIncense(burner, 1, 1);
+                await ctx.updateDayBoundary(14 * 60, 0);
+                await ctx.burnIncense(burner, 1, 1);
+            } finally {
+                await ctx.updateDayBoundary(config.utcOffsetMinutes, config.dayBoundaryHour);
+            }
+
+            const streak = await ctx.program.account.userStreak.fetch(ctx.getUserStreakPda(burner.publicKey));
+            expect(streak.currentStreak).to.equal(2); // The missed day was saved
+            expect(streak.longestStreak).to.equal(2);
+            expect(streak.streakProtections).to.equal(0);
+
+            logTestEnd("Streak Protection");
+        });
+
+        it("should light burned incense on the shared altar", async function () {
+            logTestStart("Altar");
+            this.timeout(30000);
+
+            const burner = generateUserKeypair();
+            await ctx.airdropToUser(burner.publicKey);
+            await ctx.initUser(burner);
+
+            await ctx.burnIncense(burner, 2, 1);
+
+            const altarPda = ctx.getAltarPda();
+            let altar = await ctx.program.account.altar.fetch(altarPda);
+            expect(altar.sticks).to.have.lengthOf(64);
+            const stick = altar.sticks.find((s: any) => s.burner.equals(burner.publicKey));
+            expect(stick).to.not.be.undefined;
+            expect(stick.incenseId).to.equal(2);
+            expect(stick.burnsUntil.sub(stick.litAt).toNumber()).to.equal(1800);
+
+            // Burns without the altar still succeed, they just light no stick
+            const unlit = generateUserKeypair();
+            await ctx.airdropToUser(unlit.publicKey);
+            await ctx.initUser(unlit);
+            await ctx.burnIncense(unlit, 1, 1, null, null, false);
+            altar = await ctx.program.account.altar.fetch(altarPda);
+            expect(altar.sticks.some((s: any) => s.burner.equals(unlit.publicKey))).to.be.false;
+
+            // Shrinking keeps the most recent sticks
+            await ctx.resizeAltar(1);
+            altar = await ctx.program.account.altar.fetch(altarPda);
+            expect(altar.sticks).to.have.lengthOf(1);
+            expect(altar.sticks[0].burner.equals(burner.publicKey)).to.be.true;
+            await ctx.resizeAltar(64);
+
+            try {
+                await ctx.resizeAltar(0);
+                expect.fail("Should have thrown invalid altar config error");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidAltarConfig");
+            }
+
+            logTestEnd("Altar");
+        });
+
+        it("should buy incense with a whitelisted SPL token", async function () {
+            logTestStart("SPL Token Payment");
+            this.timeout(60000);
+
+            const buyer = generateUserKeypair();
+            await ctx.airdropToUser(buyer.publicKey);
+            await ctx.initUser(buyer);
+
+            const usdc = await ctx.createTestTokenMint(6);
+            await ctx.mintTestTokens(usdc, buyer.publicKey, 10_000_000);
+
+            // Unregistered mint is rejected
+            try {
+                await ctx.buyIncense(buyer, 1, 1, usdc);
+                expect.fail("Should have thrown payment mint not accepted error");
+            } catch (error: any) {
+                expect(error.message).to.include("PaymentMintNotAccepted");
+            }
+
+            const config = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+            const original = config.dynamicConfig.paymentMints;
+            try {
+                await ctx.updatePaymentMints([
+                    {
+                        mint: usdc,
+                        decimals: 6,
+                        enabled: true,
+                        donationLamportsPerToken: new anchor.BN(5_000_000),
+                        incensePrices: [{ id: 1, amount: new anchor.BN(1_500_000) }], // 1.5 USDC
+                        itemPrices: [],
+                    },
+                ]);
+                await ctx.createPaymentTreasury(usdc);
+
+                const statsBefore = await ctx.program.account.globalStats.fetch(ctx.getGlobalStatsPda());
+                const solBefore = await ctx.provider.connection.getBalance(ctx.treasury);
+
+                await ctx.buyIncense(buyer, 1, 2, usdc);
+
+                const treasuryAta = ctx.tokenPaymentAccounts(usdc, buyer.publicKey).treasuryTokenAccount;
+                const treasuryBalance = await ctx.provider.connection.getTokenAccountBalance(treasuryAta);
+                expect(treasuryBalance.value.amount).to.equal("3000000");
+                expect(await ctx.provider.connection.getBalance(ctx.treasury)).to.equal(solBefore);
+
+                const stats = await ctx.program.account.globalStats.fetch(ctx.getGlobalStatsPda());
+                const total = stats.tokenPayments.find((t: any) => t.mint.equals(usdc));
+                expect(total.totalAmount.toString()).to.equal("3000000");
+                expect(stats.tokenPayments.length).to.equal(statsBefore.tokenPayments.length + 1);
+
+                // Only the temple owner can move the payments out of the treasury
+                const buyerAta = ctx.tokenPaymentAccounts(usdc, buyer.publicKey).payerTokenAccount;
+                try {
+                    await ctx.withdrawTokenTreasury(usdc, buyerAta, 1_000_000, buyer);
+                    expect.fail("Should have thrown unauthorized error");
+                } catch (error: any) {
+                    expect(error.message).to.include("Unauthorized");
+                }
+                const buyerBefore = await ctx.provider.connection.getTokenAccountBalance(buyerAta);
+                await ctx.withdrawTokenTreasury(usdc, buyerAta, 1_000_000);
+                const buyerAfter = await ctx.provider.connection.getTokenAccountBalance(buyerAta);
+                expect(Number(buyerAfter.value.amount) - Number(buyerBefore.value.amount)).to.equal(1_000_000);
+                const treasuryAfter = await ctx.provider.connection.getTokenAccountBalance(treasuryAta);
+                expect(treasuryAfter.value.amount).to.equal("2000000");
+
+                // No price configured for this incense type
+                try {
+                    await ctx.buyIncense(buyer, 2, 1, usdc);
+                    expect.fail("Should have thrown token price not set error");
+                } catch (error: any) {
+                    expect(error.message).to.include("TokenPriceNotSet");
+                }
+            } finally {
+                await ctx.updatePaymentMints(original);
+            }
+
+            logTestEnd("SPL Token Payment");
+        });
+
+        it("should price incense in USD through the SOL/USD feed", async function () {
+            logTestStart("USD Pricing");
+            this.timeout(60000);
+
+            const buyer = generateUserKeypair();
+            await ctx.airdropToUser(buyer.publicKey);
+            await ctx.initUser(buyer);
+
+            const config = await ctx.getTempleConfig();
+            const original = config.dynamicConfig.incenseTypes;
+            try {
+                // $3.00 per stick
+                await ctx.updateIncenseTypes(
+                    original.map((t: any) => (t.id === 1 ? { ...t, priceUsdCents: new anchor.BN(300) } : t))
+                );
+
+                // USD prices need the price feed
+                try {
+                    await ctx.buyIncense(buyer, 1, 1);
+                    expect.fail("Should have thrown price feed required error");
+                } catch (error: any) {
+                    expect(error.message).to.include("PriceFeedRequired");
+                }
+
+                // Confidence wider than 2% of the price is rejected
+                let feed = await ctx.setMockPrice(15_000_000_000, 500_000_000);
+                try {
+                    await ctx.buyIncense(buyer, 1, 1, null, feed);
+                    expect.fail("Should have thrown price confidence error");
+                } catch (error: any) {
+                    expect(error.message).to.include("PriceConfidenceTooWide");
+                }
+
+                // $150/SOL: $3.00 = 0.02 SOL
+                feed = await ctx.setMockPrice(15_000_000_000, 10_000_000);
+                const treasuryBefore = await ctx.provider.connection.getBalance(ctx.treasury);
+                await ctx.buyIncense(buyer, 1, 2, null, feed);
+                const treasuryAfter = await ctx.provider.connection.getBalance(ctx.treasury);
+                expect(treasuryAfter - treasuryBefore).to.equal(0.04 * anchor.web3.LAMPORTS_PER_SOL);
+            } finally {
+                await ctx.updateIncenseTypes(original);
+            }
+
+            logTestEnd("USD Pricing");
+        });
     });
 
     describe("Title System", () => {
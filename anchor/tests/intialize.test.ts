@@ -37,7 +37,150 @@ describe("Temple Initialize Tests", () => {
             logTestEnd("Create Temple Config");
         });
 
+        it("should seed the default five-tier fortune table", async () => {
+            logTestStart("Default Fortune Tiers");
 
+            const tiers = await ctx.getFortuneTiers();
+            expect(tiers).to.have.lengthOf(5);
+            expect(tiers[0].name).to.equal("Great Luck");
+            expect(tiers[0].category).to.have.property("greatLuck");
+
+            const regularTotal = tiers.reduce((sum: number, t: any) => sum + t.weightBps, 0);
+            const buddhaTotal = tiers.reduce((sum: number, t: any) => sum + t.buddhaWeightBps, 0);
+            expect(regularTotal).to.equal(10000);
+            expect(buddhaTotal).to.equal(10000);
+
+            logTestEnd("Default Fortune Tiers");
+        });
+
+        it("should reject invalid fortune tier updates", async () => {
+            logTestStart("Invalid Fortune Tiers");
+
+            const tier = {
+                name: "第一签 上上",
+                description: "Test slip",
+                uri: "https://example.com/slip/1.json",
+                weightBps: 5000,
+                buddhaWeightBps: 5000,
+                meritReward: new anchor.BN(2),
+                category: { greatLuck: {} },
+            };
+
+            // complete table whose weights do not add up to 100%
+            try {
+                await ctx.updateFortuneTiers(0, 1, [tier]);
+                expect.fail("Should have thrown invalid fortune config error");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidFortuneConfig");
+            }
+
+            // partial table while draws are still open
+            try {
+                await ctx.updateFortuneTiers(0, 2, [tier]);
+                expect.fail("Should have thrown invalid fortune config error");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidFortuneConfig");
+            }
+
+            // only the temple owner can update the table
+            const stranger = generateUserKeypair();
+            await ctx.airdropToUser(stranger.publicKey);
+            try {
+                await ctx.updateFortuneTiers(0, 2, [tier, tier], stranger);
+                expect.fail("Should have thrown unauthorized error");
+            } catch (error: any) {
+                expect(error.message).to.include("Unauthorized");
+            }
+
+            expect(await ctx.getFortuneTiers()).to.have.lengthOf(5);
+
+            logTestEnd("Invalid Fortune Tiers");
+        });
+
+        it("should reset daily counters at UTC+8 midnight by default", async () => {
+            logTestStart("Day Boundary");
+
+            let templeConfig = await ctx.getTempleConfig();
+            expect(templeConfig.utcOffsetMinutes).to.equal(480);
+            expect(templeConfig.dayBoundaryHour).to.equal(0);
+
+            for (const [offset, hour] of [[480, 24], [15 * 60, 0], [-13 * 60, 0]]) {
+                try {
+                    await ctx.updateDayBoundary(offset, hour);
+                    expect.fail("Should have thrown invalid day boundary error");
+                } catch (error: any) {
+                    expect(error.message).to.include("InvalidDayBoundary");
+                }
+            }
+
+            await ctx.updateDayBoundary(-300, 4);
+            templeConfig = await ctx.getTempleConfig();
+            expect(templeConfig.utcOffsetMinutes).to.equal(-300);
+            expect(templeConfig.dayBoundaryHour).to.equal(4);
+
+            await ctx.updateDayBoundary(480, 0);
+
+            logTestEnd("Day Boundary");
+        });
+
+
+    });
+
+    describe("Metadata Config", () => {
+        it("should seed default templates and let the owner update them", async () => {
+            logTestStart("Metadata Config");
+
+            const metadataConfigPda = ctx.getMetadataConfigPda();
+            const metadataConfig = await ctx.program.account.metadataConfig.fetch(metadataConfigPda);
+            expect(metadataConfig.fortune.symbol).to.equal("TMF");
+            expect(metadataConfig.medal.variantUris).to.have.lengthOf(4);
+            expect(metadataConfig.amulet.sellerFeeBasisPoints).to.equal(500);
+
+            const original = metadataConfig.wishTower;
+            const template = {
+                ...original,
+                uriTemplate: "https://gateway.example.com/ipfs/wish-tower/{level}.json",
+            };
+            try {
+                await ctx.updateMetadataTemplate({ wishTower: {} }, template);
+                const updated = await ctx.program.account.metadataConfig.fetch(metadataConfigPda);
+                expect(updated.wishTower.uriTemplate).to.equal(template.uriTemplate);
+                expect(updated.fortune.symbol).to.equal("TMF");
+
+                // symbol longer than Metaplex allows
+                try {
+                    await ctx.updateMetadataTemplate({ wishTower: {} }, { ...template, symbol: "WISH_TOWER_X" });
+                    expect.fail("Should have thrown invalid metadata config error");
+                } catch (error: any) {
+                    expect(error.message).to.include("InvalidMetadataConfig");
+                }
+
+                // name text longer than Metaplex allows once rendered
+                try {
+                    await ctx.updateMetadataTemplate({ wishTower: {} }, {
+                        ...template,
+                        nameTemplate: "Wish Tower of Boundless Merit Lv{level}",
+                    });
+                    expect.fail("Should have thrown invalid metadata config error");
+                } catch (error: any) {
+                    expect(error.message).to.include("InvalidMetadataConfig");
+                }
+
+                // only the temple owner can update templates
+                const stranger = generateUserKeypair();
+                await ctx.airdropToUser(stranger.publicKey);
+                try {
+                    await ctx.updateMetadataTemplate({ wishTower: {} }, template, stranger);
+                    expect.fail("Should have thrown unauthorized error");
+                } catch (error: any) {
+                    expect(error.message).to.include("Unauthorized");
+                }
+            } finally {
+                await ctx.updateMetadataTemplate({ wishTower: {} }, original);
+            }
+
+            logTestEnd("Metadata Config");
+        });
     });
 
     describe("NFT Mint Creation", () => {
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { expect } from "chai";
 import { getTestContext, generateUserKeypair } from "./utils/setup";
-import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { Ed25519Program, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
 
 describe("Share Fortune Tests", () => {
     const ctx = getTestContext();
@@ -39,11 +39,83 @@ describe("Share Fortune Tests", () => {
         const initialMerit = initialUserIncenseState.merit;
         console.log("初始功德值:", initialMerit.toString());
 
-        await ctx.shareFortune(user, shareHash);
+        // 首次抽签的序号为1
+        await ctx.shareFortune(user, 1, shareHash);
 
         // 验证功德值增加1
         const finalUserIncenseState = await ctx.program.account.userIncenseState.fetch(userIncenseStatePda);
         console.log("\n验证功德值增加1:", finalUserIncenseState.merit.toString());
+        expect(finalUserIncenseState.merit.sub(initialMerit).toNumber()).to.equal(1);
+
+        const shareRecord = await ctx.program.account.shareRecord.fetch(ctx.getShareRecordPda(user.publicKey, 1));
+        expect(shareRecord.serialNumber).to.equal(1);
+        expect(shareRecord.shareHash).to.deep.equal(shareHash);
+        expect(shareRecord.attested).to.equal(false);
+    });
+
+    it("should fail when sharing the same draw twice", async () => {
+        await ctx.drawFortune(user);
+        await ctx.shareFortune(user, 1, shareHash);
+
+        try {
+            await ctx.shareFortune(user, 1, shareHash);
+            expect.fail("Should have thrown error for sharing the same draw twice");
+        } catch (error: any) {
+            expect(error.message).to.include("already in use");
+        }
+    });
+
+    it("should fail when sharing a draw that does not exist", async () => {
+        await ctx.drawFortune(user);
+
+        try {
+            await ctx.shareFortune(user, 2, shareHash);
+            expect.fail("Should have thrown error for sharing an unknown draw");
+        } catch (error: any) {
+            expect(error.message).to.include("FortuneNotFound");
+        }
+    });
+
+    it("should require the attester signature when one is configured", async () => {
+        await ctx.drawFortune(user);
+
+        const attester = Keypair.generate();
+        const templeConfig = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+        const original = templeConfig.dynamicConfig.activityEconomy;
+
+        try {
+            await ctx.updateActivityEconomy({ ...original, shareAttester: attester.publicKey });
+
+            try {
+                await ctx.shareFortune(user, 1, shareHash);
+                expect.fail("Should have thrown error for missing attestation");
+            } catch (error: any) {
+                expect(error.message).to.include("ShareAttestationRequired");
+            }
+
+            // 签名内容与分享不符
+            const wrongIx = Ed25519Program.createInstructionWithPrivateKey({
+                privateKey: attester.secretKey,
+                message: ctx.getShareAttestationMessage(user.publicKey, 2, shareHash),
+            });
+            try {
+                await ctx.shareFortune(user, 1, shareHash, [wrongIx]);
+                expect.fail("Should have thrown error for mismatched attestation");
+            } catch (error: any) {
+                expect(error.message).to.include("InvalidShareAttestation");
+            }
+
+            const attestIx = Ed25519Program.createInstructionWithPrivateKey({
+                privateKey: attester.secretKey,
+                message: ctx.getShareAttestationMessage(user.publicKey, 1, shareHash),
+            });
+            await ctx.shareFortune(user, 1, shareHash, [attestIx]);
+
+            const shareRecord = await ctx.program.account.shareRecord.fetch(ctx.getShareRecordPda(user.publicKey, 1));
+            expect(shareRecord.attested).to.equal(true);
+        } finally {
+            await ctx.updateActivityEconomy(original);
+        }
     });
 
     it("should fail when sharing without recent fortune draw", async () => {
@@ -55,10 +127,11 @@ describe("Share Fortune Tests", () => {
         const shareHash = Array(32).fill(0).map((_, i) => i + 1);
 
         try {
-            await ctx.shareFortune(newUser, shareHash);
+            await ctx.shareFortune(newUser, 1, shareHash);
             expect.fail("Should have thrown error for sharing without recent fortune draw");
         } catch (error: any) {
-            expect(error.message).to.include("Share too late");
+            // 未抽签则没有抽签记录
+            expect(error.message).to.include("AccountNotInitialized");
         }
     });
 });
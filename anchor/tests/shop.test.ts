@@ -39,11 +39,12 @@ describe("Shop System", () => {
             })
             .view();
 
-        expect(shopItemsResult.items).to.have.lengthOf(6); // Should have 6 incense types
+        expect(shopItemsResult.items).to.have.lengthOf(7); // 6 incense types and the streak protection
         expect(shopItemsResult.items[0].id).to.equal(1);
         expect(shopItemsResult.items[0].name).to.equal("清香");
         expect(shopItemsResult.items[1].id).to.equal(2);
         expect(shopItemsResult.items[1].name).to.equal("檀香");
+        expect(shopItemsResult.items[6].id).to.equal(110);
 
         logTestEnd("Get Shop Items");
     });
@@ -141,8 +142,8 @@ describe("Shop System", () => {
         logTestEnd("Purchase Invalid Item");
     });
 
-    it("should buy incense with price from shop", async () => {
-        logTestStart("Buy Incense with Shop Price");
+    it("should buy incense tokens at the configured price", async () => {
+        logTestStart("Buy Incense Tokens");
 
         const user = generateUserKeypair();
         await ctx.airdropToUser(user.publicKey);
@@ -151,23 +152,42 @@ describe("Shop System", () => {
         const incenseId = 1;
         const amount = 2;
 
-        // Get price from shop config
-        const shopConfig = await ctx.program.account.shopConfig.fetch(ctx.getShopConfigPda());
-        const shopItem = shopConfig.shopItems.find(
-            (item: any) => item.itemType.incense !== undefined && item.id === incenseId
-        );
-        expect(shopItem).to.not.be.undefined;
-        const pricePerUnit = shopItem.price.toNumber();
+        const templeConfig = await ctx.getTempleConfig();
+        const incenseType = templeConfig.dynamicConfig.incenseTypes.find((t: any) => t.id === incenseId);
+        expect(incenseType).to.not.be.undefined;
 
-        // Buy incense with price from shop
-        await ctx.buyIncense(user, incenseId, amount, pricePerUnit);
+        const initialBalance = await ctx.provider.connection.getBalance(user.publicKey);
+        await ctx.buyIncense(user, incenseId, amount);
+        const finalBalance = await ctx.provider.connection.getBalance(user.publicKey);
+        // price plus the rent of the newly created token account
+        expect(initialBalance - finalBalance).to.be.at.least(incenseType!.priceLamports.toNumber() * amount);
+
+        // Incense is held as transferable SPL tokens
+        const tokenAccount = await ctx.getAssociatedTokenAddress(ctx.getIncenseMintPda(incenseId), user.publicKey);
+        const tokenBalance = await ctx.provider.connection.getTokenAccountBalance(tokenAccount);
+        expect(tokenBalance.value.amount).to.equal(amount.toString());
+
+        // Burning consumes the held tokens first
+        await ctx.burnIncense(user, incenseId, 1);
+        const afterBurn = await ctx.provider.connection.getTokenAccountBalance(tokenAccount);
+        expect(afterBurn.value.amount).to.equal("1");
+
+        logTestEnd("Buy Incense Tokens");
+    });
+
+    it("should reject buying donation-only incense tokens", async () => {
+        logTestStart("Buy Donation Only Incense");
 
-        // Verify incense balance
-        const userIncenseStatePda = ctx.getUserIncenseStatePda(user.publicKey);
-        const userIncenseState = await ctx.program.account.userIncenseState.fetch(userIncenseStatePda);
-        const incenseBalance = userIncenseState.incenseBalance.find(b => b.incenseId === incenseId);
-        expect(incenseBalance?.balance.toString()).to.equal(amount.toString());
+        const user = generateUserKeypair();
+        await ctx.airdropToUser(user.publicKey);
+
+        try {
+            await ctx.buyIncense(user, 5, 1);
+            expect.fail("Should have thrown donation only incense error");
+        } catch (error: any) {
+            expect(error.message).to.include("DonationOnlyIncense");
+        }
 
-        logTestEnd("Buy Incense with Shop Price");
+        logTestEnd("Buy Donation Only Incense");
     });
 });
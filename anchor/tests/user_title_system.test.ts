@@ -19,6 +19,21 @@ describe("User Title System Tests", () => {
         await ctx.createNftMint(2);
     });
 
+    // Progression burns far more Fresh incense than its daily cap allows
+    let originalIncenseTypes: any[];
+
+    before(async () => {
+        const config = await ctx.program.account.templeConfig.fetch(ctx.templeConfigPda);
+        originalIncenseTypes = config.dynamicConfig.incenseTypes;
+        await ctx.updateIncenseTypes(
+            originalIncenseTypes.map((t: any) => (t.id === 1 ? { ...t, dailyCap: 0, cooldownSeconds: 0 } : t))
+        );
+    });
+
+    after(async () => {
+        await ctx.updateIncenseTypes(originalIncenseTypes);
+    });
+
     describe("Title Progression", () => {
         it("should automatically update title based on merit", async () => {
             logTestStart("Title Progression Based on Merit");
@@ -190,7 +190,7 @@ export class TestContext {
 
         try {
             const tx = await this.program.methods
-                .createTempleConfig(treasury, regularFortune, buddhaFortune, donationLevels, donationRewards, templeLevels)
+                .createTempleConfig(treasury, donationLevels, donationRewards, templeLevels)
                 .accounts({
                     owner: this.owner.publicKey,
                     templeConfig: this.templeConfigPda,
@@ -206,6 +206,15 @@ export class TestContext {
             // 初始化商城物品
             await this.initShopItems();
 
+            // 创建供香台
+            await this.createAltar();
+
+            // 创建 NFT 元数据模板
+            await this.createMetadataConfig();
+
+            // 创建签文表
+            await this.createFortuneTable(regularFortune, buddhaFortune);
+
             return tx;
         } catch (error: any) {
             // Check if config already exists
@@ -255,6 +264,246 @@ export class TestContext {
         console.log(`Shop config created: ${createTx}`);
     }
 
+    public async createAltar(slotCount: number = 64): Promise<string> {
+        const tx = await this.program.methods
+            .createAltar(slotCount)
+            .accounts({
+                owner: this.owner.publicKey,
+                altar: this.getAltarPda(),
+                templeConfig: this.templeConfigPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([this.owner])
+            .rpc();
+
+        console.log(`Altar created: ${tx}`);
+        return tx;
+    }
+
+    public async createMetadataConfig(): Promise<string> {
+        const tx = await this.program.methods
+            .createMetadataConfig()
+            .accounts({
+                owner: this.owner.publicKey,
+                metadataConfig: this.getMetadataConfigPda(),
+                templeConfig: this.templeConfigPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([this.owner])
+            .rpc();
+
+        console.log(`Metadata config created: ${tx}`);
+        return tx;
+    }
+
+    public async createFortuneTable(
+        regularFortune = TEST_CONFIG.defaultRegularFortune,
+        buddhaFortune = TEST_CONFIG.defaultBuddhaFortune
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .createFortuneTable(regularFortune, buddhaFortune)
+            .accounts({
+                owner: this.owner.publicKey,
+                fortuneTable: this.getFortuneTablePda(),
+                templeConfig: this.templeConfigPda,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([this.owner])
+            .rpc();
+
+        console.log(`Fortune table created: ${tx}`);
+        return tx;
+    }
+
+    public async updateMetadataTemplate(
+        family: any,
+        template: any,
+        authority: Keypair = this.owner
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .updateMetadataTemplate(family, template)
+            .accounts({
+                metadataConfig: this.getMetadataConfigPda(),
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Metadata template updated: ${tx}`);
+        return tx;
+    }
+
+    public async resizeAltar(slotCount: number, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .resizeAltar(slotCount)
+            .accounts({
+                altar: this.getAltarPda(),
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Altar resized: ${tx}`);
+        return tx;
+    }
+
+    // Optional SPL token payment accounts, all null when paying in SOL
+    public tokenPaymentAccounts(paymentMint: PublicKey | null, payer: PublicKey): any {
+        if (!paymentMint) {
+            return { paymentMint: null, payerTokenAccount: null, treasuryTokenAccount: null };
+        }
+        return {
+            paymentMint,
+            payerTokenAccount: anchor.utils.token.associatedAddress({ mint: paymentMint, owner: payer }),
+            treasuryTokenAccount: anchor.utils.token.associatedAddress({ mint: paymentMint, owner: this.templeConfigPda }),
+        };
+    }
+
+    public async updatePaymentMints(paymentMints: any[], authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updatePaymentMints(paymentMints)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Payment mints updated: ${tx}`);
+        return tx;
+    }
+
+    public async createPaymentTreasury(paymentMint: PublicKey, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .createPaymentTreasury()
+            .accounts({
+                authority: authority.publicKey,
+                templeConfig: this.templeConfigPda,
+                paymentMint,
+                treasuryTokenAccount: anchor.utils.token.associatedAddress({ mint: paymentMint, owner: this.templeConfigPda }),
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Payment treasury created: ${tx}`);
+        return tx;
+    }
+
+    public async withdrawTokenTreasury(
+        paymentMint: PublicKey,
+        destinationTokenAccount: PublicKey,
+        amount: number,
+        authority: Keypair = this.owner
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .withdrawTokenTreasury(new anchor.BN(amount))
+            .accounts({
+                authority: authority.publicKey,
+                templeConfig: this.templeConfigPda,
+                paymentMint,
+                treasuryTokenAccount: anchor.utils.token.associatedAddress({ mint: paymentMint, owner: this.templeConfigPda }),
+                destinationTokenAccount,
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Token treasury withdrawn: ${tx}`);
+        return tx;
+    }
+
+    // Create a test SPL mint (e.g. a mock stablecoin) with the owner as mint authority
+    public async createTestTokenMint(decimals: number = 6): Promise<PublicKey> {
+        const mint = Keypair.generate();
+        const MINT_SIZE = 82;
+        const lamports = await this.provider.connection.getMinimumBalanceForRentExemption(MINT_SIZE);
+
+        // InitializeMint2: [20, decimals, mint authority, no freeze authority]
+        const data = Buffer.concat([Buffer.from([20, decimals]), this.owner.publicKey.toBuffer(), Buffer.from([0])]);
+        const tx = new anchor.web3.Transaction().add(
+            SystemProgram.createAccount({
+                fromPubkey: this.owner.publicKey,
+                newAccountPubkey: mint.publicKey,
+                space: MINT_SIZE,
+                lamports,
+                programId: anchor.utils.token.TOKEN_PROGRAM_ID,
+            }),
+            new anchor.web3.TransactionInstruction({
+                programId: anchor.utils.token.TOKEN_PROGRAM_ID,
+                keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+                data,
+            })
+        );
+        await anchor.web3.sendAndConfirmTransaction(this.provider.connection, tx, [this.owner, mint]);
+        return mint.publicKey;
+    }
+
+    // Mint test tokens into the owner's ATA, created if missing
+    public async mintTestTokens(mint: PublicKey, owner: PublicKey, amount: number): Promise<PublicKey> {
+        const ata = anchor.utils.token.associatedAddress({ mint, owner });
+        const amountData = Buffer.alloc(8);
+        amountData.writeBigUInt64LE(BigInt(amount));
+
+        const tx = new anchor.web3.Transaction().add(
+            // CreateIdempotent
+            new anchor.web3.TransactionInstruction({
+                programId: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                keys: [
+                    { pubkey: this.owner.publicKey, isSigner: true, isWritable: true },
+                    { pubkey: ata, isSigner: false, isWritable: true },
+                    { pubkey: owner, isSigner: false, isWritable: false },
+                    { pubkey: mint, isSigner: false, isWritable: false },
+                    { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+                    { pubkey: anchor.utils.token.TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+                ],
+                data: Buffer.from([1]),
+            }),
+            // MintTo
+            new anchor.web3.TransactionInstruction({
+                programId: anchor.utils.token.TOKEN_PROGRAM_ID,
+                keys: [
+                    { pubkey: mint, isSigner: false, isWritable: true },
+                    { pubkey: ata, isSigner: false, isWritable: true },
+                    { pubkey: this.owner.publicKey, isSigner: true, isWritable: false },
+                ],
+                data: Buffer.concat([Buffer.from([7]), amountData]),
+            })
+        );
+        await anchor.web3.sendAndConfirmTransaction(this.provider.connection, tx, [this.owner]);
+        return ata;
+    }
+
+    public getAltarPda(): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("altar"), this.templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getMetadataConfigPda(): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("metadata_config"), this.templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getFortuneTablePda(): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("fortune_table"), this.templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
     public getShopConfigPda(): PublicKey {
         const [pda] = PublicKey.findProgramAddressSync(
             [Buffer.from("shop_config"), this.templeConfigPda.toBuffer()],
@@ -384,6 +633,8 @@ export class TestContext {
                 userIncenseState: userIncenseStatePda,
                 userMedalState: userMedalStatePda,
                 userDonationState: userDonationStatePda,
+                pendingAmulets: this.getPendingAmuletsPda(userKeypair.publicKey),
+                userIncenseInventory: this.getUserIncenseInventoryPda(userKeypair.publicKey),
                 user: userKeypair.publicKey,
                 systemProgram: anchor.web3.SystemProgram.programId
             })
@@ -394,12 +645,64 @@ export class TestContext {
         return tx;
     }
 
-    // Note: buyIncense has been merged into burnIncense - burning now includes payment
+    public getIncenseMintPda(incenseId: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("IncenseNFT_V1"),
+                this.templeConfigPda.toBuffer(),
+                Buffer.from([incenseId])
+            ],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    // Buy incense tokens into the user's ATA; burnIncense consumes them first
+    public async buyIncense(
+        user: Keypair,
+        incenseId: number,
+        amount: number,
+        paymentMint: PublicKey | null = null,
+        solUsdPrice: PublicKey | null = null,
+    ): Promise<string> {
+        console.log(`User buying ${amount} incense of type ${incenseId}...`);
+
+        const nftMintPda = this.getIncenseMintPda(incenseId);
+        const nftAssociatedTokenAccount = await anchor.utils.token.associatedAddress({
+            mint: nftMintPda,
+            owner: user.publicKey,
+        });
+
+        const tx = await this.program.methods
+            .buyIncense(incenseId, new BN(amount))
+            .accounts({
+                authority: user.publicKey,
+                templeAuthority: this.owner.publicKey,
+                templeTreasury: this.treasury,
+                templeConfig: this.templeConfigPda,
+                globalStats: this.getGlobalStatsPda(),
+                ...this.tokenPaymentAccounts(paymentMint, user.publicKey),
+                solUsdPrice,
+                nftMintAccount: nftMintPda,
+                nftAssociatedTokenAccount,
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([user])
+            .rpc();
+
+        console.log(`Incense bought: ${tx}`);
+        return tx;
+    }
 
     public async burnIncense(
         user: Keypair,
         incenseId: number,
         amount: number,
+        meritAmuletMint: PublicKey | null = null,
+        beneficiary: any = null,
+        lightAltar: boolean = true,
     ): Promise<string> {
         console.log(`User burning ${amount} incense of type ${incenseId}...`);
 
@@ -438,20 +741,35 @@ export class TestContext {
             templeAuthority: this.owner.publicKey,
             templeTreasury: this.treasury,
             templeConfig: this.templeConfigPda,
+            // The altar is optional, burns skip it when left out
+            altar: lightAltar ? this.getAltarPda() : null,
             userState: userStatePda,
             userIncenseState: userIncenseStatePda,
+            userIncenseInventory: this.getUserIncenseInventoryPda(user.publicKey),
+            userStreak: this.getUserStreakPda(user.publicKey),
+            pendingAmulets: this.getPendingAmuletsPda(user.publicKey),
             nftMintAccount: nftMintPda,
             nftAssociatedTokenAccount,
             tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
             tokenMetadataProgram: new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"),
-            associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+            associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            medalNftAccount: null,
+            ...this.tokenPaymentAccounts(null, user.publicKey),
+            meritAmulet: meritAmuletMint ? this.getAmuletNftPda(meritAmuletMint) : null,
+            meritAmuletTokenAccount: meritAmuletMint
+                ? await anchor.utils.token.associatedAddress({ mint: meritAmuletMint, owner: user.publicKey })
+                : null,
+            slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+            // Dedication (回向): { user: [pubkey] } or { memorial: [hash] }
+            beneficiaryIncenseState: beneficiary?.user ? this.getUserIncenseStatePda(beneficiary.user[0]) : null,
+            dedicationPool: beneficiary?.memorial ? this.getDedicationPoolPda() : null,
         };
 
 
         const tx = await this.program.methods
-            .burnIncense(incenseId, new BN(amount), false)
+            .burnIncense(incenseId, new BN(amount), beneficiary)
             .accounts(accounts)
             .signers([user])
             .rpc();
@@ -460,40 +778,467 @@ export class TestContext {
         return tx;
     }
 
+    // Burn several incense types at once; mint and ATA pairs go in remaining accounts
+    public async burnIncenseBatch(
+        user: Keypair,
+        entries: { incenseId: number; amount: number }[],
+    ): Promise<string> {
+        console.log(`User burning incense batch: ${JSON.stringify(entries)}`);
+
+        const remainingAccounts = [];
+        for (const entry of entries) {
+            const mint = this.getIncenseMintPda(entry.incenseId);
+            const ata = await anchor.utils.token.associatedAddress({ mint, owner: user.publicKey });
+            remainingAccounts.push(
+                { pubkey: mint, isWritable: true, isSigner: false },
+                { pubkey: ata, isWritable: true, isSigner: false },
+            );
+        }
+
+        const tx = await this.program.methods
+            .burnIncenseBatch(entries.map((e) => ({ incenseId: e.incenseId, amount: new BN(e.amount) })))
+            .accounts({
+                authority: user.publicKey,
+                templeTreasury: this.treasury,
+                templeConfig: this.templeConfigPda,
+                altar: this.getAltarPda(),
+                userState: this.getUserStatePda(user.publicKey),
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                userIncenseInventory: this.getUserIncenseInventoryPda(user.publicKey),
+                pendingAmulets: this.getPendingAmuletsPda(user.publicKey),
+                medalNftAccount: null,
+                meritAmulet: null,
+                meritAmuletTokenAccount: null,
+                slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .remainingAccounts(remainingAccounts)
+            .signers([user])
+            .rpc();
+
+        console.log(`Incense batch burned: ${tx}`);
+        return tx;
+    }
+
     // Original drawFortune method for localnet (no randomness account)
-    public async drawFortune(user: Keypair, useMerit: boolean = false, hasFortuneAmulet: boolean = false, hasProtectionAmulet: boolean = false): Promise<any> {
+    public async drawFortune(
+        user: Keypair,
+        useMerit: boolean = false,
+        fortuneAmuletMint: PublicKey | null = null,
+        protectionAmuletMint: PublicKey | null = null
+    ): Promise<any> {
         console.log(`User drawing fortune (localnet), use merit: ${useMerit}`);
 
-        const [userStatePda] = PublicKey.findProgramAddressSync(
-            [
-                Buffer.from("user_state"),
-                user.publicKey.toBuffer()
-            ],
-            this.program.programId
+        const accounts = await this.getDrawFortuneAccounts(
+            user,
+            fortuneAmuletMint,
+            protectionAmuletMint
         );
 
-        const [userIncenseStatePda] = PublicKey.findProgramAddressSync(
-            [
-                Buffer.from("user_incense"),
-                user.publicKey.toBuffer()
-            ],
-            this.program.programId
+        const tx = await this.program.methods
+            .drawFortune(useMerit)
+            .accounts(accounts)
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune drawn: ${tx}`);
+        return tx;
+    }
+
+    // Commit mock randomness and bind it to a fortune request in one transaction
+    public async requestFortune(
+        user: Keypair,
+        useMerit: boolean = false,
+        fortuneAmuletMint: PublicKey | null = null,
+        protectionAmuletMint: PublicKey | null = null
+    ): Promise<string> {
+        console.log(`User requesting fortune, use merit: ${useMerit}`);
+
+        const mockRandomness = this.getMockRandomnessPda(user.publicKey);
+        const commitIx = await this.program.methods
+            .commitMockRandomness()
+            .accounts({
+                authority: user.publicKey,
+                mockRandomness,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .instruction();
+
+        const tx = await this.program.methods
+            .requestFortune(useMerit)
+            .accounts({
+                user: user.publicKey,
+                userState: this.getUserStatePda(user.publicKey),
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                randomnessAccount: mockRandomness,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                fortuneAmulet: fortuneAmuletMint ? this.getAmuletNftPda(fortuneAmuletMint) : null,
+                fortuneAmuletTokenAccount: fortuneAmuletMint
+                    ? await anchor.utils.token.associatedAddress({ mint: fortuneAmuletMint, owner: user.publicKey })
+                    : null,
+                protectionAmulet: protectionAmuletMint ? this.getAmuletNftPda(protectionAmuletMint) : null,
+                protectionAmuletTokenAccount: protectionAmuletMint
+                    ? await anchor.utils.token.associatedAddress({ mint: protectionAmuletMint, owner: user.publicKey })
+                    : null,
+            } as any)
+            .preInstructions([commitIx])
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune requested: ${tx}`);
+        return tx;
+    }
+
+    // Reveal mock randomness and settle the pending fortune in one transaction
+    public async settleFortune(
+        user: Keypair,
+        randomValue: number[] = Array.from({ length: 32 }, (_, i) => i)
+    ): Promise<string> {
+        console.log(`User settling fortune`);
+
+        const mockRandomness = this.getMockRandomnessPda(user.publicKey);
+        const revealIx = await this.program.methods
+            .revealMockRandomness(randomValue)
+            .accounts({
+                authority: user.publicKey,
+                mockRandomness,
+            } as any)
+            .instruction();
+
+        const accounts = {
+            ...(await this.getDrawFortuneAccounts(user)),
+            randomnessAccount: mockRandomness,
+        };
+
+        const tx = await this.program.methods
+            .settleFortune()
+            .accounts(accounts)
+            .preInstructions([revealIx])
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune settled: ${tx}`);
+        return tx;
+    }
+
+    // Draw without minting, returns the draw index to claim the Fortune NFT with
+    public async drawOnly(user: Keypair, useMerit: boolean = false): Promise<number> {
+        console.log(`User drawing fortune without minting, use merit: ${useMerit}`);
+
+        const userIncenseState = await this.program.account.userIncenseState.fetch(
+            this.getUserIncenseStatePda(user.publicKey)
         );
+        const drawIndex = userIncenseState.totalDraws;
+
+        const tx = await this.program.methods
+            .drawOnly(useMerit)
+            .accounts({
+                user: user.publicKey,
+                userState: this.getUserStatePda(user.publicKey),
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                pendingAmulets: this.getPendingAmuletsPda(user.publicKey),
+                fortuneHistory: this.getFortuneHistoryPda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                fortuneTable: this.getFortuneTablePda(),
+                pendingFortune: this.getPendingFortunePda(user.publicKey, drawIndex),
+                fortuneAmulet: null,
+                fortuneAmuletTokenAccount: null,
+                protectionAmulet: null,
+                protectionAmuletTokenAccount: null,
+                slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune drawn without minting: ${tx}`);
+        return drawIndex;
+    }
+
+    // Mint the Fortune NFT of a pending fortune
+    public async claimFortuneNft(user: Keypair, drawIndex: number): Promise<string> {
+        console.log(`User claiming fortune NFT for draw ${drawIndex}`);
+
+        const fortuneNftMint = this.getFortuneNftMintPda(user.publicKey, drawIndex);
+
+        const tx = await this.program.methods
+            .claimFortuneNft(drawIndex)
+            .accounts({
+                user: user.publicKey,
+                pendingFortune: this.getPendingFortunePda(user.publicKey, drawIndex),
+                templeConfig: this.templeConfigPda,
+                metadataConfig: this.getMetadataConfigPda(),
+                fortuneNftAccount: this.getFortuneNftPda(user.publicKey, drawIndex),
+                fortuneNftMint,
+                fortuneNftTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: fortuneNftMint,
+                    owner: user.publicKey,
+                }),
+                fortuneNftMetadata: this.getMetadataPda(fortuneNftMint),
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                tokenMetadataProgram: this.TOKEN_METADATA_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            } as any)
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune NFT claimed: ${tx}`);
+        return tx;
+    }
+
+    // Resolve a bad fortune, paying with incense (held tokens, then prepaid balance) or merit
+    public async resolveFortune(
+        user: Keypair,
+        drawIndex: number,
+        payment: "incense" | "merit"
+    ): Promise<string> {
+        console.log(`User resolving fortune for draw ${drawIndex}, paying with ${payment}`);
+
+        const fortuneNftMint = this.getFortuneNftMintPda(user.publicKey, drawIndex);
+        let incenseAccounts: any = {
+            incenseMint: null,
+            incenseTokenAccount: null,
+            userIncenseInventory: null,
+        };
+        if (payment === "incense") {
+            const templeConfig = await this.getTempleConfig();
+            const incenseMint = this.getIncenseMintPda(templeConfig.dynamicConfig.fortuneResolve.incenseId);
+            incenseAccounts = {
+                incenseMint,
+                incenseTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: incenseMint,
+                    owner: user.publicKey,
+                }),
+                userIncenseInventory: this.getUserIncenseInventoryPda(user.publicKey),
+            };
+        }
+
+        const tx = await this.program.methods
+            .resolveFortune(drawIndex, payment === "incense" ? { incense: {} } : { merit: {} })
+            .accounts({
+                user: user.publicKey,
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                fortuneTable: this.getFortuneTablePda(),
+                metadataConfig: this.getMetadataConfigPda(),
+                fortuneNftAccount: this.getFortuneNftPda(user.publicKey, drawIndex),
+                fortuneNftMint,
+                fortuneNftTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: fortuneNftMint,
+                    owner: user.publicKey,
+                }),
+                fortuneNftMetadata: this.getMetadataPda(fortuneNftMint),
+                pendingReroll: this.getPendingRerollPda(user.publicKey),
+                ...incenseAccounts,
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                tokenMetadataProgram: this.TOKEN_METADATA_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune resolved: ${tx}`);
+        return tx;
+    }
+
+    // Request a reroll on committed mock randomness, then reveal it and settle the reroll
+    public async rerollFortune(
+        user: Keypair,
+        drawIndex: number,
+        randomValue: number[] = Array.from({ length: 32 }, (_, i) => i)
+    ): Promise<string> {
+        await this.requestReroll(user, drawIndex);
+        return this.settleReroll(user, drawIndex, randomValue);
+    }
+
+    // Pay for a reroll and commit its randomness, settled later by settleReroll
+    public async requestReroll(user: Keypair, drawIndex: number): Promise<string> {
+        console.log(`User rerolling fortune for draw ${drawIndex}`);
+
+        const fortuneNftMint = this.getFortuneNftMintPda(user.publicKey, drawIndex);
+        const mockRandomness = this.getMockRandomnessPda(user.publicKey);
+
+        const commitIx = await this.program.methods
+            .commitMockRandomness()
+            .accounts({
+                authority: user.publicKey,
+                mockRandomness,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .instruction();
+
+        const requestTx = await this.program.methods
+            .requestReroll(drawIndex)
+            .accounts({
+                user: user.publicKey,
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                fortuneHistory: this.getFortuneHistoryPda(user.publicKey),
+                pendingReroll: this.getPendingRerollPda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                fortuneNftAccount: this.getFortuneNftPda(user.publicKey, drawIndex),
+                fortuneNftMint,
+                fortuneNftTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: fortuneNftMint,
+                    owner: user.publicKey,
+                }),
+                randomnessAccount: mockRandomness,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .preInstructions([commitIx])
+            .signers([user])
+            .rpc();
+        console.log(`Fortune reroll requested: ${requestTx}`);
+        return requestTx;
+    }
+
+    public async settleReroll(
+        user: Keypair,
+        drawIndex: number,
+        randomValue: number[] = Array.from({ length: 32 }, (_, i) => i)
+    ): Promise<string> {
+        const fortuneNftMint = this.getFortuneNftMintPda(user.publicKey, drawIndex);
+        const mockRandomness = this.getMockRandomnessPda(user.publicKey);
+
+        const revealIx = await this.program.methods
+            .revealMockRandomness(randomValue)
+            .accounts({
+                authority: user.publicKey,
+                mockRandomness,
+            } as any)
+            .instruction();
+
+        const tx = await this.program.methods
+            .settleReroll(drawIndex)
+            .accounts({
+                user: user.publicKey,
+                userState: this.getUserStatePda(user.publicKey),
+                fortuneHistory: this.getFortuneHistoryPda(user.publicKey),
+                pendingReroll: this.getPendingRerollPda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                fortuneTable: this.getFortuneTablePda(),
+                metadataConfig: this.getMetadataConfigPda(),
+                fortuneNftAccount: this.getFortuneNftPda(user.publicKey, drawIndex),
+                fortuneNftMint,
+                fortuneNftTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: fortuneNftMint,
+                    owner: user.publicKey,
+                }),
+                fortuneNftMetadata: this.getMetadataPda(fortuneNftMint),
+                randomnessAccount: mockRandomness,
+                tokenMetadataProgram: this.TOKEN_METADATA_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .preInstructions([revealIx])
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune rerolled: ${tx}`);
+        return tx;
+    }
+
+    public async releaseFortune(user: Keypair, drawIndex: number): Promise<string> {
+        console.log(`User releasing fortune for draw ${drawIndex}`);
+
+        const fortuneNftMint = this.getFortuneNftMintPda(user.publicKey, drawIndex);
+
+        const tx = await this.program.methods
+            .releaseFortune(drawIndex)
+            .accounts({
+                user: user.publicKey,
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                fortuneNftAccount: this.getFortuneNftPda(user.publicKey, drawIndex),
+                fortuneNftMint,
+                fortuneNftTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: fortuneNftMint,
+                    owner: user.publicKey,
+                }),
+                pendingReroll: this.getPendingRerollPda(user.publicKey),
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune released: ${tx}`);
+        return tx;
+    }
+
+    // Trade one Fortune NFT per table category, in FortuneResult order, for a Complete Fortune NFT.
+    // Returns the index of the claimed set
+    public async claimFortuneSet(
+        user: Keypair,
+        fortunes: { drawer: PublicKey; drawIndex: number }[]
+    ): Promise<number> {
+        console.log(`User claiming a fortune set of ${fortunes.length} NFTs`);
+
+        const remainingAccounts = [];
+        for (const { drawer, drawIndex } of fortunes) {
+            const mint = this.getFortuneNftMintPda(drawer, drawIndex);
+            remainingAccounts.push(
+                { pubkey: this.getFortuneNftPda(drawer, drawIndex), isSigner: false, isWritable: true },
+                { pubkey: mint, isSigner: false, isWritable: true },
+                {
+                    pubkey: await anchor.utils.token.associatedAddress({ mint, owner: user.publicKey }),
+                    isSigner: false,
+                    isWritable: true,
+                },
+            );
+        }
+
+        const fortuneHistoryPda = this.getFortuneHistoryPda(user.publicKey);
+        const fortuneHistory = await this.program.account.fortuneHistory.fetchNullable(fortuneHistoryPda);
+        const setIndex = fortuneHistory ? fortuneHistory.setsClaimed : 0;
+
+        const setNftMint = this.getFortuneSetNftMintPda(user.publicKey, setIndex);
+        const tx = await this.program.methods
+            .claimFortuneSet()
+            .accounts({
+                user: user.publicKey,
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                templeConfig: this.templeConfigPda,
+                fortuneTable: this.getFortuneTablePda(),
+                metadataConfig: this.getMetadataConfigPda(),
+                fortuneHistory: fortuneHistoryPda,
+                fortuneSetClaim: this.getFortuneSetClaimPda(user.publicKey, setIndex),
+                setNftMint,
+                setNftTokenAccount: await anchor.utils.token.associatedAddress({
+                    mint: setNftMint,
+                    owner: user.publicKey,
+                }),
+                setNftMetadata: this.getMetadataPda(setNftMint),
+                tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+                tokenMetadataProgram: this.TOKEN_METADATA_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
+                systemProgram: anchor.web3.SystemProgram.programId,
+                rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            } as any)
+            .remainingAccounts(remainingAccounts)
+            .signers([user])
+            .rpc();
+
+        console.log(`Fortune set claimed: ${tx}`);
+        return setIndex;
+    }
+
+    private async getDrawFortuneAccounts(
+        user: Keypair,
+        fortuneAmuletMint: PublicKey | null = null,
+        protectionAmuletMint: PublicKey | null = null
+    ): Promise<any> {
+        const userIncenseStatePda = this.getUserIncenseStatePda(user.publicKey);
 
         // Get current total_draws for fortune NFT PDA
         const userIncenseState = await this.program.account.userIncenseState.fetch(userIncenseStatePda);
         const totalDraws = userIncenseState.totalDraws; // Use current value, will be incremented in the instruction
 
         const fortuneNftPda = this.getFortuneNftPda(user.publicKey, totalDraws);
-        const fortuneNftMintPda = PublicKey.findProgramAddressSync(
-            [
-                Buffer.from("fortune_nft_mint"),
-                this.templeConfigPda.toBuffer(),
-                user.publicKey.toBuffer(),
-                Buffer.from(totalDraws.toString())
-            ],
-            this.program.programId
-        )[0];
+        const fortuneNftMintPda = this.getFortuneNftMintPda(user.publicKey, totalDraws);
 
         const fortuneNftTokenAccount = await anchor.utils.token.associatedAddress({
             mint: fortuneNftMintPda,
@@ -502,35 +1247,36 @@ export class TestContext {
 
         const metaAccount = this.getMetadataPda(fortuneNftMintPda);
 
-        // Prepare accounts object (no randomness account for localnet)
-        const accounts: any = {
+        return {
             user: user.publicKey,
-            userState: userStatePda,
+            userState: this.getUserStatePda(user.publicKey),
             userIncenseState: userIncenseStatePda,
+            pendingAmulets: this.getPendingAmuletsPda(user.publicKey),
+            fortuneHistory: this.getFortuneHistoryPda(user.publicKey),
             templeConfig: this.templeConfigPda,
+            fortuneTable: this.getFortuneTablePda(),
+            metadataConfig: this.getMetadataConfigPda(),
             fortuneNftAccount: fortuneNftPda,
             fortuneNftMint: fortuneNftMintPda,
             fortuneNftTokenAccount,
             fortuneNftMetadata: metaAccount,
+            fortuneAmulet: fortuneAmuletMint ? this.getAmuletNftPda(fortuneAmuletMint) : null,
+            fortuneAmuletTokenAccount: fortuneAmuletMint
+                ? await anchor.utils.token.associatedAddress({ mint: fortuneAmuletMint, owner: user.publicKey })
+                : null,
+            protectionAmulet: protectionAmuletMint ? this.getAmuletNftPda(protectionAmuletMint) : null,
+            protectionAmuletTokenAccount: protectionAmuletMint
+                ? await anchor.utils.token.associatedAddress({ mint: protectionAmuletMint, owner: user.publicKey })
+                : null,
+            slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
             tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
             tokenMetadataProgram: this.TOKEN_METADATA_PROGRAM_ID,
             associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         };
-
-        const tx = await this.program.methods
-            .drawFortune(useMerit, hasFortuneAmulet, hasProtectionAmulet)
-            .accounts(accounts)
-            .signers([user])
-            .rpc();
-
-        console.log(`Fortune drawn: ${tx}`);
-        return tx;
     }
 
-
-
     public async mintBuddhaNft(user: Keypair): Promise<string> {
         console.log(`User minting Buddha NFT: ${user.publicKey.toString()}`);
 
@@ -577,7 +1323,7 @@ export class TestContext {
                 nftAssociatedTokenAccount,
                 buddhaNftAccount: buddhaNftPda,
                 tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 tokenMetadataProgram: new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"),
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -639,8 +1385,10 @@ export class TestContext {
                 wishTowerAccount: this.getWishTowerPda(user.publicKey),
                 userState: userStatePda,
                 userIncenseState: userIncenseStatePda,
+                pendingAmulets: this.getPendingAmuletsPda(user.publicKey),
                 templeConfig: this.templeConfigPda,
                 globalStats: this.getGlobalStatsPda(),
+                slotHashes: anchor.web3.SYSVAR_SLOT_HASHES_PUBKEY,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([user])
@@ -720,7 +1468,7 @@ export class TestContext {
                 stakedNftTokenAccount,
                 templeConfig: this.templeConfigPda,
                 tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([user])
@@ -784,7 +1532,7 @@ export class TestContext {
                 stakedNftTokenAccount,
                 templeConfig: this.templeConfigPda,
                 tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
             })
             .signers([user])
@@ -794,33 +1542,49 @@ export class TestContext {
         return tx;
     }
 
-    public async shareFortune(user: Keypair, shareHash: number[]): Promise<string> {
-        console.log(`User sharing fortune with hash: ${shareHash.slice(0, 4).join(',')}...`);
-
-        const [userStatePda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("user_state"), user.publicKey.toBuffer()],
-            this.program.programId
-        );
-
-        const [userIncenseStatePda] = PublicKey.findProgramAddressSync(
-            [Buffer.from("user_incense"), user.publicKey.toBuffer()],
-            this.program.programId
-        );
+    public async shareFortune(
+        user: Keypair,
+        serialNumber: number,
+        shareHash: number[],
+        preInstructions: anchor.web3.TransactionInstruction[] = []
+    ): Promise<string> {
+        console.log(`User sharing fortune #${serialNumber} with hash: ${shareHash.slice(0, 4).join(',')}...`);
 
         const tx = await this.program.methods
-            .shareFortune(shareHash)
+            .shareFortune(serialNumber, shareHash)
             .accounts({
                 user: user.publicKey,
-                userState: userStatePda,
-                userIncenseState: userIncenseStatePda,
+                userState: this.getUserStatePda(user.publicKey),
+                userIncenseState: this.getUserIncenseStatePda(user.publicKey),
+                fortuneHistory: this.getFortuneHistoryPda(user.publicKey),
+                shareRecord: this.getShareRecordPda(user.publicKey, serialNumber),
                 templeConfig: this.templeConfigPda,
-            })
+                instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .preInstructions(preInstructions)
             .signers([user])
             .rpc();
 
         console.log(`Fortune shared: ${tx}`);
         return tx;
     }
+
+    // Message the share attester signs: user, serial number (LE) and share hash
+    public getShareAttestationMessage(userPubkey: PublicKey, serialNumber: number, shareHash: number[]): Buffer {
+        const serial = Buffer.alloc(4);
+        serial.writeUInt32LE(serialNumber);
+        return Buffer.concat([userPubkey.toBuffer(), serial, Buffer.from(shareHash)]);
+    }
+
+    public getShareRecordPda(userPubkey: PublicKey, serialNumber: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("share_record"), userPubkey.toBuffer(), Buffer.from(serialNumber.toString())],
+            this.program.programId
+        );
+        return pda;
+    }
+
     public getUserIncenseStatePda(userPubkey: PublicKey): PublicKey {
         const [pda] = PublicKey.findProgramAddressSync(
             [Buffer.from("user_incense"), userPubkey.toBuffer()],
@@ -829,6 +1593,91 @@ export class TestContext {
         return pda;
     }
 
+    public getMockRandomnessPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("mock_randomness"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getMockPriceFeedPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("mock_price_feed"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    // Publish a mock SOL/USD price (price * 10^exponent USD per SOL), returns the feed account
+    public async setMockPrice(
+        price: number,
+        conf: number,
+        exponent: number = -8,
+        authority: Keypair = this.owner
+    ): Promise<PublicKey> {
+        const tx = await this.program.methods
+            .setMockPrice(new BN(price), new BN(conf), exponent)
+            .accounts({
+                authority: authority.publicKey,
+                mockPriceFeed: this.getMockPriceFeedPda(authority.publicKey),
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Mock price set: ${tx}`);
+        return this.getMockPriceFeedPda(authority.publicKey);
+    }
+
+    public getUserIncenseInventoryPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("user_incense_inventory"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getFortuneHistoryPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("fortune_history"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getUserStreakPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("user_streak"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getDedicationPoolPda(): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("dedication_pool"), this.templeConfigPda.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    // Incense balance held in the user's incense inventory
+    public async getIncenseBalance(userPubkey: PublicKey, incenseId: number): Promise<string | undefined> {
+        const inventory = await this.program.account.userIncenseInventory.fetch(
+            this.getUserIncenseInventoryPda(userPubkey)
+        );
+        return inventory.slots.find((s: any) => s.incenseId === incenseId)?.balance.toString();
+    }
+
+    public getPendingAmuletsPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_amulets"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
     public getUserMedalStatePda(userPubkey: PublicKey): PublicKey {
         const [pda] = PublicKey.findProgramAddressSync(
             [Buffer.from("user_medal"), userPubkey.toBuffer()],
@@ -891,18 +1740,81 @@ export class TestContext {
         return pda;
     }
 
-    public async mintAmuletNft(user: Keypair, amuletType: number, source: number): Promise<string> {
-        console.log(`User minting amulet NFT with type: ${amuletType}, source: ${source}`);
+    public getFortuneNftMintPda(userPubkey: PublicKey, totalDraws: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("fortune_nft_mint"),
+                this.templeConfigPda.toBuffer(),
+                userPubkey.toBuffer(),
+                Buffer.from(totalDraws.toString())
+            ],
+            this.program.programId
+        );
+        return pda;
+    }
 
-        const templeConfig = await this.getTempleConfig();
-        const [nftMintPda] = PublicKey.findProgramAddressSync(
+    public getPendingFortunePda(userPubkey: PublicKey, drawIndex: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_fortune"), userPubkey.toBuffer(), Buffer.from(drawIndex.toString())],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getPendingRerollPda(userPubkey: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("pending_reroll"), userPubkey.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getFortuneSetClaimPda(userPubkey: PublicKey, setIndex: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("fortune_set_claim"), userPubkey.toBuffer(), Buffer.from(setIndex.toString())],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getFortuneSetNftMintPda(userPubkey: PublicKey, setIndex: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [
+                Buffer.from("fortune_set_nft_mint"),
+                this.templeConfigPda.toBuffer(),
+                userPubkey.toBuffer(),
+                Buffer.from(setIndex.toString()),
+            ],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getAmuletNftPda(amuletMint: PublicKey): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("amulet_nft"), Buffer.from("account"), amuletMint.toBuffer()],
+            this.program.programId
+        );
+        return pda;
+    }
+
+    public getAmuletMintPda(userPubkey: PublicKey, totalAmulets: number): PublicKey {
+        const [pda] = PublicKey.findProgramAddressSync(
             [
                 Buffer.from("amulet_nft"),
-                user.publicKey.toBuffer(),
-                Buffer.from(templeConfig.totalAmulets.toString(), 'utf8'),
+                userPubkey.toBuffer(),
+                Buffer.from(totalAmulets.toString(), 'utf8'),
             ],
             this.program.programId
         );
+        return pda;
+    }
+
+    public async mintAmuletNft(user: Keypair, amuletType: number, source: number): Promise<string> {
+        console.log(`User minting amulet NFT with type: ${amuletType}, source: ${source}`);
+
+        const templeConfig = await this.getTempleConfig();
+        const nftMintPda = this.getAmuletMintPda(user.publicKey, templeConfig.totalAmulets);
 
         const nftAssociatedTokenAccount = await anchor.utils.token.associatedAddress({
             mint: nftMintPda,
@@ -918,7 +1830,9 @@ export class TestContext {
                 templeConfig: this.templeConfigPda,
                 globalStats: this.getGlobalStatsPda(),
                 userState: this.getUserStatePda(user.publicKey),
+                pendingAmulets: this.getPendingAmuletsPda(user.publicKey),
                 nftMintAccount: nftMintPda,
+                amuletNftAccount: this.getAmuletNftPda(nftMintPda),
                 nftAssociatedTokenAccount: nftAssociatedTokenAccount,
                 metaAccount: metaAccount,
                 tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -934,10 +1848,178 @@ export class TestContext {
         return tx;
     }
 
-    private async getTempleConfig() {
+    public async getTempleConfig() {
         return await this.program.account.templeConfig.fetch(this.templeConfigPda);
     }
 
+    public async getFortuneTiers(): Promise<any[]> {
+        const fortuneTable = await this.program.account.fortuneTable.fetch(this.getFortuneTablePda());
+        return fortuneTable.tiers;
+    }
+
+    public async updateIncenseTypes(
+        incenseTypes: any[],
+        authority: Keypair = this.owner
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .updateIncenseTypes(incenseTypes)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Incense types updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateDayBoundary(
+        utcOffsetMinutes: number,
+        dayBoundaryHour: number,
+        authority: Keypair = this.owner
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .updateDayBoundary(utcOffsetMinutes, dayBoundaryHour)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Day boundary updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateStreakMultipliers(
+        streakMultipliers: { minDays: number; bonusPercent: number }[],
+        authority: Keypair = this.owner
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .updateStreakMultipliers(streakMultipliers)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Streak multipliers updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateActivityEconomy(activityEconomy: any, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updateActivityEconomy(activityEconomy)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Activity economy updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateFortuneResolve(fortuneResolve: any, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updateFortuneResolve(fortuneResolve)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Fortune resolve config updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateFortuneReroll(fortuneReroll: any, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updateFortuneReroll(fortuneReroll)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Fortune reroll config updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateFortuneSet(fortuneSet: any, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updateFortuneSet(fortuneSet)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Fortune set config updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateFortuneRelease(fortuneRelease: any, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updateFortuneRelease(fortuneRelease)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Fortune release config updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateDedication(dedication: any, authority: Keypair = this.owner): Promise<string> {
+        const tx = await this.program.methods
+            .updateDedication(dedication)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Dedication config updated: ${tx}`);
+        return tx;
+    }
+
+    public async updateFortuneTiers(
+        offset: number,
+        totalTiers: number,
+        tiers: any[],
+        authority: Keypair = this.owner
+    ): Promise<string> {
+        const tx = await this.program.methods
+            .updateFortuneTiers(offset, totalTiers, tiers)
+            .accounts({
+                templeConfig: this.templeConfigPda,
+                fortuneTable: this.getFortuneTablePda(),
+                authority: authority.publicKey,
+                systemProgram: anchor.web3.SystemProgram.programId,
+            } as any)
+            .signers([authority])
+            .rpc();
+
+        console.log(`Fortune tiers updated: ${tx}`);
+        return tx;
+    }
+
     public async getAssociatedTokenAddress(mint: PublicKey, owner: PublicKey): Promise<PublicKey> {
         return await anchor.utils.token.associatedAddress({
             mint,
@@ -1028,7 +2110,7 @@ export class TestContext {
                 userTokenAccount: userTokenAccount,
                 user: userPublicKey,
                 tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
-                associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+                associatedTokenProgram: this.ASSOCIATED_TOKEN_PROGRAM_ID,
                 systemProgram: anchor.web3.SystemProgram.programId,
                 rent: anchor.web3.SYSVAR_RENT_PUBKEY,
             })